@@ -0,0 +1,35 @@
+/*
+* Request size and time limits for the API.
+*
+* Request bodies above the configured size are rejected with 413 and
+* handlers running past the configured timeout are aborted with 408. The
+* underlying layers answer with plain text or an empty body, so both
+* responses are rewritten as ErrorResponse JSON.
+*/
+
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use super::routes::common::{payload_too_large_error, request_timeout_error};
+
+/*
+* Renders 408 and 413 responses that are not already JSON as ErrorResponse.
+*
+* @param response Response produced by the inner layers
+* @return The response, with limit errors rendered as JSON
+*/
+pub async fn render_limit_errors(response: Response) -> Response {
+    let is_json = response.headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if is_json {
+        return response;
+    }
+
+    match response.status() {
+        StatusCode::PAYLOAD_TOO_LARGE => payload_too_large_error("Request body exceeds the maximum size").into_response(),
+        StatusCode::REQUEST_TIMEOUT => request_timeout_error("Request timed out").into_response(),
+        _ => response,
+    }
+}
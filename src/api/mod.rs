@@ -4,37 +4,224 @@
 
 pub mod routes;
 pub mod openapi;
+pub mod rate_limit;
+pub mod limits;
+pub mod protobuf;
 
-use axum::{Router, routing::get};
+use std::sync::{atomic::AtomicU64, Arc};
+use std::time::Duration;
+use axum::{extract::DefaultBodyLimit, http::{header, HeaderName, HeaderValue, Method}, middleware, Router, routing::{get, post}};
+use metrics_exporter_prometheus::PrometheusHandle;
 use sqlx::{Pool, Postgres};
-use tower_http::cors::{CorsLayer, Any};
-use utoipa::OpenApi;
+use tokio::sync::{broadcast, mpsc};
+use tower_http::{compression::CompressionLayer, cors::{AllowOrigin, CorsLayer, Any}, limit::RequestBodyLimitLayer, timeout::TimeoutLayer};
 use utoipa_swagger_ui::SwaggerUi;
+use crate::client::{last_error::LastErrorTracker, resync::ResyncJob, throughput::ThroughputMeter};
+use crate::models::{block::BlockSummary, transaction::TransactionSummary};
+use rate_limit::RateLimiter;
+
+/*
+* Deployment settings for the API router.
+*/
+#[derive(Debug, Clone)]
+pub struct ApiConfig {
+    /// Requests per second allowed for each client IP
+    pub rate_limit_per_sec: u32,
+
+    /// Whether the /api/debug routes are mounted
+    pub enable_debug_endpoints: bool,
+
+    /// Key required by the /api/admin routes, which are only mounted when it is set
+    pub api_key: Option<String>,
+
+    /// Origins allowed by CORS; any origin when None
+    pub cors_allowed_origins: Option<Vec<String>>,
+
+    /// Path prefix all routes are mounted under, e.g. "/indexer"; the root when None
+    pub base_path: Option<String>,
+
+    /// Largest request body accepted, in bytes
+    pub max_body_bytes: usize,
+
+    /// Time a request may take before it is aborted with 408
+    pub request_timeout: Duration,
+}
 
 /*
 * Creates and configures the API router.
+*
+* The router must be served with connect info so the rate limiter can
+* key requests by client address. Debug and admin routes are only mounted
+* when enabled, admin routes only with an API key. Responses are compressed when the client sends Accept-Encoding.
+* With a base path, every route including Swagger UI and the OpenAPI
+* document is served under it. Request bodies are capped at the configured
+* size, and every route except the streaming ones at the configured timeout.
 */
-pub fn create_router(pool: Pool<Postgres>) -> Router {
-    let api_doc = openapi::ApiDoc::openapi();
+#[allow(clippy::too_many_arguments)]
+pub fn create_router(
+    pool: Pool<Postgres>,
+    metrics_handle: PrometheusHandle,
+    block_tx: broadcast::Sender<BlockSummary>,
+    tx_feed: broadcast::Sender<TransactionSummary>,
+    throughput: ThroughputMeter,
+    last_error: LastErrorTracker,
+    resync_tx: mpsc::Sender<ResyncJob>,
+    chain_tip: Arc<AtomicU64>,
+    config: ApiConfig,
+) -> Router {
+    let base_path = config.base_path.as_deref().unwrap_or_default();
+    let api_doc = openapi::api_doc(config.base_path.as_deref());
+
+    // Kept outside the compression layer so upgrade responses and event streams are left untouched
+    let ws_routes = Router::new()
+        .route("/api/ws/blocks", get(routes::ws::stream_blocks))
+        .with_state(block_tx)
+        .merge(
+            Router::new()
+                .route("/api/sse/transactions", get(routes::sse::stream_transactions))
+                .with_state(tx_feed)
+        );
+
+    let throughput_routes = Router::new()
+        .route("/stats/indexer-throughput", get(routes::stats::get_indexer_throughput))
+        .with_state(throughput);
+
+    let export_routes = Router::new()
+        .route("/blocks/export.ndjson", get(routes::blocks::export_blocks))
+        .with_state(routes::blocks::ExportState::new(pool.clone()));
 
-    let api_routes = Router::new()
+    let mut api_routes = Router::new()
         .route("/blocks", get(routes::blocks::get_latest_blocks))
+        .route("/blocks/latest", get(routes::blocks::get_latest_block))
+        .route("/blocks/first", get(routes::blocks::get_first_block))
+        .route("/blocks/range", get(routes::blocks::get_blocks_in_time_range))
+        .route("/blocks/batch", post(routes::blocks::get_blocks_by_heights))
+        .route("/blocks/busiest", get(routes::blocks::get_busiest_blocks))
         .route("/blocks/:height", get(routes::blocks::get_block_by_height))
+        .route("/blocks/:height/raw", get(routes::blocks::get_block_raw))
+        .route("/blocks/:height/tx-count", get(routes::blocks::get_block_tx_count))
         .route("/stats", get(routes::stats::get_chain_stats))
+        .route("/stats/network-overview", get(routes::stats::get_network_overview))
+        .route("/stats/burn", get(routes::stats::get_burn_stats))
+        .route("/stats/cumulative-burn", get(routes::stats::get_cumulative_burn))
+        .route("/stats/tx-daily", get(routes::stats::get_daily_transactions))
+        .route("/stats/tx-count", get(routes::stats::get_transaction_counts_in_timezone))
+        .route("/stats/tx-by-height-range", get(routes::stats::get_tx_by_height_range))
+        .route("/stats/action-breakdown", get(routes::stats::get_action_breakdown))
+        .route("/stats/action-distribution", get(routes::stats::get_action_distribution))
+        .route("/stats/sync-history", get(routes::stats::get_sync_history))
+        .route("/stats/block-sizes", get(routes::stats::get_block_size_stats))
+        .route("/stats/total-fees", get(routes::stats::get_total_fees))
+        .route("/stats/block-time-anomalies", get(routes::stats::get_block_time_anomalies))
+        .route("/resolve/height-at", get(routes::resolve::get_height_at))
+        .route("/resolve/time-at", get(routes::resolve::get_time_at))
+        .route("/validators", get(routes::validators::get_validators))
+        .route("/validators/search", get(routes::validators::search_validators))
+        .route("/validators/:address/blocks", get(routes::validators::get_proposer_blocks))
         .route("/transactions", get(routes::transactions::get_latest_transactions))
+        .route("/transactions/ibc", get(routes::transactions::get_ibc_transfers))
+        .route("/transactions/staking", get(routes::transactions::get_staking_transactions))
+        .route("/transactions/staking/net-delegations", get(routes::transactions::get_net_delegations))
+        .route("/transactions/large", get(routes::transactions::get_large_transactions))
+        .route("/transactions/:tx_hash/decoded", get(routes::transactions::get_decoded_transaction))
         .route("/blocks/:height/transactions", get(routes::transactions::get_transactions_by_block_height))
+        .route("/coverage", get(routes::stats::get_coverage))
+        .route("/health", get(routes::common::health_check))
+        .with_state(pool.clone())
+        .merge(export_routes)
+        .merge(throughput_routes);
+
+    if config.enable_debug_endpoints {
+        let mut debug_routes = Router::new()
+            .route("/debug/pool", get(routes::debug::get_pool_stats))
+            .route("/debug/failed-blocks", get(routes::debug::get_failed_blocks))
+            .with_state(pool.clone())
+            .merge(
+                Router::new()
+                    .route("/debug/last-error", get(routes::debug::get_last_error))
+                    .with_state(last_error)
+            );
+        if let Some(api_key) = config.api_key.as_deref() {
+            let admin_state = routes::admin::AdminState {
+                resync_tx,
+                api_key: Arc::from(api_key),
+                chain_tip,
+            };
+            debug_routes = debug_routes.merge(
+                Router::new()
+                    .route("/admin/resync", post(routes::admin::resync_range))
+                    .with_state(admin_state)
+            );
+        }
+        api_routes = api_routes.merge(debug_routes);
+    }
+
+    let health_routes = Router::new()
+        .route("/health", get(routes::common::health_check))
         .with_state(pool);
 
-    Router::new()
+    let metrics_routes = Router::new()
+        .route("/metrics", get(routes::metrics::get_metrics))
+        .with_state(metrics_handle);
+
+    let routes = Router::new()
         .nest("/api", api_routes)
+        .merge(health_routes)
+        .merge(metrics_routes);
+
+    // Swagger UI registers its routes from the absolute paths it serves, so it is not nested
+    with_base_path(base_path, routes)
         .merge(
-            SwaggerUi::new("/swagger-ui")
-                .url("/api-docs/openapi.json", api_doc)
-        )
-        .layer(
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods(Any)
-                .allow_headers(Any)
+            SwaggerUi::new(format!("{}/swagger-ui", base_path))
+                .url(format!("{}/api-docs/openapi.json", base_path), api_doc)
         )
+        .layer(TimeoutLayer::new(config.request_timeout))
+        .layer(CompressionLayer::new())
+        .merge(with_base_path(base_path, ws_routes))
+        // Disables axum's own 2 MB default so the configured limit is the only one
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(config.max_body_bytes))
+        .layer(middleware::map_response(limits::render_limit_errors))
+        .layer(middleware::from_fn_with_state(
+            RateLimiter::new(config.rate_limit_per_sec),
+            rate_limit::limit_requests,
+        ))
+        .layer(cors_layer(config.cors_allowed_origins.as_deref()))
+}
+
+/*
+* Mounts routes under the base path, or leaves them at the root when it is empty.
+*/
+fn with_base_path(base_path: &str, routes: Router) -> Router {
+    if base_path.is_empty() {
+        routes
+    } else {
+        Router::new().nest(base_path, routes)
+    }
+}
+
+/*
+* Builds the CORS layer.
+*
+* Restricts methods to those the API serves. Origins that are not valid
+* header values are skipped. Pagination headers are exposed to browser clients.
+*
+* @param allowed_origins Explicit origin allowlist, or None to allow any origin
+*/
+fn cors_layer(allowed_origins: Option<&[String]>) -> CorsLayer {
+    let allow_origin = match allowed_origins {
+        Some(origins) => AllowOrigin::list(
+            origins.iter().filter_map(|origin| HeaderValue::from_str(origin).ok())
+        ),
+        None => AllowOrigin::from(Any),
+    };
+
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_headers(Any)
+        .expose_headers([
+            HeaderName::from_static(routes::pagination::TOTAL_COUNT_HEADER),
+            header::LINK,
+        ])
 }
@@ -5,7 +5,7 @@
 * including paths, request parameters, and response schemas.
 */
 
-use utoipa::{OpenApi, ToSchema};
+use utoipa::{openapi::server::Server, OpenApi, ToSchema};
 use chrono::{DateTime, Utc};
 
 #[derive(ToSchema)]
@@ -17,14 +17,51 @@ struct DateTimeSchema(DateTime<Utc>);
     paths(
         // Block routes
         crate::api::routes::blocks::get_latest_blocks,
+        crate::api::routes::blocks::get_latest_block,
+        crate::api::routes::blocks::get_first_block,
         crate::api::routes::blocks::get_block_by_height,
+        crate::api::routes::blocks::get_block_raw,
+        crate::api::routes::blocks::get_block_tx_count,
+        crate::api::routes::blocks::get_blocks_in_time_range,
+        crate::api::routes::blocks::get_blocks_by_heights,
+        crate::api::routes::blocks::export_blocks,
+        crate::api::routes::blocks::get_busiest_blocks,
+        crate::api::routes::resolve::get_height_at,
+        crate::api::routes::resolve::get_time_at,
 
         // Transaction routes
         crate::api::routes::transactions::get_latest_transactions,
         crate::api::routes::transactions::get_transactions_by_block_height,
+        crate::api::routes::transactions::get_ibc_transfers,
+        crate::api::routes::transactions::get_staking_transactions,
+        crate::api::routes::transactions::get_net_delegations,
+        crate::api::routes::transactions::get_large_transactions,
+        crate::api::routes::transactions::get_decoded_transaction,
 
         // Statistics routes
         crate::api::routes::stats::get_chain_stats,
+        crate::api::routes::stats::get_network_overview,
+        crate::api::routes::stats::get_coverage,
+        crate::api::routes::stats::get_daily_transactions,
+        crate::api::routes::stats::get_transaction_counts_in_timezone,
+        crate::api::routes::stats::get_tx_by_height_range,
+        crate::api::routes::stats::get_action_breakdown,
+        crate::api::routes::stats::get_sync_history,
+        crate::api::routes::stats::get_block_time_anomalies,
+        crate::api::routes::stats::get_cumulative_burn,
+        crate::api::routes::stats::get_action_distribution,
+        crate::api::routes::stats::get_indexer_throughput,
+        crate::api::routes::stats::get_burn_stats,
+        crate::api::routes::stats::get_block_size_stats,
+        crate::api::routes::stats::get_total_fees,
+
+        // Validator routes
+        crate::api::routes::validators::get_validators,
+        crate::api::routes::validators::get_proposer_blocks,
+        crate::api::routes::validators::search_validators,
+
+        // Health routes
+        crate::api::routes::common::health_check,
     ),
     components(
         schemas(
@@ -32,11 +69,19 @@ struct DateTimeSchema(DateTime<Utc>);
             crate::models::block::StoredBlock,
             crate::models::block::BlockSummary,
             crate::models::block::BlockList,
+            crate::models::block::BusiestBlockList,
+            crate::models::block::BlockTxCount,
+            crate::models::block::BlockTime,
+            crate::api::routes::blocks::BlockBatchRequest,
 
             // Transaction schemas
             crate::models::transaction::Transaction,
             crate::models::transaction::TransactionSummary,
             crate::models::transaction::TransactionList,
+            crate::models::transaction::SortOrder,
+            crate::models::transaction::DecodedTransaction,
+            crate::models::transaction::NetDelegation,
+            crate::models::transaction::NetDelegationList,
 
             // Stats schemas
             crate::models::stats::StatsResponse,
@@ -44,10 +89,37 @@ struct DateTimeSchema(DateTime<Utc>);
             crate::models::stats::TransactionStats,
             crate::models::stats::BurnStats,
             crate::models::stats::ChartPoint,
+            crate::models::stats::BurnChartPoint,
+            crate::models::stats::CumulativeBurn,
+            crate::models::stats::ProposerStats,
+            crate::models::stats::ProposerStatsList,
+            crate::models::stats::ProposerMatch,
+            crate::models::stats::ProposerMatchList,
+            crate::models::stats::DailyTransactionCounts,
+            crate::models::stats::TimezoneTransactionCounts,
+            crate::models::stats::HeightBucket,
+            crate::models::stats::HeightBucketList,
+            crate::models::stats::ActionCount,
+            crate::models::stats::ActionBreakdown,
+            crate::models::stats::ActionShare,
+            crate::models::stats::ActionDistribution,
+            crate::models::stats::IndexerThroughput,
+            crate::models::stats::BlockSizeStats,
+            crate::models::stats::FeeTotal,
+            crate::models::stats::TotalFees,
+            crate::models::stats::NetworkOverview,
+            crate::models::stats::Coverage,
+            crate::models::stats::SyncStatusPoint,
+            crate::models::stats::SyncHistory,
+            crate::models::stats::BlockTimeAnomaly,
+            crate::models::stats::BlockTimeAnomalyList,
 
             // Error response schema
             crate::api::routes::common::ErrorResponse,
 
+            // Health schema
+            crate::api::routes::common::HealthResponse,
+
             // Custom types
             DateTimeSchema
         )
@@ -55,7 +127,9 @@ struct DateTimeSchema(DateTime<Utc>);
     tags(
         (name = "Blocks", description = "Block data endpoints"),
         (name = "Transactions", description = "Transaction data endpoints"),
-        (name = "Statistics", description = "Blockchain statistics endpoints")
+        (name = "Statistics", description = "Blockchain statistics endpoints"),
+        (name = "Validators", description = "Block proposer endpoints"),
+        (name = "Health", description = "Service health endpoints")
     ),
     info(
         title = "Penumbra Blockchain API",
@@ -72,3 +146,20 @@ struct DateTimeSchema(DateTime<Utc>);
     )
 )]
 pub struct ApiDoc;
+
+/*
+* Builds the OpenAPI document for the configured mount point.
+*
+* When the API is served under a path prefix, the prefix is listed as the
+* document's server so Swagger UI requests reach the prefixed routes.
+*
+* @param base_path Path prefix the API is mounted under, None at the root
+* @return The OpenAPI document
+*/
+pub fn api_doc(base_path: Option<&str>) -> utoipa::openapi::OpenApi {
+    let mut doc = ApiDoc::openapi();
+    if let Some(base_path) = base_path {
+        doc.servers = Some(vec![Server::new(base_path)]);
+    }
+    doc
+}
@@ -0,0 +1,155 @@
+/*
+* Protobuf content negotiation.
+*
+* Endpoints that support it answer clients preferring application/x-protobuf
+* with a prost-encoded body instead of JSON. The messages are hand-written
+* mirrors of proto/penumbra_indexer/v1/block.proto, which clients compile to
+* decode the responses.
+*/
+
+use std::collections::HashMap;
+use axum::{
+    http::{header::{ACCEPT, CONTENT_TYPE}, HeaderMap, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use prost::Message;
+use crate::models::block::StoredBlock;
+
+/* Media type of protobuf response bodies */
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+/* google.protobuf.Timestamp */
+#[derive(Clone, PartialEq, Message)]
+pub struct Timestamp {
+    #[prost(int64, tag = "1")]
+    pub seconds: i64,
+    #[prost(int32, tag = "2")]
+    pub nanos: i32,
+}
+
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(time: DateTime<Utc>) -> Self {
+        Self {
+            seconds: time.timestamp(),
+            nanos: time.timestamp_subsec_nanos() as i32,
+        }
+    }
+}
+
+/* penumbra_indexer.v1.Block */
+#[derive(Clone, PartialEq, Message)]
+pub struct Block {
+    #[prost(int64, tag = "1")]
+    pub height: i64,
+    #[prost(message, optional, tag = "2")]
+    pub time: Option<Timestamp>,
+    #[prost(string, tag = "3")]
+    pub hash: String,
+    #[prost(string, tag = "4")]
+    pub proposer_address: String,
+    #[prost(int32, tag = "5")]
+    pub tx_count: i32,
+    #[prost(string, optional, tag = "6")]
+    pub previous_block_hash: Option<String>,
+    #[prost(int64, optional, tag = "8")]
+    pub block_size_bytes: Option<i64>,
+    #[prost(bytes = "vec", tag = "9")]
+    pub data: Vec<u8>,
+    #[prost(map = "string, int64", tag = "10")]
+    pub action_counts: HashMap<String, i64>,
+    #[prost(message, optional, tag = "11")]
+    pub created_at: Option<Timestamp>,
+    #[prost(bool, tag = "12")]
+    pub has_swaps: bool,
+    #[prost(bool, tag = "13")]
+    pub has_transfers: bool,
+    #[prost(message, optional, tag = "14")]
+    pub updated_at: Option<Timestamp>,
+    #[prost(string, tag = "15")]
+    pub burn_amount: String,
+}
+
+impl From<StoredBlock> for Block {
+    fn from(block: StoredBlock) -> Self {
+        // A block stored without its payload has a null data field
+        let data = match block.data {
+            serde_json::Value::Null => Vec::new(),
+            data => data.to_string().into_bytes(),
+        };
+        let action_counts = block.action_counts
+            .as_ref()
+            .and_then(|counts| counts.as_object())
+            .map(|counts| {
+                counts.iter()
+                    .filter_map(|(action, count)| Some((action.clone(), count.as_i64()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            height: block.height,
+            time: Some(block.time.into()),
+            hash: block.hash,
+            proposer_address: block.proposer_address,
+            tx_count: block.tx_count,
+            previous_block_hash: block.previous_block_hash,
+            burn_amount: block.burn_amount.to_string(),
+            block_size_bytes: block.block_size_bytes,
+            data,
+            action_counts,
+            created_at: Some(block.created_at.into()),
+            has_swaps: block.has_swaps,
+            has_transfers: block.has_transfers,
+            updated_at: block.updated_at.map(Into::into),
+        }
+    }
+}
+
+/*
+* A prost message rendered as an application/x-protobuf response body.
+*/
+pub struct Protobuf<T>(pub T);
+
+impl<T: Message> IntoResponse for Protobuf<T> {
+    fn into_response(self) -> Response {
+        (
+            [(CONTENT_TYPE, HeaderValue::from_static(PROTOBUF_CONTENT_TYPE))],
+            self.0.encode_to_vec(),
+        ).into_response()
+    }
+}
+
+/*
+* Checks whether the client prefers a protobuf response over JSON.
+*
+* True when the Accept header lists application/x-protobuf with a quality
+* at least as high as any media range JSON would satisfy. Without an
+* Accept header, or with only wildcards, JSON stays the default.
+*/
+pub fn prefers_protobuf(headers: &HeaderMap) -> bool {
+    let mut protobuf_quality: f32 = 0.0;
+    let mut json_quality: f32 = 0.0;
+
+    let media_ranges = headers.get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','));
+
+    for media_range in media_ranges {
+        let mut parts = media_range.split(';').map(str::trim);
+        let media_type = parts.next().unwrap_or_default().to_ascii_lowercase();
+        let quality = parts
+            .filter_map(|param| param.strip_prefix("q="))
+            .find_map(|q| q.parse::<f32>().ok())
+            .unwrap_or(1.0);
+
+        match media_type.as_str() {
+            PROTOBUF_CONTENT_TYPE => protobuf_quality = protobuf_quality.max(quality),
+            "application/json" | "application/*" | "*/*" => json_quality = json_quality.max(quality),
+            _ => {}
+        }
+    }
+
+    protobuf_quality > 0.0 && protobuf_quality >= json_quality
+}
@@ -0,0 +1,191 @@
+/*
+* Per-client rate limiting for the API.
+*
+* Keeps a token bucket per client IP address. Each bucket holds up to one
+* second's worth of requests and refills continuously at the configured
+* rate; requests arriving at an empty bucket are rejected with 429.
+*
+* A bucket left idle for a full refill window is full again and no
+* different from a new one, so idle buckets are swept periodically to keep
+* the map from growing with every address ever seen.
+*/
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use dashmap::DashMap;
+use super::routes::common::too_many_requests_error;
+
+/* Time an empty bucket takes to refill completely */
+const REFILL_WINDOW: Duration = Duration::from_secs(1);
+
+/* Minimum time between sweeps of idle buckets */
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/*
+* Token bucket state for a single client.
+*/
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/*
+* Shared rate limiter state keyed by client IP address.
+*/
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    buckets: Arc<DashMap<IpAddr, TokenBucket>>,
+    requests_per_sec: f64,
+    last_sweep: Arc<Mutex<Instant>>,
+}
+
+impl RateLimiter {
+    /*
+    * Creates a rate limiter allowing the given sustained request rate per client.
+    *
+    * @param requests_per_sec Requests per second allowed for each IP address
+    */
+    pub fn new(requests_per_sec: u32) -> Self {
+        Self {
+            buckets: Arc::new(DashMap::new()),
+            requests_per_sec: requests_per_sec as f64,
+            last_sweep: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /*
+    * Takes a token from the client's bucket.
+    *
+    * @param ip Address of the client
+    * @return Whether the request is within the client's limit
+    */
+    fn try_acquire(&self, ip: IpAddr) -> bool {
+        let now = Instant::now();
+        self.sweep_if_due(now);
+
+        let mut bucket = self.buckets.entry(ip).or_insert_with(|| TokenBucket {
+            tokens: self.requests_per_sec,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.requests_per_sec).min(self.requests_per_sec);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /*
+    * Sweeps idle buckets when the sweep interval has passed.
+    *
+    * Only one request sweeps at a time; others skip the sweep rather than
+    * wait for it.
+    *
+    * @param now Current time
+    */
+    fn sweep_if_due(&self, now: Instant) {
+        let Ok(mut last_sweep) = self.last_sweep.try_lock() else {
+            return;
+        };
+        if now.duration_since(*last_sweep) < SWEEP_INTERVAL {
+            return;
+        }
+        *last_sweep = now;
+        drop(last_sweep);
+
+        self.sweep_idle(now);
+    }
+
+    /*
+    * Drops buckets that have been idle for at least a refill window.
+    *
+    * @param now Current time
+    * @return Number of buckets dropped
+    */
+    fn sweep_idle(&self, now: Instant) -> usize {
+        let before = self.buckets.len();
+        self.buckets.retain(|_, bucket| now.duration_since(bucket.last_refill) < REFILL_WINDOW);
+        before.saturating_sub(self.buckets.len())
+    }
+}
+
+/*
+* Middleware rejecting requests from clients that exceed their rate limit.
+*
+* @param limiter Shared rate limiter state
+* @param addr Remote address of the client
+* @param request Incoming request
+* @param next Remaining middleware and handler
+* @return The handler's response, or a 429 error response
+*/
+pub async fn limit_requests(
+    State(limiter): State<RateLimiter>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !limiter.try_acquire(addr.ip()) {
+        return too_many_requests_error("Rate limit exceeded, please slow down").into_response();
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn ip(last: u8) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::new(10, 0, 0, last))
+    }
+
+    #[test]
+    fn idle_buckets_are_swept() {
+        let limiter = RateLimiter::new(5);
+        for last in 0..100 {
+            assert!(limiter.try_acquire(ip(last)));
+        }
+        assert_eq!(limiter.buckets.len(), 100);
+
+        assert_eq!(limiter.sweep_idle(Instant::now()), 0);
+        assert_eq!(limiter.sweep_idle(Instant::now() + REFILL_WINDOW), 100);
+        assert!(limiter.buckets.is_empty());
+    }
+
+    #[test]
+    fn a_swept_client_starts_with_a_full_bucket() {
+        let limiter = RateLimiter::new(2);
+        assert!(limiter.try_acquire(ip(1)));
+        assert!(limiter.try_acquire(ip(1)));
+        assert!(!limiter.try_acquire(ip(1)));
+
+        limiter.sweep_idle(Instant::now() + REFILL_WINDOW);
+        assert!(limiter.try_acquire(ip(1)));
+    }
+
+    #[test]
+    fn sweeps_run_at_most_once_per_interval() {
+        let limiter = RateLimiter::new(5);
+        limiter.try_acquire(ip(1));
+        let start = *limiter.last_sweep.lock().unwrap();
+
+        limiter.sweep_if_due(start + SWEEP_INTERVAL / 2);
+        assert_eq!(limiter.buckets.len(), 1);
+
+        limiter.sweep_if_due(start + SWEEP_INTERVAL);
+        assert!(limiter.buckets.is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+/*
+* Admin API module.
+*
+* Exposes operator actions that change what the indexer stores. These
+* routes are only mounted when ENABLE_DEBUG_ENDPOINTS and API_KEY are set,
+* and every request must carry the key in the X-API-Key header.
+*/
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use axum::{extract::State, http::{HeaderMap, StatusCode}, Json};
+use subtle::ConstantTimeEq;
+use tokio::sync::mpsc::{error::TrySendError, Sender};
+use crate::client::resync::{ResyncJob, MAX_RESYNC_SPAN};
+use super::common::{bad_request_error, service_unavailable_error, unauthorized_error, ErrorResponse};
+
+/* Header carrying the admin API key */
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/*
+* Shared state of the admin routes.
+*/
+#[derive(Debug, Clone)]
+pub struct AdminState {
+    /// Sending end of the resync queue
+    pub resync_tx: Sender<ResyncJob>,
+
+    /// Key every admin request must present
+    pub api_key: Arc<str>,
+
+    /// Highest chain height seen by the indexer, 0 until known
+    pub chain_tip: Arc<AtomicU64>,
+}
+
+/*
+* Checks the request's API key against the configured one.
+*
+* The comparison takes the same time wherever the keys differ, so
+* response timing does not reveal how much of a guess was right.
+*
+* @param headers Request headers
+* @param api_key The configured API key
+* @return Ok when the key matches, otherwise a 401 error response
+*/
+fn authorize(headers: &HeaderMap, api_key: &str) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let provided = headers
+        .get(API_KEY_HEADER)
+        .map(|value| value.as_bytes())
+        .unwrap_or_default();
+
+    if bool::from(provided.ct_eq(api_key.as_bytes())) {
+        Ok(())
+    } else {
+        Err(unauthorized_error("Missing or invalid X-API-Key header"))
+    }
+}
+
+/*
+* Queues a height range to be fetched and stored again.
+*
+* Returns as soon as the job is queued; the resync worker runs queued
+* jobs one at a time in the background.
+*
+* @param state Admin state with the resync queue, API key and chain tip
+* @param headers Request headers carrying the API key
+* @param job Inclusive height range to refetch
+* @return 202 with the queued range, 401 without a valid API key, 400 for
+*         an invalid, oversized or future range, or 503 when the chain tip
+*         is not known yet, the queue is full or no worker is running
+*/
+pub async fn resync_range(
+    State(state): State<AdminState>,
+    headers: HeaderMap,
+    Json(job): Json<ResyncJob>,
+) -> Result<(StatusCode, Json<ResyncJob>), (StatusCode, Json<ErrorResponse>)> {
+    authorize(&headers, &state.api_key)?;
+
+    if job.from == 0 || job.from > job.to {
+        return Err(bad_request_error("'from' must be at least 1 and not greater than 'to'"));
+    }
+    if job.to - job.from >= MAX_RESYNC_SPAN {
+        return Err(bad_request_error(format!("A resync may cover at most {} heights", MAX_RESYNC_SPAN)));
+    }
+
+    let chain_tip = state.chain_tip.load(Ordering::Relaxed);
+    if chain_tip == 0 {
+        return Err(service_unavailable_error("Chain tip is not known yet, try again later"));
+    }
+    if job.to > chain_tip {
+        return Err(bad_request_error(format!("'to' is above the current chain tip {}", chain_tip)));
+    }
+
+    match state.resync_tx.try_send(job) {
+        Ok(()) => Ok((StatusCode::ACCEPTED, Json(job))),
+        Err(TrySendError::Full(_)) => Err(service_unavailable_error("Resync queue is full, try again later")),
+        Err(TrySendError::Closed(_)) => Err(service_unavailable_error("Resyncs only run while the indexer follows the chain")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use tokio::sync::mpsc;
+
+    const KEY: &str = "secret-key";
+
+    fn state(chain_tip: u64) -> (AdminState, mpsc::Receiver<ResyncJob>) {
+        let (resync_tx, resync_rx) = mpsc::channel(1);
+        let state = AdminState {
+            resync_tx,
+            api_key: Arc::from(KEY),
+            chain_tip: Arc::new(AtomicU64::new(chain_tip)),
+        };
+        (state, resync_rx)
+    }
+
+    fn headers(key: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(API_KEY_HEADER, HeaderValue::from_str(key).unwrap());
+        headers
+    }
+
+    async fn status(state: AdminState, headers: HeaderMap, from: u64, to: u64) -> StatusCode {
+        match resync_range(State(state), headers, Json(ResyncJob { from, to })).await {
+            Ok((status, _)) => status,
+            Err((status, _)) => status,
+        }
+    }
+
+    #[tokio::test]
+    async fn queues_a_valid_range() {
+        let (state, mut jobs) = state(100);
+        assert_eq!(status(state, headers(KEY), 10, 20).await, StatusCode::ACCEPTED);
+        let job = jobs.recv().await.unwrap();
+        assert_eq!((job.from, job.to), (10, 20));
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_and_wrong_keys() {
+        let (state, _jobs) = state(100);
+        assert_eq!(status(state.clone(), HeaderMap::new(), 10, 20).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(state.clone(), headers("secret-kez"), 10, 20).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(state, headers("secret"), 10, 20).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_and_future_ranges() {
+        let (state, _jobs) = state(50_000);
+        assert_eq!(status(state.clone(), headers(KEY), 1, u64::MAX).await, StatusCode::BAD_REQUEST);
+        assert_eq!(status(state.clone(), headers(KEY), 1, MAX_RESYNC_SPAN + 1).await, StatusCode::BAD_REQUEST);
+        assert_eq!(status(state.clone(), headers(KEY), 49_995, 50_001).await, StatusCode::BAD_REQUEST);
+        assert_eq!(status(state, headers(KEY), 1, MAX_RESYNC_SPAN).await, StatusCode::ACCEPTED);
+    }
+
+    #[tokio::test]
+    async fn rejects_resyncs_until_the_chain_tip_is_known() {
+        let (state, _jobs) = state(0);
+        assert_eq!(status(state, headers(KEY), 1, 10).await, StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
@@ -5,10 +5,133 @@
 * including fetching recent blocks and specific blocks by height.
 */
 
-use axum::{extract::{State, Path}, http::StatusCode, Json};
+use axum::{
+    body::Body,
+    extract::{Query, State, Path},
+    http::{header::{CONTENT_TYPE, ETAG, IF_NONE_MATCH, VARY}, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::{stream, StreamExt};
+use serde::Deserialize;
 use sqlx::{Pool, Postgres};
-use crate::{db, models::block::{BlockList, StoredBlock}};
-use super::common::{database_error, not_found_error, ErrorResponse};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::Duration;
+use tokio::sync::{mpsc, Semaphore};
+use tracing::{error, warn};
+use utoipa::{IntoParams, ToSchema};
+use crate::{
+    api::protobuf::{self, Protobuf},
+    client::sync::MAX_REORG_DEPTH,
+    db,
+    models::block::{BlockList, BlockTxCount, BusiestBlockList, StoredBlock},
+};
+use super::common::{
+    bad_request_error, database_error, gone_error, not_found_error, parse_height, parse_timestamp,
+    service_unavailable_error, ErrorResponse,
+};
+use super::pagination::with_total_count;
+
+/* Default number of blocks returned by a time range query */
+const DEFAULT_RANGE_LIMIT: i64 = 100;
+
+/* Maximum number of blocks returned by a time range query */
+const MAX_RANGE_LIMIT: i64 = 1000;
+
+/* Default number of blocks returned by the busiest blocks endpoint */
+const DEFAULT_BUSIEST_LIMIT: i64 = 10;
+
+/* Maximum number of blocks returned by the busiest blocks endpoint */
+const MAX_BUSIEST_LIMIT: i64 = 100;
+
+/* Maximum number of heights covered by one export */
+const MAX_EXPORT_HEIGHTS: i64 = 100_000;
+
+/* Number of serialized blocks buffered ahead of a slow export client */
+const EXPORT_BUFFER_BLOCKS: usize = 32;
+
+/* Maximum number of exports streaming at once, each holding a pooled connection */
+pub const MAX_CONCURRENT_EXPORTS: usize = 2;
+
+/* Time an export waits for a stalled client to take the next block */
+pub const EXPORT_SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/* Longest time an export may stream before it is cut off */
+pub const EXPORT_MAX_DURATION: Duration = Duration::from_secs(600);
+
+/* Content type of newline-delimited JSON */
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/* Maximum number of heights accepted by the batch block endpoint */
+const MAX_BATCH_HEIGHTS: usize = 100;
+
+/*
+* Request body for the batch block endpoint.
+*/
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BlockBatchRequest {
+    /// Heights of the blocks to retrieve (at most 100)
+    pub heights: Vec<i64>,
+}
+
+/*
+* Query parameters for the raw block endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RawBlockQuery {
+    /// Comma-separated payload sections to return instead of the whole block: block_id, header, data, evidence, last_commit
+    #[param(example = "header,block_id")]
+    pub fields: Option<String>,
+}
+
+/*
+* Query parameters for the busiest blocks endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BusiestBlocksQuery {
+    /// Maximum number of blocks to return (1-100, default 10)
+    #[param(default = 10, minimum = 1, maximum = 100)]
+    pub limit: Option<i64>,
+
+    /// Only consider blocks produced at or after this time (RFC 3339)
+    #[param(format = DateTime, example = "2025-02-25T00:00:00Z")]
+    pub since: Option<String>,
+}
+
+/*
+* Query parameters for the block export endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BlockExportQuery {
+    /// First block height to export (inclusive)
+    #[param(value_type = i64, minimum = 0)]
+    pub from: Option<i64>,
+
+    /// Last block height to export (inclusive); at most 100000 heights after `from`
+    #[param(value_type = i64, minimum = 0)]
+    pub to: Option<i64>,
+}
+
+/*
+* Query parameters for the block time range endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BlockRangeQuery {
+    /// Inclusive start of the window (RFC 3339)
+    #[param(format = DateTime, example = "2025-02-25T00:00:00Z")]
+    pub from: Option<String>,
+
+    /// Exclusive end of the window (RFC 3339)
+    #[param(format = DateTime, example = "2025-02-26T00:00:00Z")]
+    pub to: Option<String>,
+
+    /// Maximum number of blocks to return (1-1000, default 100)
+    #[param(default = 100, minimum = 1, maximum = 1000)]
+    pub limit: Option<i64>,
+}
 
 /*
 * Retrieves the latest blocks.
@@ -23,21 +146,82 @@ use super::common::{database_error, not_found_error, ErrorResponse};
     path = "/api/blocks",
     tag = "Blocks",
     responses(
-        (status = 200, description = "List of latest blocks retrieved successfully", body = BlockList),
+        (status = 200, description = "List of latest blocks retrieved successfully", body = BlockList, headers(
+            ("x-total-count" = i64, description = "Total number of indexed blocks")
+        )),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 pub async fn get_latest_blocks(
     State(pool): State<Pool<Postgres>>,
-) -> Result<(StatusCode, Json<BlockList>), (StatusCode, Json<ErrorResponse>)> {
-    match db::blocks::get_latest_blocks(&pool).await {
-        Ok(blocks) => {
-            let summaries = blocks.into_iter()
-                .map(|block| block.to_summary())
-                .collect();
-            let response = BlockList::new(summaries);
-            Ok((StatusCode::OK, Json(response)))
-        }
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let blocks = match db::blocks::get_latest_blocks(&pool).await {
+        Ok(blocks) => blocks,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let total_count = match db::blocks::count_blocks(&pool).await {
+        Ok(count) => count,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let response = BlockList::new(blocks, total_count);
+    Ok(with_total_count(response, total_count))
+}
+
+/*
+* Retrieves the latest block.
+*
+* Returns the full details of the highest indexed block.
+*
+* @param pool Database connection pool
+* @return JSON response containing the latest block
+*/
+#[utoipa::path(
+    get,
+    path = "/api/blocks/latest",
+    tag = "Blocks",
+    responses(
+        (status = 200, description = "Latest block retrieved successfully", body = StoredBlock),
+        (status = 404, description = "No blocks indexed yet", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_latest_block(
+    State(pool): State<Pool<Postgres>>,
+) -> Result<(StatusCode, Json<StoredBlock>), (StatusCode, Json<ErrorResponse>)> {
+    match db::blocks::get_latest_block(&pool).await {
+        Ok(Some(block)) => Ok((StatusCode::OK, Json(block))),
+        Ok(None) => Err(not_found_error("No blocks have been indexed yet")),
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+/*
+* Retrieves the earliest indexed block.
+*
+* Returns the full details of the lowest indexed block. Together with the
+* latest block it gives the indexer's coverage window.
+*
+* @param pool Database connection pool
+* @return JSON response containing the earliest block
+*/
+#[utoipa::path(
+    get,
+    path = "/api/blocks/first",
+    tag = "Blocks",
+    responses(
+        (status = 200, description = "Earliest indexed block retrieved successfully", body = StoredBlock),
+        (status = 404, description = "No blocks indexed yet", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_first_block(
+    State(pool): State<Pool<Postgres>>,
+) -> Result<(StatusCode, Json<StoredBlock>), (StatusCode, Json<ErrorResponse>)> {
+    match db::blocks::get_earliest_block(&pool).await {
+        Ok(Some(block)) => Ok((StatusCode::OK, Json(block))),
+        Ok(None) => Err(not_found_error("No blocks have been indexed yet")),
         Err(e) => Err(database_error(e)),
     }
 }
@@ -45,11 +229,17 @@ pub async fn get_latest_blocks(
 /*
 * Retrieves a specific block by its height.
 *
-* Returns the block details for the given height if it exists.
+* Returns the block details for the given height if it exists. Blocks
+* deeper than the reorg window below the tip can no longer change, so they
+* carry a strong ETag derived from the block hash and a matching
+* If-None-Match request is answered with 304 Not Modified. Clients that
+* prefer application/x-protobuf in Accept receive the block encoded as
+* penumbra_indexer.v1.Block instead of JSON.
 *
 * @param pool Database connection pool
 * @param height Block height to query
-* @return JSON response containing the requested block data
+* @param headers Request headers, checked for If-None-Match and Accept
+* @return JSON or protobuf response containing the requested block data
 */
 #[utoipa::path(
     get,
@@ -59,18 +249,599 @@ pub async fn get_latest_blocks(
         ("height" = i64, Path, description = "Block height to retrieve")
     ),
     responses(
-        (status = 200, description = "Block retrieved successfully", body = StoredBlock),
+        (status = 200, description = "Block retrieved successfully", content(
+            ("application/json" = StoredBlock),
+            ("application/x-protobuf" = [u8])
+        ), headers(
+            ("etag" = String, description = "Strong validator for blocks outside the reorg window"),
+            ("vary" = String, description = "Accept, since the body format depends on it")
+        )),
+        (status = 304, description = "Block unchanged since the ETag sent in If-None-Match"),
+        (status = 400, description = "Invalid block height", body = ErrorResponse),
         (status = 404, description = "Block not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 pub async fn get_block_by_height(
     State(pool): State<Pool<Postgres>>,
-    Path(height): Path<i64>,
-) -> Result<(StatusCode, Json<StoredBlock>), (StatusCode, Json<ErrorResponse>)> {
-    match db::blocks::get_block_by_height(&pool, height).await {
-        Ok(Some(block)) => Ok((StatusCode::OK, Json(block))),
+    Path(height): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let height = parse_height(&height)?;
+    let as_protobuf = protobuf::prefers_protobuf(&headers);
+
+    let block = match db::blocks::get_block_by_height(&pool, height).await {
+        Ok(Some(block)) => block,
+        Ok(None) => return Err(not_found_error(format!("Block at height {} not found", height))),
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let latest_height = match db::blocks::get_latest_height(&pool).await {
+        Ok(latest) => latest.unwrap_or(height),
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(VARY, HeaderValue::from_static("accept"));
+
+    if height + MAX_REORG_DEPTH as i64 >= latest_height {
+        return Ok(block_response(block, as_protobuf, response_headers));
+    }
+
+    // Each representation needs its own strong validator
+    let etag = if as_protobuf {
+        format!("\"{}-pb\"", block.hash)
+    } else {
+        format!("\"{}\"", block.hash)
+    };
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response_headers.insert(ETAG, value);
+    }
+
+    if etag_matches(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, response_headers).into_response());
+    }
+
+    Ok(block_response(block, as_protobuf, response_headers))
+}
+
+/*
+* Renders a block as JSON, or as protobuf when the client prefers it.
+*/
+fn block_response(block: StoredBlock, as_protobuf: bool, headers: HeaderMap) -> Response {
+    if as_protobuf {
+        (StatusCode::OK, headers, Protobuf(protobuf::Block::from(block))).into_response()
+    } else {
+        (StatusCode::OK, headers, Json(block)).into_response()
+    }
+}
+
+/*
+* Checks whether an If-None-Match header matches the given ETag.
+*
+* Accepts a list of tags, weak tags and the wildcard, per the weak
+* comparison If-None-Match uses.
+*/
+fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers.get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/*
+* Retrieves the transaction count of a block.
+*
+* Reads the count stored with the block instead of loading its
+* transactions.
+*
+* @param pool Database connection pool
+* @param height Block height to query
+* @return JSON response containing the height and transaction count
+*/
+#[utoipa::path(
+    get,
+    path = "/api/blocks/{height}/tx-count",
+    tag = "Blocks",
+    params(
+        ("height" = i64, Path, description = "Block height to count transactions for")
+    ),
+    responses(
+        (status = 200, description = "Transaction count retrieved successfully", body = BlockTxCount),
+        (status = 400, description = "Invalid block height", body = ErrorResponse),
+        (status = 404, description = "Block not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_block_tx_count(
+    State(pool): State<Pool<Postgres>>,
+    Path(height): Path<String>,
+) -> Result<(StatusCode, Json<BlockTxCount>), (StatusCode, Json<ErrorResponse>)> {
+    let height = parse_height(&height)?;
+
+    match db::blocks::get_tx_count_at_height(&pool, height).await {
+        Ok(Some(tx_count)) => Ok((StatusCode::OK, Json(BlockTxCount { height, tx_count }))),
         Ok(None) => Err(not_found_error(format!("Block at height {} not found", height))),
         Err(e) => Err(database_error(e)),
     }
 }
+
+/*
+* Retrieves the raw Tendermint JSON of a block.
+*
+* Returns only the persisted block payload, without the indexed
+* wrapper fields, for clients that want the canonical block. With
+* `fields`, only the named sections of the payload are returned. Blocks
+* indexed with STORE_RAW_BLOCK_DATA disabled have no payload and
+* return 410.
+*
+* @param pool Database connection pool
+* @param height Block height to query
+* @param query Optional payload sections to return
+* @return JSON response containing the raw block data
+*/
+#[utoipa::path(
+    get,
+    path = "/api/blocks/{height}/raw",
+    tag = "Blocks",
+    params(
+        ("height" = i64, Path, description = "Block height to retrieve"),
+        RawBlockQuery
+    ),
+    responses(
+        (status = 200, description = "Raw block retrieved successfully", body = Object),
+        (status = 400, description = "Invalid block height or unknown field", body = ErrorResponse),
+        (status = 404, description = "Block not found", body = ErrorResponse),
+        (status = 410, description = "Raw data not stored for the block", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_block_raw(
+    State(pool): State<Pool<Postgres>>,
+    Path(height): Path<String>,
+    Query(query): Query<RawBlockQuery>,
+) -> Result<(StatusCode, Json<serde_json::Value>), (StatusCode, Json<ErrorResponse>)> {
+    let height = parse_height(&height)?;
+
+    let result = match query.fields.as_deref() {
+        Some(raw) => {
+            let fields = parse_raw_fields(raw)?;
+            db::blocks::get_block_raw_fields(&pool, height, &fields).await
+        }
+        None => db::blocks::get_block_raw_data(&pool, height).await,
+    };
+
+    match result {
+        Ok(Some(serde_json::Value::Null)) => Err(gone_error(format!("Raw data not stored for block at height {}", height))),
+        Ok(Some(data)) => Ok((StatusCode::OK, Json(data))),
+        Ok(None) => Err(not_found_error(format!("Block at height {} not found", height))),
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+/*
+* Parses a comma-separated list of raw block sections.
+*
+* @param raw The raw fields parameter
+* @return The requested field names, or a bad request error for an empty
+*         list or a name outside the allowlist
+*/
+fn parse_raw_fields(raw: &str) -> Result<Vec<&str>, (StatusCode, Json<ErrorResponse>)> {
+    let allowed: Vec<&str> = db::blocks::RAW_BLOCK_FIELDS.iter().map(|(name, _)| *name).collect();
+    let fields: Vec<&str> = raw.split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .collect();
+
+    if fields.is_empty() {
+        return Err(bad_request_error(format!("'fields' must name at least one of: {}", allowed.join(", "))));
+    }
+    if let Some(unknown) = fields.iter().find(|field| !allowed.contains(field)) {
+        return Err(bad_request_error(format!("Unknown field '{}': expected one of {}", unknown, allowed.join(", "))));
+    }
+
+    Ok(fields)
+}
+
+/*
+* Retrieves blocks produced within a time window.
+*
+* Returns block summaries with `from <= time < to` in ascending height
+* order, capped at a maximum number of blocks.
+*
+* @param pool Database connection pool
+* @param query Time window bounds and optional limit
+* @return JSON response containing the blocks in the window
+*/
+#[utoipa::path(
+    get,
+    path = "/api/blocks/range",
+    tag = "Blocks",
+    params(BlockRangeQuery),
+    responses(
+        (status = 200, description = "Blocks in the time range retrieved successfully", body = BlockList, headers(
+            ("x-total-count" = i64, description = "Total number of blocks in the time range")
+        )),
+        (status = 400, description = "Missing or invalid time range", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_blocks_in_time_range(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<BlockRangeQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let from = match query.from.as_deref() {
+        Some(raw) => parse_timestamp("from", raw)?,
+        None => return Err(bad_request_error("Missing required 'from' timestamp")),
+    };
+    let to = match query.to.as_deref() {
+        Some(raw) => parse_timestamp("to", raw)?,
+        None => return Err(bad_request_error("Missing required 'to' timestamp")),
+    };
+    if from > to {
+        return Err(bad_request_error("'from' must not be later than 'to'"));
+    }
+
+    let limit = query.limit.unwrap_or(DEFAULT_RANGE_LIMIT).clamp(1, MAX_RANGE_LIMIT);
+
+    let blocks = match db::blocks::get_blocks_in_time_range(&pool, from, to, limit).await {
+        Ok(blocks) => blocks,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let total_count = match db::blocks::count_blocks_in_time_range(&pool, from, to).await {
+        Ok(count) => count,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let response = BlockList::new(blocks, total_count);
+    Ok(with_total_count(response, total_count))
+}
+
+/*
+* Retrieves the blocks with the most transactions.
+*
+* Blocks with equal transaction counts are ordered newest first. An
+* optional start time restricts the list to recent activity.
+*
+* @param pool Database connection pool
+* @param query Optional limit and start time
+* @return JSON response containing the busiest block summaries
+*/
+#[utoipa::path(
+    get,
+    path = "/api/blocks/busiest",
+    tag = "Blocks",
+    params(BusiestBlocksQuery),
+    responses(
+        (status = 200, description = "Busiest blocks retrieved successfully", body = BusiestBlockList),
+        (status = 400, description = "Invalid start time", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_busiest_blocks(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<BusiestBlocksQuery>,
+) -> Result<(StatusCode, Json<BusiestBlockList>), (StatusCode, Json<ErrorResponse>)> {
+    let limit = query.limit.unwrap_or(DEFAULT_BUSIEST_LIMIT).clamp(1, MAX_BUSIEST_LIMIT);
+    let since = match query.since.as_deref() {
+        Some(raw) => Some(parse_timestamp("since", raw)?),
+        None => None,
+    };
+
+    match db::blocks::get_busiest_blocks(&pool, limit, since).await {
+        Ok(blocks) => Ok((StatusCode::OK, Json(BusiestBlockList::new(blocks)))),
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+/*
+* Shared state of the block export endpoint.
+*
+* Each running export holds a pooled connection for as long as its client
+* reads, which the request timeout does not cover once headers are sent.
+* Permits cap how many exports run at once, and the timeouts release the
+* connection of clients that stall or read too slowly.
+*/
+#[derive(Debug, Clone)]
+pub struct ExportState {
+    /// Database connection pool
+    pub pool: Pool<Postgres>,
+
+    /// One permit per export allowed to run at once
+    pub permits: Arc<Semaphore>,
+
+    /// Time to wait for the client to take the next block
+    pub send_timeout: Duration,
+
+    /// Longest time an export may stream
+    pub max_duration: Duration,
+}
+
+impl ExportState {
+    /*
+    * Creates the export state with the default limits.
+    *
+    * @param pool Database connection pool
+    */
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self {
+            pool,
+            permits: Arc::new(Semaphore::new(MAX_CONCURRENT_EXPORTS)),
+            send_timeout: EXPORT_SEND_TIMEOUT,
+            max_duration: EXPORT_MAX_DURATION,
+        }
+    }
+}
+
+/*
+* Exports the blocks in a height range as newline-delimited JSON.
+*
+* Streams one StoredBlock object per line in ascending height order.
+* Rows are read from the database as the client consumes them, so the
+* range is never buffered in memory. A database error, a client that
+* stops reading for the send timeout or an export running past its
+* maximum duration aborts the response, leaving the client with a
+* truncated body.
+*
+* @param state Export state with the pool, permits and timeouts
+* @param query Inclusive height range to export
+* @return Streaming NDJSON response of the indexed blocks in the range,
+*         or 503 when the maximum number of exports is already running
+*/
+#[utoipa::path(
+    get,
+    path = "/api/blocks/export.ndjson",
+    tag = "Blocks",
+    params(BlockExportQuery),
+    responses(
+        (status = 200, description = "Blocks streamed one JSON object per line", content_type = "application/x-ndjson", body = StoredBlock),
+        (status = 400, description = "Missing or invalid height range", body = ErrorResponse),
+        (status = 503, description = "Too many exports running, try again later", body = ErrorResponse)
+    )
+)]
+pub async fn export_blocks(
+    State(state): State<ExportState>,
+    Query(query): Query<BlockExportQuery>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let (from, to) = match (query.from, query.to) {
+        (Some(from), Some(to)) => (from, to),
+        _ => return Err(bad_request_error("'from' and 'to' are required")),
+    };
+    if from < 0 || from > to {
+        return Err(bad_request_error("'from' must be non-negative and not greater than 'to'"));
+    }
+    if to - from >= MAX_EXPORT_HEIGHTS {
+        return Err(bad_request_error(format!("An export may cover at most {} heights", MAX_EXPORT_HEIGHTS)));
+    }
+
+    // Held by the export task, so the permit returns once its connection does
+    let permit = match state.permits.clone().try_acquire_owned() {
+        Ok(permit) => permit,
+        Err(_) => return Err(service_unavailable_error("Too many exports running, try again later")),
+    };
+
+    // The bounded channel holds back the database reader while the client is slow
+    let (line_tx, line_rx) = mpsc::channel::<Result<Vec<u8>, std::io::Error>>(EXPORT_BUFFER_BLOCKS);
+    let timed_out = Arc::new(AtomicBool::new(false));
+    tokio::spawn({
+        let timed_out = timed_out.clone();
+        async move {
+            let _permit = permit;
+            let send_timeout = state.send_timeout;
+            let export = async {
+                let mut blocks = db::blocks::stream_blocks_in_height_range(&state.pool, from, to);
+                while let Some(block) = blocks.next().await {
+                    let line = block
+                        .map_err(|e| {
+                            error!(from, to, error = %e, "Block export failed");
+                            std::io::Error::other(e)
+                        })
+                        .and_then(|block| {
+                            let mut line = serde_json::to_vec(&block)?;
+                            line.push(b'\n');
+                            Ok(line)
+                        });
+                    let failed = line.is_err();
+                    match tokio::time::timeout(send_timeout, line_tx.send(line)).await {
+                        // The client disconnected when the receiver is gone
+                        Ok(Err(_)) => return true,
+                        Ok(Ok(())) if failed => return true,
+                        Ok(Ok(())) => {}
+                        Err(_) => return false,
+                    }
+                }
+                true
+            };
+
+            let finished = matches!(tokio::time::timeout(state.max_duration, export).await, Ok(true));
+            if !finished {
+                warn!(from, to, "Block export timed out, releasing its connection");
+                timed_out.store(true, Ordering::Relaxed);
+            }
+        }
+    });
+
+    // A timed out export ends the body with an error, so clients see it is incomplete
+    let lines = stream::unfold(Some(line_rx), move |line_rx| {
+        let timed_out = timed_out.clone();
+        async move {
+            let mut line_rx = line_rx?;
+            match line_rx.recv().await {
+                Some(line) => Some((line, Some(line_rx))),
+                None if timed_out.load(Ordering::Relaxed) => {
+                    let error = std::io::Error::new(std::io::ErrorKind::TimedOut, "block export timed out");
+                    Some((Err(error), None))
+                }
+                None => None,
+            }
+        }
+    });
+
+    Ok((
+        [(CONTENT_TYPE, HeaderValue::from_static(NDJSON_CONTENT_TYPE))],
+        Body::from_stream(lines),
+    ).into_response())
+}
+
+/*
+* Retrieves several blocks by height in a single request.
+*
+* Heights that are not indexed are left out of the response rather
+* than causing an error.
+*
+* @param pool Database connection pool
+* @param request List of block heights to retrieve
+* @return JSON response containing the found blocks in ascending height order
+*/
+#[utoipa::path(
+    post,
+    path = "/api/blocks/batch",
+    tag = "Blocks",
+    request_body = BlockBatchRequest,
+    responses(
+        (status = 200, description = "Blocks retrieved successfully", body = Vec<StoredBlock>),
+        (status = 400, description = "Too many heights requested", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_blocks_by_heights(
+    State(pool): State<Pool<Postgres>>,
+    Json(request): Json<BlockBatchRequest>,
+) -> Result<(StatusCode, Json<Vec<StoredBlock>>), (StatusCode, Json<ErrorResponse>)> {
+    if request.heights.len() > MAX_BATCH_HEIGHTS {
+        return Err(bad_request_error(format!(
+            "At most {} heights may be requested at once, got {}",
+            MAX_BATCH_HEIGHTS,
+            request.heights.len()
+        )));
+    }
+
+    match db::blocks::get_blocks_by_heights(&pool, &request.heights).await {
+        Ok(blocks) => Ok((StatusCode::OK, Json(blocks))),
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::amount::TokenAmount;
+
+    /* Heights far above any real chain, so the test never touches indexed blocks */
+    const EXPORT_TEST_FROM: i64 = 9_000_000_000_500;
+
+    fn export_state(pool: Pool<Postgres>, permits: usize, send_timeout: Duration) -> ExportState {
+        ExportState {
+            pool,
+            permits: Arc::new(Semaphore::new(permits)),
+            send_timeout,
+            max_duration: EXPORT_MAX_DURATION,
+        }
+    }
+
+    fn export_query(from: i64, to: i64) -> Query<BlockExportQuery> {
+        Query(BlockExportQuery { from: Some(from), to: Some(to) })
+    }
+
+    fn block(height: i64) -> StoredBlock {
+        StoredBlock {
+            height,
+            time: chrono::Utc::now(),
+            hash: format!("{:064X}", height),
+            proposer_address: "PROPOSER".to_string(),
+            tx_count: 0,
+            previous_block_hash: None,
+            burn_amount: TokenAmount::default(),
+            block_size_bytes: None,
+            data: serde_json::Value::Null,
+            action_counts: None,
+            has_swaps: false,
+            has_transfers: false,
+            created_at: chrono::Utc::now(),
+            updated_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_exports_beyond_the_concurrency_limit() {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+        let state = export_state(pool, 0, EXPORT_SEND_TIMEOUT);
+
+        match export_blocks(State(state), export_query(1, 10)).await {
+            Err((status, _)) => assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE),
+            Ok(_) => panic!("expected the export to be rejected"),
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL database in TEST_DATABASE_URL"]
+    async fn a_stalled_export_releases_its_permit() {
+        // More blocks than the channel buffers, so the export blocks on the client
+        let to = EXPORT_TEST_FROM + EXPORT_BUFFER_BLOCKS as i64 + 8;
+        let pool = db::init_db(&std::env::var("TEST_DATABASE_URL").unwrap()).await.unwrap();
+        let mut tx = pool.begin().await.unwrap();
+        for height in EXPORT_TEST_FROM..=to {
+            db::blocks::store_block(&mut tx, &block(height), false).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let state = export_state(pool.clone(), 1, Duration::from_millis(50));
+        let stalled = match export_blocks(State(state.clone()), export_query(EXPORT_TEST_FROM, to)).await {
+            Ok(response) => response,
+            Err((status, _)) => panic!("export failed with {}", status),
+        };
+        let busy = export_blocks(State(state.clone()), export_query(EXPORT_TEST_FROM, to)).await.err().map(|(status, _)| status);
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let available = state.permits.available_permits();
+        let body = axum::body::to_bytes(stalled.into_body(), usize::MAX).await;
+
+        sqlx::query("DELETE FROM blocks WHERE height BETWEEN $1 AND $2")
+            .bind(EXPORT_TEST_FROM)
+            .bind(to)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(busy, Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(available, 1);
+        assert!(body.is_err(), "a timed out export must not end like a complete one");
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL database in TEST_DATABASE_URL"]
+    async fn a_read_export_streams_every_block() {
+        let from = EXPORT_TEST_FROM + 100;
+        let to = from + 4;
+        let pool = db::init_db(&std::env::var("TEST_DATABASE_URL").unwrap()).await.unwrap();
+        let mut tx = pool.begin().await.unwrap();
+        for height in from..=to {
+            db::blocks::store_block(&mut tx, &block(height), false).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let state = export_state(pool.clone(), 1, EXPORT_SEND_TIMEOUT);
+        let response = match export_blocks(State(state.clone()), export_query(from, to)).await {
+            Ok(response) => response,
+            Err((status, _)) => panic!("export failed with {}", status),
+        };
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+
+        sqlx::query("DELETE FROM blocks WHERE height BETWEEN $1 AND $2")
+            .bind(from)
+            .bind(to)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let body = body.unwrap();
+        assert_eq!(body.iter().filter(|byte| **byte == b'\n').count(), 5);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(state.permits.available_permits(), 1);
+    }
+}
@@ -2,11 +2,14 @@
 * Error handling module for API responses.
 *
 * Provides standardized error responses for database errors and
-* resource not found scenarios.
+* resource not found scenarios, along with the service health check.
 */
 
-use axum::{http::StatusCode, Json};
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
 use utoipa::ToSchema;
+use crate::db;
 
 /*
 * Represents a structured error response.
@@ -20,6 +23,15 @@ pub struct ErrorResponse {
     pub code: u16,
 }
 
+/*
+* Represents the health status of the service.
+*/
+#[derive(serde::Serialize, ToSchema)]
+pub struct HealthResponse {
+    /// Service status, "ok" when the database is reachable
+    pub status: String,
+}
+
 /*
 * Generates a database error response.
 *
@@ -51,3 +63,213 @@ pub fn not_found_error(message: impl Into<String>) -> (StatusCode, Json<ErrorRes
     };
     (StatusCode::NOT_FOUND, Json(error_response))
 }
+
+/*
+* Generates a bad request error response.
+*
+* Accepts a custom error message and assigns an HTTP 400 status code.
+*
+* @param message The bad request error message
+* @return Tuple containing the status code and error response JSON
+*/
+pub fn bad_request_error(message: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: message.into(),
+        code: StatusCode::BAD_REQUEST.as_u16(),
+    };
+    (StatusCode::BAD_REQUEST, Json(error_response))
+}
+
+/*
+* Parses a block height path segment.
+*
+* Rejects non-numeric and negative values since no such block can exist.
+*
+* @param raw The raw height string from the request path
+* @return The parsed height or a bad request error response
+*/
+pub fn parse_height(raw: &str) -> Result<i64, (StatusCode, Json<ErrorResponse>)> {
+    match raw.parse::<i64>() {
+        Ok(height) if height >= 0 => Ok(height),
+        Ok(_) => Err(bad_request_error(format!("Block height must not be negative, got {}", raw))),
+        Err(_) => Err(bad_request_error(format!("Invalid block height '{}': expected a non-negative integer", raw))),
+    }
+}
+
+/*
+* Parses an RFC 3339 timestamp query parameter.
+*
+* @param name Name of the query parameter, used in the error message
+* @param raw The raw timestamp string
+* @return The parsed UTC timestamp or a bad request error response
+*/
+pub fn parse_timestamp(name: &str, raw: &str) -> Result<DateTime<Utc>, (StatusCode, Json<ErrorResponse>)> {
+    DateTime::parse_from_rfc3339(raw)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|e| bad_request_error(format!("Invalid '{}' timestamp '{}': {}", name, raw, e)))
+}
+
+/*
+* Generates an unprocessable entity error response.
+*
+* Accepts a custom error message and assigns an HTTP 422 status code.
+*
+* @param message The unprocessable entity error message
+* @return Tuple containing the status code and error response JSON
+*/
+pub fn unprocessable_entity_error(message: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: message.into(),
+        code: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+    };
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error_response))
+}
+
+/*
+* Generates a request timeout error response.
+*
+* Accepts a custom error message and assigns an HTTP 408 status code.
+*
+* @param message The request timeout error message
+* @return Tuple containing the status code and error response JSON
+*/
+pub fn request_timeout_error(message: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: message.into(),
+        code: StatusCode::REQUEST_TIMEOUT.as_u16(),
+    };
+    (StatusCode::REQUEST_TIMEOUT, Json(error_response))
+}
+
+/*
+* Generates a payload too large error response.
+*
+* Accepts a custom error message and assigns an HTTP 413 status code.
+*
+* @param message The payload too large error message
+* @return Tuple containing the status code and error response JSON
+*/
+pub fn payload_too_large_error(message: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: message.into(),
+        code: StatusCode::PAYLOAD_TOO_LARGE.as_u16(),
+    };
+    (StatusCode::PAYLOAD_TOO_LARGE, Json(error_response))
+}
+
+/*
+* Generates a too many requests error response.
+*
+* Accepts a custom error message and assigns an HTTP 429 status code.
+*
+* @param message The rate limit error message
+* @return Tuple containing the status code and error response JSON
+*/
+pub fn too_many_requests_error(message: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: message.into(),
+        code: StatusCode::TOO_MANY_REQUESTS.as_u16(),
+    };
+    (StatusCode::TOO_MANY_REQUESTS, Json(error_response))
+}
+
+/*
+* Generates a gone error response.
+*
+* Accepts a custom error message and assigns an HTTP 410 status code.
+*
+* @param message The gone error message
+* @return Tuple containing the status code and error response JSON
+*/
+pub fn gone_error(message: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: message.into(),
+        code: StatusCode::GONE.as_u16(),
+    };
+    (StatusCode::GONE, Json(error_response))
+}
+
+/*
+* Generates a service unavailable error response.
+*
+* Accepts a custom error message and assigns an HTTP 503 status code.
+*
+* @param message The service unavailable error message
+* @return Tuple containing the status code and error response JSON
+*/
+pub fn service_unavailable_error(message: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: message.into(),
+        code: StatusCode::SERVICE_UNAVAILABLE.as_u16(),
+    };
+    (StatusCode::SERVICE_UNAVAILABLE, Json(error_response))
+}
+
+/*
+* Generates an unauthorized error response.
+*
+* Accepts a custom error message and assigns an HTTP 401 status code.
+*
+* @param message The unauthorized error message
+* @return Tuple containing the status code and error response JSON
+*/
+pub fn unauthorized_error(message: impl Into<String>) -> (StatusCode, Json<ErrorResponse>) {
+    let error_response = ErrorResponse {
+        error: message.into(),
+        code: StatusCode::UNAUTHORIZED.as_u16(),
+    };
+    (StatusCode::UNAUTHORIZED, Json(error_response))
+}
+
+/*
+* Reports whether the service is ready to handle requests.
+*
+* Runs a trivial query against the database and returns HTTP 503
+* when it cannot be reached.
+*
+* @param pool Database connection pool
+* @return JSON response containing the health status
+*/
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "Health",
+    responses(
+        (status = 200, description = "Service is healthy", body = HealthResponse),
+        (status = 503, description = "Database is unavailable", body = ErrorResponse)
+    )
+)]
+pub async fn health_check(
+    State(pool): State<Pool<Postgres>>,
+) -> Result<(StatusCode, Json<HealthResponse>), (StatusCode, Json<ErrorResponse>)> {
+    match db::ping(&pool).await {
+        Ok(()) => Ok((StatusCode::OK, Json(HealthResponse { status: "ok".to_string() }))),
+        Err(e) => Err(service_unavailable_error(format!("Database unavailable: {}", e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_height_accepts_a_valid_height() {
+        assert_eq!(parse_height("12345").ok(), Some(12345));
+        assert_eq!(parse_height("0").ok(), Some(0));
+    }
+
+    #[test]
+    fn parse_height_rejects_non_numeric_heights() {
+        let (status, Json(body)) = parse_height("abc").err().unwrap();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.code, 400);
+        assert!(body.error.contains("'abc'"));
+    }
+
+    #[test]
+    fn parse_height_rejects_negative_heights() {
+        let (status, Json(body)) = parse_height("-1").err().unwrap();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body.error.contains("must not be negative"));
+    }
+}
@@ -0,0 +1,92 @@
+/*
+* Debug API module.
+*
+* Exposes internal diagnostics for operators. These routes are only
+* mounted when ENABLE_DEBUG_ENDPOINTS is set.
+*/
+
+use axum::{extract::{Query, State}, http::StatusCode, Json};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use crate::client::last_error::{IndexerError, LastErrorTracker};
+use crate::db;
+use crate::models::block::FailedBlock;
+use super::common::{database_error, ErrorResponse};
+
+/* Default number of failed blocks returned */
+const DEFAULT_FAILED_BLOCKS_LIMIT: i64 = 100;
+
+/* Maximum number of failed blocks returned */
+const MAX_FAILED_BLOCKS_LIMIT: i64 = 1000;
+
+/*
+* Query parameters for the failed blocks endpoint.
+*/
+#[derive(Debug, Deserialize)]
+pub struct FailedBlocksQuery {
+    /// Maximum number of blocks to return (1-1000, default 100)
+    pub limit: Option<i64>,
+}
+
+/*
+* Snapshot of the database connection pool usage.
+*/
+#[derive(Debug, Serialize)]
+pub struct PoolStats {
+    /// Number of open connections, idle or in use
+    pub size: u32,
+
+    /// Number of open connections not currently in use
+    pub idle: usize,
+
+    /// Configured maximum number of connections
+    pub max_connections: u32,
+}
+
+/*
+* Reports the current connection pool usage.
+*
+* @param pool Database connection pool
+* @return JSON response containing the pool statistics
+*/
+pub async fn get_pool_stats(State(pool): State<Pool<Postgres>>) -> (StatusCode, Json<PoolStats>) {
+    let stats = PoolStats {
+        size: pool.size(),
+        idle: pool.num_idle(),
+        max_connections: pool.options().get_max_connections(),
+    };
+    (StatusCode::OK, Json(stats))
+}
+
+/*
+* Reports the most recent indexer error.
+*
+* The body is null when the last processed block was stored successfully.
+*
+* @param last_error Tracker updated by the indexer
+* @return JSON response containing the last error, its height and timestamp
+*/
+pub async fn get_last_error(
+    State(last_error): State<LastErrorTracker>,
+) -> (StatusCode, Json<Option<IndexerError>>) {
+    (StatusCode::OK, Json(last_error.get()))
+}
+
+/*
+* Lists blocks that exhausted their retries, lowest height first.
+*
+* @param pool Database connection pool
+* @param query Optional limit
+* @return JSON response containing the failed blocks with their last error
+*/
+pub async fn get_failed_blocks(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<FailedBlocksQuery>,
+) -> Result<(StatusCode, Json<Vec<FailedBlock>>), (StatusCode, Json<ErrorResponse>)> {
+    let limit = query.limit.unwrap_or(DEFAULT_FAILED_BLOCKS_LIMIT).clamp(1, MAX_FAILED_BLOCKS_LIMIT);
+
+    match db::failed_blocks::get_failed_blocks(&pool, limit).await {
+        Ok(blocks) => Ok((StatusCode::OK, Json(blocks))),
+        Err(e) => Err(database_error(e)),
+    }
+}
@@ -0,0 +1,18 @@
+/*
+* Metrics API module.
+*
+* Exposes the indexer's Prometheus metrics in the text exposition format.
+*/
+
+use axum::extract::State;
+use metrics_exporter_prometheus::PrometheusHandle;
+
+/*
+* Renders the current metrics snapshot.
+*
+* @param handle Handle to the installed Prometheus recorder
+* @return Metrics in Prometheus text format
+*/
+pub async fn get_metrics(State(handle): State<PrometheusHandle>) -> String {
+    handle.render()
+}
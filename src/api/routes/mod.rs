@@ -2,10 +2,19 @@
 * API route handlers module.
 *
 * Organizes and exposes different API route handlers for blocks,
-* transactions, statistics, and common utilities.
+* transactions, statistics, validators, height/time resolution, metrics, WebSocket and SSE streams, debugging, admin actions,
+* pagination headers and common utilities.
 */
 
 pub mod blocks;
 pub mod transactions;
 pub mod stats;
+pub mod validators;
+pub mod resolve;
+pub mod metrics;
+pub mod ws;
+pub mod sse;
+pub mod debug;
+pub mod admin;
 pub mod common;
+pub mod pagination;
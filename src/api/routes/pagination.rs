@@ -0,0 +1,104 @@
+/*
+* Pagination headers for list endpoints.
+*
+* Attaches the standard X-Total-Count header and a GitHub-style Link
+* header to list responses, so generic table libraries can page through
+* the API without reading the response body.
+*/
+
+use axum::{
+    http::{header::LINK, HeaderMap, HeaderValue, StatusCode, Uri},
+    Json,
+};
+
+/* Header carrying the total number of items matching a list query */
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+/*
+* Position of a returned page within the full result set.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct Page {
+    /// Maximum number of items in the page
+    pub limit: i64,
+
+    /// Number of items skipped before the page
+    pub offset: i64,
+}
+
+/*
+* Builds a list response carrying the X-Total-Count header.
+*
+* Used by list endpoints that return their whole result set or have no
+* offset parameter.
+*
+* @param body JSON body of the list response
+* @param total_count Total number of items matching the query
+* @return Status code, count header and JSON body
+*/
+pub fn with_total_count<T>(body: T, total_count: i64) -> (StatusCode, HeaderMap, Json<T>) {
+    let mut headers = HeaderMap::new();
+    headers.insert(TOTAL_COUNT_HEADER, HeaderValue::from(total_count));
+    (StatusCode::OK, headers, Json(body))
+}
+
+/*
+* Builds a list response with pagination headers.
+*
+* The Link header is only set when the request carried pagination
+* parameters, and only lists the neighbouring pages that exist.
+*
+* @param body JSON body of the list response
+* @param total_count Total number of items matching the query
+* @param page Page the body holds, None when no pagination parameters were given
+* @param uri Original request URI the page links are derived from
+* @return Status code, pagination headers and JSON body
+*/
+pub fn paginated<T>(
+    body: T,
+    total_count: i64,
+    page: Option<Page>,
+    uri: &Uri,
+) -> (StatusCode, HeaderMap, Json<T>) {
+    let (status, mut headers, body) = with_total_count(body, total_count);
+
+    if let Some(page) = page {
+        let mut links = Vec::new();
+        if page.offset + page.limit < total_count {
+            links.push(page_link(uri, page.limit, page.offset + page.limit, "next"));
+        }
+        if page.offset > 0 {
+            links.push(page_link(uri, page.limit, (page.offset - page.limit).max(0), "prev"));
+        }
+
+        if !links.is_empty() {
+            if let Ok(value) = HeaderValue::from_str(&links.join(", ")) {
+                headers.insert(LINK, value);
+            }
+        }
+    }
+
+    (status, headers, body)
+}
+
+/*
+* Formats a single Link header entry for another page of the same query.
+*
+* Keeps every query parameter except limit and offset, which are
+* replaced with the target page's values.
+*/
+fn page_link(uri: &Uri, limit: i64, offset: i64, rel: &str) -> String {
+    let mut params: Vec<&str> = uri.query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| {
+            let name = pair.split('=').next().unwrap_or_default();
+            !pair.is_empty() && name != "limit" && name != "offset"
+        })
+        .collect();
+
+    let paging = format!("limit={}&offset={}", limit, offset);
+    params.push(&paging);
+
+    format!("<{}?{}>; rel=\"{}\"", uri.path(), params.join("&"), rel)
+}
@@ -0,0 +1,110 @@
+/*
+* Resolver API module.
+*
+* Converts between block heights and timestamps, e.g. to find the block
+* that was current at a given moment.
+*/
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use sqlx::{Pool, Postgres};
+use utoipa::IntoParams;
+use crate::{db, models::block::BlockTime};
+use super::common::{bad_request_error, database_error, not_found_error, parse_timestamp, ErrorResponse};
+
+/*
+* Query parameters for the height-at-time resolver.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct HeightAtQuery {
+    /// Timestamp to resolve (RFC 3339)
+    #[param(value_type = String, format = DateTime, example = "2025-02-25T15:00:00Z")]
+    pub time: Option<String>,
+}
+
+/*
+* Query parameters for the time-at-height resolver.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TimeAtQuery {
+    /// Block height to resolve
+    #[param(value_type = i64, minimum = 0)]
+    pub height: Option<i64>,
+}
+
+/*
+* Resolves the block height at a timestamp.
+*
+* Returns the last block produced at or before the given time.
+*
+* @param pool Database connection pool
+* @param query Timestamp to resolve
+* @return JSON response containing the block height and its time
+*/
+#[utoipa::path(
+    get,
+    path = "/api/resolve/height-at",
+    tag = "Blocks",
+    params(HeightAtQuery),
+    responses(
+        (status = 200, description = "Height resolved successfully", body = BlockTime),
+        (status = 400, description = "Missing or invalid timestamp", body = ErrorResponse),
+        (status = 404, description = "Timestamp is earlier than every indexed block", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_height_at(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<HeightAtQuery>,
+) -> Result<(StatusCode, Json<BlockTime>), (StatusCode, Json<ErrorResponse>)> {
+    let time = match query.time.as_deref() {
+        Some(raw) => parse_timestamp("time", raw)?,
+        None => return Err(bad_request_error("Missing required 'time' timestamp")),
+    };
+
+    match db::blocks::get_block_at_or_before_time(&pool, time).await {
+        Ok(Some(block)) => Ok((StatusCode::OK, Json(block))),
+        Ok(None) => Err(not_found_error(format!("No indexed block at or before {}", time.to_rfc3339()))),
+        Err(e) => Err(database_error(e)),
+    }
+}
+
+/*
+* Resolves the timestamp of a block height.
+*
+* @param pool Database connection pool
+* @param query Block height to resolve
+* @return JSON response containing the block height and its time
+*/
+#[utoipa::path(
+    get,
+    path = "/api/resolve/time-at",
+    tag = "Blocks",
+    params(TimeAtQuery),
+    responses(
+        (status = 200, description = "Time resolved successfully", body = BlockTime),
+        (status = 400, description = "Missing or negative height", body = ErrorResponse),
+        (status = 404, description = "Block not indexed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_time_at(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<TimeAtQuery>,
+) -> Result<(StatusCode, Json<BlockTime>), (StatusCode, Json<ErrorResponse>)> {
+    let height = match query.height {
+        Some(height) if height >= 0 => height,
+        Some(height) => return Err(bad_request_error(format!("Block height must not be negative, got {}", height))),
+        None => return Err(bad_request_error("Missing required 'height'")),
+    };
+
+    match db::blocks::get_block_time_at_height(&pool, height).await {
+        Ok(Some(block)) => Ok((StatusCode::OK, Json(block))),
+        Ok(None) => Err(not_found_error(format!("Block at height {} not found", height))),
+        Err(e) => Err(database_error(e)),
+    }
+}
@@ -0,0 +1,71 @@
+/*
+* Server-Sent Events API module.
+*
+* Streams newly indexed transactions to browser clients as they are
+* stored by the block synchronizer.
+*/
+
+use std::convert::Infallible;
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::stream::{self, Stream};
+use serde::Deserialize;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{error, warn};
+use crate::models::transaction::TransactionSummary;
+
+/*
+* Query parameters for the transaction feed.
+*/
+#[derive(Debug, Deserialize)]
+pub struct TransactionFeedQuery {
+    /// Only stream transactions of this action type, e.g. "ibc_transfer"
+    pub action_type: Option<String>,
+}
+
+/*
+* Streams new transactions as Server-Sent Events.
+*
+* Each stored transaction is sent as a "transaction" event carrying its
+* TransactionSummary as JSON. Keep-alive comments let a disconnect be
+* noticed even while the filter suppresses every event; the stream, and
+* with it the broadcast subscription, is dropped as soon as the client
+* goes away. Clients that fall behind the broadcast buffer are
+* disconnected and may reconnect.
+*
+* @param tx_feed Broadcast channel the indexer publishes new transactions on
+* @param query Optional action type filter
+* @return Event stream response
+*/
+pub async fn stream_transactions(
+    State(tx_feed): State<broadcast::Sender<TransactionSummary>>,
+    Query(query): Query<TransactionFeedQuery>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = tx_feed.subscribe();
+
+    let events = stream::unfold((receiver, query.action_type), |(mut receiver, action_type)| async move {
+        loop {
+            let summary = match receiver.recv().await {
+                Ok(summary) => summary,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "Dropping lagging SSE client");
+                    return None;
+                }
+                Err(RecvError::Closed) => return None,
+            };
+
+            if action_type.as_deref().is_some_and(|wanted| wanted != summary.action_type) {
+                continue;
+            }
+
+            match Event::default().event("transaction").json_data(&summary) {
+                Ok(event) => return Some((Ok(event), (receiver, action_type))),
+                Err(e) => error!(tx_hash = %summary.tx_hash, error = %e, "Failed to serialize transaction"),
+            }
+        }
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
@@ -1,13 +1,202 @@
-use axum::{extract::State, Json, http::StatusCode};
-use chrono::Utc;
+use axum::{extract::{Query, State}, Json, http::StatusCode};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
 use sqlx::{Pool, Postgres};
 use tracing::{error, instrument};
+use utoipa::IntoParams;
 
 use crate::{
+    client::throughput::ThroughputMeter,
     db::stats::StatsQueries,
-    models::stats::{BurnStats, CurrentBlockStats, StatsResponse, TransactionStats},
+    models::stats::{
+        ActionBreakdown, ActionDistribution, BlockSizeStats, BlockTimeAnomalyList, BurnStats, Coverage, CumulativeBurn, CurrentBlockStats, DailyTransactionCounts, HeightBucketList, IndexerThroughput, NetworkOverview,
+        StatsResponse, SyncHistory, TimezoneTransactionCounts, TotalFees, TransactionStats,
+    },
 };
-use super::common::{database_error, ErrorResponse};
+use super::common::{bad_request_error, database_error, parse_timestamp, service_unavailable_error, ErrorResponse};
+
+/* Number of most recent blocks averaged for the average block time */
+const AVG_BLOCK_TIME_WINDOW: i64 = 100;
+
+/* Number of most recent blocks averaged for the average index delay */
+const AVG_INDEX_DELAY_WINDOW: i64 = 100;
+
+/* Hours of recent blocks whose proposers count as active */
+const ACTIVE_PROPOSER_WINDOW_HOURS: i64 = 24;
+
+/* Default number of days returned by the daily transaction endpoint */
+const DEFAULT_TX_DAILY_DAYS: i64 = 30;
+
+/* Maximum number of days returned by the daily transaction endpoint */
+const MAX_TX_DAILY_DAYS: i64 = 365;
+
+/* Timezone days are bucketed in when none is requested */
+const DEFAULT_TX_COUNT_TIMEZONE: &str = "UTC";
+
+/* Default number of days in the burn history */
+const DEFAULT_BURN_HISTORY_DAYS: i64 = 30;
+
+/* Maximum number of days in the burn history */
+const MAX_BURN_HISTORY_DAYS: i64 = 365;
+
+/* Maximum number of buckets a height range aggregation may span */
+const MAX_HEIGHT_BUCKETS: i64 = 1000;
+
+/* Default gap in seconds above which a block time is reported as an anomaly */
+const DEFAULT_ANOMALY_THRESHOLD_SECS: f64 = 30.0;
+
+/* Default number of anomalies returned by the block time anomaly endpoint */
+const DEFAULT_ANOMALY_LIMIT: i64 = 100;
+
+/* Maximum number of anomalies returned by the block time anomaly endpoint */
+const MAX_ANOMALY_LIMIT: i64 = 1000;
+
+/* Default number of snapshots returned by the sync history endpoint */
+const DEFAULT_SYNC_HISTORY_LIMIT: i64 = 100;
+
+/* Maximum number of snapshots returned by the sync history endpoint */
+const MAX_SYNC_HISTORY_LIMIT: i64 = 1000;
+
+/* Default number of days covered by the block size statistics */
+const DEFAULT_BLOCK_SIZE_DAYS: i64 = 30;
+
+/* Maximum number of days covered by the block size statistics */
+const MAX_BLOCK_SIZE_DAYS: i64 = 365;
+
+/* Default number of days covered by the fee totals */
+const DEFAULT_TOTAL_FEES_DAYS: i64 = 30;
+
+/* Maximum number of days covered by the fee totals */
+const MAX_TOTAL_FEES_DAYS: i64 = 365;
+
+/*
+* Query parameters for the daily transaction endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TxDailyQuery {
+    /// Number of days ending today to return (1-365, default 30)
+    #[param(default = 30, minimum = 1, maximum = 365)]
+    pub days: Option<i64>,
+}
+
+/*
+* Query parameters for the burn statistics endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BurnHistoryQuery {
+    /// Number of days ending today in the burn history (1-365, default 30)
+    #[param(default = 30, minimum = 1, maximum = 365)]
+    pub days: Option<i64>,
+}
+
+/*
+* Query parameters for the block size statistics endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BlockSizeQuery {
+    /// Number of days ending today to cover (1-365, default 30)
+    #[param(default = 30, minimum = 1, maximum = 365)]
+    pub days: Option<i64>,
+}
+
+/*
+* Query parameters for the fee totals endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TotalFeesQuery {
+    /// Number of days ending today to cover (1-365, default 30)
+    #[param(default = 30, minimum = 1, maximum = 365)]
+    pub days: Option<i64>,
+}
+
+/*
+* Query parameters for the timezone-aware transaction count endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TxCountQuery {
+    /// IANA timezone whose calendar days are counted (default UTC)
+    #[param(default = "UTC", example = "America/New_York")]
+    pub tz: Option<String>,
+
+    /// Number of days ending today to return (1-365, default 30)
+    #[param(default = 30, minimum = 1, maximum = 365)]
+    pub days: Option<i64>,
+}
+
+/*
+* Query parameters for the height range aggregation endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct HeightRangeQuery {
+    /// First block height of the range (inclusive)
+    #[param(value_type = i64, minimum = 0)]
+    pub from: Option<i64>,
+
+    /// Last block height of the range (inclusive)
+    #[param(value_type = i64, minimum = 0)]
+    pub to: Option<i64>,
+
+    /// Number of blocks per bucket; the range may span at most 1000 buckets
+    #[param(value_type = i64, minimum = 1)]
+    pub bucket: Option<i64>,
+}
+
+/*
+* Query parameters for the action breakdown endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ActionBreakdownQuery {
+    /// First block height of the window (inclusive)
+    pub from_height: Option<i64>,
+
+    /// Last block height of the window (inclusive)
+    pub to_height: Option<i64>,
+
+    /// Inclusive start of the time window (RFC 3339)
+    #[param(format = DateTime, example = "2025-02-25T00:00:00Z")]
+    pub from: Option<String>,
+
+    /// Exclusive end of the time window (RFC 3339)
+    #[param(format = DateTime, example = "2025-02-26T00:00:00Z")]
+    pub to: Option<String>,
+}
+
+/*
+* Query parameters for the block time anomaly endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BlockTimeAnomalyQuery {
+    /// Minimum gap in seconds to the previous block (default 30)
+    #[param(default = 30.0)]
+    pub threshold: Option<f64>,
+
+    /// Number of most recent anomalies to return (1-1000, default 100)
+    #[param(default = 100, minimum = 1, maximum = 1000)]
+    pub limit: Option<i64>,
+}
+
+/*
+* Query parameters for the action distribution endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ActionDistributionQuery {
+    /// Only count transactions at or after this time (RFC 3339)
+    #[param(format = DateTime, example = "2025-02-25T00:00:00Z")]
+    pub since: Option<String>,
+
+    /// Only count transactions at or above this block height
+    pub from_height: Option<i64>,
+}
+
+/*
+* Query parameters for the sync history endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SyncHistoryQuery {
+    /// Number of most recent snapshots to return (1-1000, default 100)
+    #[param(default = 100, minimum = 1, maximum = 1000)]
+    pub limit: Option<i64>,
+}
 
 #[utoipa::path(
     get,
@@ -15,6 +204,7 @@ use super::common::{database_error, ErrorResponse};
     tag = "Statistics",
     responses(
         (status = 200, description = "Blockchain statistics retrieved successfully", body = StatsResponse),
+        (status = 503, description = "No blocks indexed yet, initial sync in progress", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
@@ -25,7 +215,10 @@ pub async fn get_chain_stats(
     let now = Utc::now();
 
     let latest_block = match StatsQueries::get_latest_block_timing(&pool).await {
-        Ok(block) => block,
+        Ok(Some(block)) => block,
+        Ok(None) => {
+            return Err(service_unavailable_error("Indexer is syncing, data not yet available"));
+        }
         Err(e) => {
             error!("Failed to fetch latest block: {}", e);
             return Err(database_error(e));
@@ -40,9 +233,27 @@ pub async fn get_chain_stats(
         }
     };
 
-    let block_time = (latest_block.timestamp - prev_block.timestamp).num_seconds();
+    // A lone block, or one whose predecessor is not indexed, has no interval yet
+    let block_time = prev_block
+        .map_or(0, |prev| (latest_block.timestamp - prev.timestamp).num_seconds());
     let received_new = (now - latest_block.timestamp).num_seconds().max(0);
 
+    let avg_block_time = match StatsQueries::get_average_block_time(&pool, AVG_BLOCK_TIME_WINDOW).await {
+        Ok(avg) => avg,
+        Err(e) => {
+            error!("Failed to fetch average block time: {}", e);
+            return Err(database_error(e));
+        }
+    };
+
+    let avg_index_delay = match StatsQueries::get_average_index_delay(&pool, AVG_INDEX_DELAY_WINDOW).await {
+        Ok(avg) => avg,
+        Err(e) => {
+            error!("Failed to fetch average index delay: {}", e);
+            return Err(database_error(e));
+        }
+    };
+
     let total_tx_count = match StatsQueries::get_total_transactions(&pool).await {
         Ok(count) => count,
         Err(e) => {
@@ -75,7 +286,7 @@ pub async fn get_chain_stats(
         }
     };
 
-    let burn_history = match StatsQueries::get_burn_history(&pool).await {
+    let burn_history = match StatsQueries::get_burn_history(&pool, DEFAULT_BURN_HISTORY_DAYS).await {
         Ok(history) => history,
         Err(e) => {
             error!("Failed to fetch burn history: {}", e);
@@ -84,10 +295,614 @@ pub async fn get_chain_stats(
     };
 
     let response = StatsResponse::new(
-        CurrentBlockStats::new(latest_block.height, block_time.to_string(), received_new.to_string()),
+        CurrentBlockStats::new(
+            latest_block.height,
+            block_time.to_string(),
+            received_new.to_string(),
+            avg_block_time,
+            avg_index_delay,
+        ),
         TransactionStats::new(total_tx_count, new_today_tx, tx_history),
         BurnStats::new(total_burn, burn_history),
     );
 
     Ok((StatusCode::OK, Json(response)))
 }
+
+/*
+* Retrieves which block heights the indexer has covered.
+*
+* Compares the indexed height range with the chain height from the
+* indexer's last node status check and counts the heights missing inside
+* the range. An empty database reports zeros.
+*
+* @param pool Database connection pool
+* @return JSON response containing the indexer coverage
+*/
+#[utoipa::path(
+    get,
+    path = "/api/coverage",
+    tag = "Statistics",
+    responses(
+        (status = 200, description = "Indexer coverage retrieved successfully", body = Coverage),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_coverage(
+    State(pool): State<Pool<Postgres>>,
+) -> Result<(StatusCode, Json<Coverage>), (StatusCode, Json<ErrorResponse>)> {
+    let result = tokio::try_join!(
+        StatsQueries::get_height_coverage(&pool),
+        StatsQueries::get_latest_chain_height(&pool),
+    );
+
+    let ((first_indexed, latest_indexed, total_indexed, missing_count), chain_height) = match result {
+        Ok(values) => values,
+        Err(e) => {
+            error!("Failed to fetch indexer coverage: {}", e);
+            return Err(database_error(e));
+        }
+    };
+
+    let coverage = Coverage {
+        first_indexed: first_indexed.unwrap_or(0),
+        latest_indexed: latest_indexed.unwrap_or(0),
+        chain_height: chain_height.unwrap_or(0),
+        total_indexed,
+        missing_count,
+    };
+
+    Ok((StatusCode::OK, Json(coverage)))
+}
+
+/*
+* Retrieves the key network metrics in one response.
+*
+* Runs the underlying statistics queries concurrently, so a dashboard
+* landing page needs a single request.
+*
+* @param pool Database connection pool
+* @return JSON response containing the network overview
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/network-overview",
+    tag = "Statistics",
+    responses(
+        (status = 200, description = "Network overview retrieved successfully", body = NetworkOverview),
+        (status = 503, description = "No blocks indexed yet, initial sync in progress", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_network_overview(
+    State(pool): State<Pool<Postgres>>,
+) -> Result<(StatusCode, Json<NetworkOverview>), (StatusCode, Json<ErrorResponse>)> {
+    let active_since = Utc::now() - Duration::hours(ACTIVE_PROPOSER_WINDOW_HOURS);
+
+    let result = tokio::try_join!(
+        StatsQueries::get_latest_block_timing(&pool),
+        StatsQueries::get_average_block_time(&pool, AVG_BLOCK_TIME_WINDOW),
+        StatsQueries::get_total_transactions(&pool),
+        StatsQueries::get_today_transactions(&pool),
+        StatsQueries::get_total_burn(&pool),
+        StatsQueries::count_active_proposers(&pool, active_since),
+        StatsQueries::get_sync_history(&pool, 1),
+    );
+
+    let (latest_block, avg_block_time, total_transactions, today_transactions, total_burn, active_proposers, sync_history) =
+        match result {
+            Ok(values) => values,
+            Err(e) => {
+                error!("Failed to fetch network overview: {}", e);
+                return Err(database_error(e));
+            }
+        };
+
+    let latest_block = match latest_block {
+        Some(block) => block,
+        None => return Err(service_unavailable_error("Indexer is syncing, data not yet available")),
+    };
+
+    let overview = NetworkOverview {
+        latest_height: latest_block.height,
+        avg_block_time_secs: avg_block_time,
+        total_transactions,
+        today_transactions,
+        total_burn,
+        active_proposers,
+        sync_lag: sync_history.last().map(|point| point.lag),
+    };
+
+    Ok((StatusCode::OK, Json(overview)))
+}
+
+/*
+* Retrieves per-day transaction counts.
+*
+* Returns one bucket per calendar day ending today, labeled YYYY-MM-DD,
+* with zero counts for days without blocks.
+*
+* @param pool Database connection pool
+* @param query Optional number of days to return
+* @return JSON response containing the daily transaction counts
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/tx-daily",
+    tag = "Statistics",
+    params(TxDailyQuery),
+    responses(
+        (status = 200, description = "Daily transaction counts retrieved successfully", body = DailyTransactionCounts),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_daily_transactions(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<TxDailyQuery>,
+) -> Result<(StatusCode, Json<DailyTransactionCounts>), (StatusCode, Json<ErrorResponse>)> {
+    let days = query.days.unwrap_or(DEFAULT_TX_DAILY_DAYS).clamp(1, MAX_TX_DAILY_DAYS);
+
+    match StatsQueries::get_daily_transaction_counts(&pool, days).await {
+        Ok(history) => Ok((StatusCode::OK, Json(DailyTransactionCounts::new(history)))),
+        Err(e) => {
+            error!("Failed to fetch daily transaction counts: {}", e);
+            Err(database_error(e))
+        }
+    }
+}
+
+/*
+* Retrieves per-day transaction counts in a timezone.
+*
+* Days run from midnight to midnight in the requested IANA timezone
+* rather than the database server's, so "today" rolls over when it does
+* for the user. Days without blocks report zero.
+*
+* @param pool Database connection pool
+* @param query Timezone and optional number of days to return
+* @return JSON response containing today's count and the daily history
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/tx-count",
+    tag = "Statistics",
+    params(TxCountQuery),
+    responses(
+        (status = 200, description = "Transaction counts retrieved successfully", body = TimezoneTransactionCounts),
+        (status = 400, description = "Unknown timezone", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_transaction_counts_in_timezone(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<TxCountQuery>,
+) -> Result<(StatusCode, Json<TimezoneTransactionCounts>), (StatusCode, Json<ErrorResponse>)> {
+    let timezone = query.tz.unwrap_or_else(|| DEFAULT_TX_COUNT_TIMEZONE.to_string());
+    let days = query.days.unwrap_or(DEFAULT_TX_DAILY_DAYS).clamp(1, MAX_TX_DAILY_DAYS);
+
+    match StatsQueries::is_known_timezone(&pool, &timezone).await {
+        Ok(true) => {}
+        Ok(false) => return Err(bad_request_error(format!("Unknown timezone '{}': expected an IANA name such as America/New_York", timezone))),
+        Err(e) => {
+            error!("Failed to validate timezone: {}", e);
+            return Err(database_error(e));
+        }
+    }
+
+    match StatsQueries::get_daily_transaction_counts_in_timezone(&pool, days, &timezone).await {
+        Ok(history) => Ok((StatusCode::OK, Json(TimezoneTransactionCounts::new(timezone, history)))),
+        Err(e) => {
+            error!("Failed to fetch daily transaction counts: {}", e);
+            Err(database_error(e))
+        }
+    }
+}
+
+/*
+* Retrieves transaction counts bucketed by block height.
+*
+* Groups the blocks in [from, to] into windows of `bucket` heights
+* aligned to multiples of the bucket size and sums their transactions.
+* Buckets without indexed blocks are omitted.
+*
+* @param pool Database connection pool
+* @param query Height range and bucket size
+* @return JSON response containing the bucketed transaction counts
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/tx-by-height-range",
+    tag = "Statistics",
+    params(HeightRangeQuery),
+    responses(
+        (status = 200, description = "Bucketed transaction counts retrieved successfully", body = HeightBucketList),
+        (status = 400, description = "Missing or invalid range or bucket size", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_tx_by_height_range(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<HeightRangeQuery>,
+) -> Result<(StatusCode, Json<HeightBucketList>), (StatusCode, Json<ErrorResponse>)> {
+    let (from, to, bucket) = match (query.from, query.to, query.bucket) {
+        (Some(from), Some(to), Some(bucket)) => (from, to, bucket),
+        _ => return Err(bad_request_error("'from', 'to' and 'bucket' are required")),
+    };
+    if from < 0 || from > to {
+        return Err(bad_request_error("'from' must be non-negative and not greater than 'to'"));
+    }
+    if bucket < 1 {
+        return Err(bad_request_error("'bucket' must be at least 1"));
+    }
+    if (to - from) / bucket >= MAX_HEIGHT_BUCKETS {
+        return Err(bad_request_error(format!(
+            "Range spans more than {} buckets, use a larger 'bucket'",
+            MAX_HEIGHT_BUCKETS
+        )));
+    }
+
+    match StatsQueries::get_tx_counts_by_height_bucket(&pool, from, to, bucket).await {
+        Ok(buckets) => Ok((StatusCode::OK, Json(HeightBucketList::new(buckets)))),
+        Err(e) => {
+            error!("Failed to fetch transaction counts by height: {}", e);
+            Err(database_error(e))
+        }
+    }
+}
+
+/*
+* Retrieves transaction counts per action type.
+*
+* Aggregates the per-block action counts over an optional height and
+* time window. Without bounds the whole chain is covered.
+*
+* @param pool Database connection pool
+* @param query Optional height and time bounds
+* @return JSON response containing the action type breakdown
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/action-breakdown",
+    tag = "Statistics",
+    params(ActionBreakdownQuery),
+    responses(
+        (status = 200, description = "Action breakdown retrieved successfully", body = ActionBreakdown),
+        (status = 400, description = "Invalid height or time window", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_action_breakdown(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<ActionBreakdownQuery>,
+) -> Result<(StatusCode, Json<ActionBreakdown>), (StatusCode, Json<ErrorResponse>)> {
+    if let (Some(from_height), Some(to_height)) = (query.from_height, query.to_height) {
+        if from_height > to_height {
+            return Err(bad_request_error("'from_height' must not be greater than 'to_height'"));
+        }
+    }
+
+    let from = match query.from.as_deref() {
+        Some(raw) => Some(parse_timestamp("from", raw)?),
+        None => None,
+    };
+    let to = match query.to.as_deref() {
+        Some(raw) => Some(parse_timestamp("to", raw)?),
+        None => None,
+    };
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(bad_request_error("'from' must not be later than 'to'"));
+        }
+    }
+
+    match StatsQueries::get_action_breakdown(&pool, query.from_height, query.to_height, from, to).await {
+        Ok(actions) => Ok((StatusCode::OK, Json(ActionBreakdown::new(actions)))),
+        Err(e) => {
+            error!("Failed to fetch action breakdown: {}", e);
+            Err(database_error(e))
+        }
+    }
+}
+
+/*
+* Retrieves recent node sync status snapshots.
+*
+* Returns the latest chain and indexed heights recorded by the polling
+* loop in chronological order, for charting indexer lag over time.
+*
+* @param pool Database connection pool
+* @param query Optional number of snapshots to return
+* @return JSON response containing the sync history
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/sync-history",
+    tag = "Statistics",
+    params(SyncHistoryQuery),
+    responses(
+        (status = 200, description = "Sync history retrieved successfully", body = SyncHistory),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_sync_history(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<SyncHistoryQuery>,
+) -> Result<(StatusCode, Json<SyncHistory>), (StatusCode, Json<ErrorResponse>)> {
+    let limit = query.limit.unwrap_or(DEFAULT_SYNC_HISTORY_LIMIT).clamp(1, MAX_SYNC_HISTORY_LIMIT);
+
+    match StatsQueries::get_sync_history(&pool, limit).await {
+        Ok(history) => Ok((StatusCode::OK, Json(SyncHistory::new(history)))),
+        Err(e) => {
+            error!("Failed to fetch sync history: {}", e);
+            Err(database_error(e))
+        }
+    }
+}
+
+/*
+* Retrieves blocks that arrived unusually long after their predecessor.
+*
+* Reports heights where the gap to the previous block exceeded the
+* threshold, which points at chain halts or missed proposer rounds.
+*
+* @param pool Database connection pool
+* @param query Optional gap threshold and limit
+* @return JSON response containing the most recent anomalies
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/block-time-anomalies",
+    tag = "Statistics",
+    params(BlockTimeAnomalyQuery),
+    responses(
+        (status = 200, description = "Block time anomalies retrieved successfully", body = BlockTimeAnomalyList),
+        (status = 400, description = "Invalid threshold", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_block_time_anomalies(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<BlockTimeAnomalyQuery>,
+) -> Result<(StatusCode, Json<BlockTimeAnomalyList>), (StatusCode, Json<ErrorResponse>)> {
+    let threshold = query.threshold.unwrap_or(DEFAULT_ANOMALY_THRESHOLD_SECS);
+    if !threshold.is_finite() || threshold < 0.0 {
+        return Err(bad_request_error("'threshold' must be a non-negative number of seconds"));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_ANOMALY_LIMIT).clamp(1, MAX_ANOMALY_LIMIT);
+
+    match StatsQueries::get_block_time_anomalies(&pool, threshold, limit).await {
+        Ok(anomalies) => Ok((StatusCode::OK, Json(BlockTimeAnomalyList::new(anomalies)))),
+        Err(e) => {
+            error!("Failed to fetch block time anomalies: {}", e);
+            Err(database_error(e))
+        }
+    }
+}
+
+/*
+* Retrieves the current indexing throughput.
+*
+* Reports how many blocks the indexer stored over the last minute,
+* useful for estimating how long a full sync will take.
+*
+* @param throughput Meter shared with the indexer
+* @return JSON response containing the indexing rate
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/indexer-throughput",
+    tag = "Statistics",
+    responses(
+        (status = 200, description = "Indexer throughput retrieved successfully", body = IndexerThroughput)
+    )
+)]
+pub async fn get_indexer_throughput(
+    State(throughput): State<ThroughputMeter>,
+) -> (StatusCode, Json<IndexerThroughput>) {
+    let response = IndexerThroughput {
+        blocks_last_minute: throughput.blocks_last_minute(),
+        blocks_per_sec: throughput.blocks_per_sec(),
+    };
+    (StatusCode::OK, Json(response))
+}
+
+/*
+* Retrieves block size statistics.
+*
+* Returns the minimum, maximum, average and 95th percentile size of the
+* blocks produced in the window, computed in a single aggregate query.
+* Blocks stored before sizes were tracked are left out, and an empty
+* window reports zeros.
+*
+* @param pool Database connection pool
+* @param query Optional number of days to cover
+* @return JSON response containing the block size statistics
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/block-sizes",
+    tag = "Statistics",
+    params(BlockSizeQuery),
+    responses(
+        (status = 200, description = "Block size statistics retrieved successfully", body = BlockSizeStats),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_block_size_stats(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<BlockSizeQuery>,
+) -> Result<(StatusCode, Json<BlockSizeStats>), (StatusCode, Json<ErrorResponse>)> {
+    let days = query.days.unwrap_or(DEFAULT_BLOCK_SIZE_DAYS).clamp(1, MAX_BLOCK_SIZE_DAYS);
+
+    match StatsQueries::get_block_size_stats(&pool, days).await {
+        Ok(stats) => Ok((StatusCode::OK, Json(stats))),
+        Err(e) => {
+            error!("Failed to fetch block size statistics: {}", e);
+            Err(database_error(e))
+        }
+    }
+}
+
+/*
+* Retrieves the fees paid over a window of days.
+*
+* Sums the fees of the transactions included in the window separately for
+* each fee denomination. Transactions indexed before fees were decoded are
+* not counted, and an empty window returns no totals.
+*
+* @param pool Database connection pool
+* @param query Optional number of days to cover
+* @return JSON response containing the fee totals per denomination
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/total-fees",
+    tag = "Statistics",
+    params(TotalFeesQuery),
+    responses(
+        (status = 200, description = "Fee totals retrieved successfully", body = TotalFees),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_total_fees(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<TotalFeesQuery>,
+) -> Result<(StatusCode, Json<TotalFees>), (StatusCode, Json<ErrorResponse>)> {
+    let days = query.days.unwrap_or(DEFAULT_TOTAL_FEES_DAYS).clamp(1, MAX_TOTAL_FEES_DAYS);
+
+    match StatsQueries::get_total_fees(&pool, days).await {
+        Ok(totals) => Ok((StatusCode::OK, Json(TotalFees::new(days, totals)))),
+        Err(e) => {
+            error!("Failed to fetch fee totals: {}", e);
+            Err(database_error(e))
+        }
+    }
+}
+
+/*
+* Retrieves chain-wide token burn totals.
+*
+* Returns the total burned amount both formatted for display and as a
+* full-precision number, along with the daily burn history.
+*
+* @param pool Database connection pool
+* @param query Optional number of history days
+* @return JSON response containing the burn statistics
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/burn",
+    tag = "Statistics",
+    params(BurnHistoryQuery),
+    responses(
+        (status = 200, description = "Burn statistics retrieved successfully", body = BurnStats),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_burn_stats(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<BurnHistoryQuery>,
+) -> Result<(StatusCode, Json<BurnStats>), (StatusCode, Json<ErrorResponse>)> {
+    let days = query.days.unwrap_or(DEFAULT_BURN_HISTORY_DAYS).clamp(1, MAX_BURN_HISTORY_DAYS);
+
+    let total_burn = match StatsQueries::get_total_burn(&pool).await {
+        Ok(burn) => burn,
+        Err(e) => {
+            error!("Failed to fetch total burn: {}", e);
+            return Err(database_error(e));
+        }
+    };
+
+    let burn_history = match StatsQueries::get_burn_history(&pool, days).await {
+        Ok(history) => history,
+        Err(e) => {
+            error!("Failed to fetch burn history: {}", e);
+            return Err(database_error(e));
+        }
+    };
+
+    Ok((StatusCode::OK, Json(BurnStats::new(total_burn, burn_history))))
+}
+
+/*
+* Retrieves the running total of burned UM per day.
+*
+* Each point is the cumulative burn up to and including that day, which
+* is what a supply chart needs.
+*
+* @param pool Database connection pool
+* @return JSON response containing the cumulative burn history
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/cumulative-burn",
+    tag = "Statistics",
+    responses(
+        (status = 200, description = "Cumulative burn retrieved successfully", body = CumulativeBurn),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_cumulative_burn(
+    State(pool): State<Pool<Postgres>>,
+) -> Result<(StatusCode, Json<CumulativeBurn>), (StatusCode, Json<ErrorResponse>)> {
+    match StatsQueries::get_cumulative_burn(&pool).await {
+        Ok(history) => Ok((StatusCode::OK, Json(CumulativeBurn::new(history)))),
+        Err(e) => {
+            error!("Failed to fetch cumulative burn: {}", e);
+            Err(database_error(e))
+        }
+    }
+}
+
+/*
+* Retrieves each action type's share of transactions.
+*
+* Counts transactions per action type along with their percentage of
+* the total, for distribution charts. Without bounds all transactions
+* are counted.
+*
+* @param pool Database connection pool
+* @param query Optional start time and height
+* @return JSON response containing the action type distribution
+*/
+#[utoipa::path(
+    get,
+    path = "/api/stats/action-distribution",
+    tag = "Statistics",
+    params(ActionDistributionQuery),
+    responses(
+        (status = 200, description = "Action distribution retrieved successfully", body = ActionDistribution),
+        (status = 400, description = "Invalid since timestamp", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[instrument(skip(pool))]
+pub async fn get_action_distribution(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<ActionDistributionQuery>,
+) -> Result<(StatusCode, Json<ActionDistribution>), (StatusCode, Json<ErrorResponse>)> {
+    let since = match query.since.as_deref() {
+        Some(raw) => Some(parse_timestamp("since", raw)?),
+        None => None,
+    };
+
+    match StatsQueries::get_action_distribution(&pool, since, query.from_height).await {
+        Ok(actions) => Ok((StatusCode::OK, Json(ActionDistribution::new(actions)))),
+        Err(e) => {
+            error!("Failed to fetch action distribution: {}", e);
+            Err(database_error(e))
+        }
+    }
+}
@@ -2,83 +2,499 @@
 * Transaction API module.
 *
 * Provides endpoints for retrieving transaction data from the database,
-* including fetching recent transactions and transactions by block height.
+* including fetching recent transactions, transactions by block height
+* and decoded transaction contents.
 */
 
-use axum::{extract::{State, Path}, http::StatusCode, Json};
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
 use sqlx::{Pool, Postgres};
-use crate::{db, models::transaction::TransactionList};
-use super::common::{database_error, not_found_error, ErrorResponse};
+use utoipa::IntoParams;
+use crate::{
+    client::decode::{self, DELEGATE_ACTION, IBC_TRANSFER_ACTION, UNDELEGATE_ACTION},
+    db,
+    models::{amount::TokenAmount, transaction::{DecodedTransaction, NetDelegationList, SortOrder, TransactionList}},
+};
+use super::common::{
+    bad_request_error, database_error, not_found_error, parse_height, unprocessable_entity_error, ErrorResponse,
+};
+use super::pagination::{paginated, Page};
+
+/* Default number of transactions returned by the latest transactions endpoint */
+const DEFAULT_TRANSACTION_LIMIT: i64 = 50;
+
+/* Maximum number of transactions returned by the latest transactions endpoint */
+const MAX_TRANSACTION_LIMIT: i64 = 200;
+
+/*
+* Query parameters for the paginated transaction list endpoints.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct LatestTransactionsQuery {
+    /// Maximum number of transactions to return (1-200, default 50)
+    #[param(value_type = Option<i64>, default = 50, minimum = 1, maximum = 200)]
+    pub limit: Option<String>,
+
+    /// Number of transactions to skip (default 0)
+    #[param(value_type = Option<i64>, default = 0, minimum = 0)]
+    pub offset: Option<String>,
+}
+
+impl LatestTransactionsQuery {
+    /*
+    * Resolves the requested limit.
+    *
+    * Falls back to the default when absent or not a number and clamps
+    * numeric values into the allowed range.
+    */
+    fn limit(&self) -> i64 {
+        self.limit
+            .as_deref()
+            .and_then(|raw| raw.parse::<i64>().ok())
+            .map_or(DEFAULT_TRANSACTION_LIMIT, |limit| limit.clamp(1, MAX_TRANSACTION_LIMIT))
+    }
+
+    /*
+    * Resolves the requested offset, treating absent, invalid or negative
+    * values as zero.
+    */
+    fn offset(&self) -> i64 {
+        self.offset
+            .as_deref()
+            .and_then(|raw| raw.parse::<i64>().ok())
+            .map_or(0, |offset| offset.max(0))
+    }
+
+    /*
+    * Resolves the requested page, None when no pagination parameters were given.
+    */
+    fn page(&self) -> Option<Page> {
+        if self.limit.is_none() && self.offset.is_none() {
+            return None;
+        }
+        Some(Page { limit: self.limit(), offset: self.offset() })
+    }
+}
+
+/*
+* Ordering parameter for the latest transactions endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TransactionOrderQuery {
+    /// Block height ordering, `asc` or `desc` (default `desc`)
+    #[param(inline)]
+    pub order: Option<SortOrder>,
+}
+
+/*
+* Amount threshold parameter for the large transactions endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AmountThresholdQuery {
+    /// Minimum amount in base units, a non-negative integer
+    #[param(value_type = String, example = "1000000")]
+    pub min_amount: Option<String>,
+}
+
+/*
+* Limit parameter for the net delegations endpoint.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct NetDelegationsQuery {
+    /// Maximum number of validators to return (1-200, default 50)
+    #[param(value_type = Option<i64>, default = 50, minimum = 1, maximum = 200)]
+    pub limit: Option<String>,
+}
+
+impl NetDelegationsQuery {
+    /*
+    * Resolves the requested limit, falling back to the default when absent
+    * or not a number and clamping numeric values into the allowed range.
+    */
+    fn limit(&self) -> i64 {
+        self.limit
+            .as_deref()
+            .and_then(|raw| raw.parse::<i64>().ok())
+            .map_or(DEFAULT_TRANSACTION_LIMIT, |limit| limit.clamp(1, MAX_TRANSACTION_LIMIT))
+    }
+}
 
 /*
 * Retrieves the latest transactions.
 *
-* Fetches a list of the most recent transactions.
+* Fetches a list of the most recent transactions, or the oldest ones
+* when ascending order is requested.
 *
 * @param pool Database connection pool
-* @return JSON response containing recent transactions
+* @param uri Original request URI used for the page links
+* @param query Optional limit and offset
+* @param order Optional block height ordering
+* @return JSON response containing recent transactions with pagination headers
 */
 #[utoipa::path(
     get,
     path = "/api/transactions",
     tag = "Transactions",
+    params(LatestTransactionsQuery, TransactionOrderQuery),
     responses(
-        (status = 200, description = "Latest transactions retrieved successfully", body = TransactionList),
+        (status = 200, description = "Latest transactions retrieved successfully", body = TransactionList, headers(
+            ("x-total-count" = i64, description = "Total number of indexed transactions"),
+            ("link" = String, description = "Next and previous page URLs when limit or offset is given")
+        )),
+        (status = 400, description = "Invalid order", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 pub async fn get_latest_transactions(
     State(pool): State<Pool<Postgres>>,
-) -> Result<(StatusCode, Json<TransactionList>), (StatusCode, Json<ErrorResponse>)> {
-    match db::transactions::get_latest_transactions(&pool, 50).await {
-        Ok(transactions) => {
-            let summaries = transactions.into_iter()
-                .map(|tx| tx.to_summary())
-                .collect();
-            let response = TransactionList::new(summaries);
-            Ok((StatusCode::OK, Json(response)))
-        }
-        Err(e) => Err(database_error(e)),
-    }
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<LatestTransactionsQuery>,
+    Query(order): Query<TransactionOrderQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let order = order.order.unwrap_or_default();
+
+    let transactions = match db::transactions::get_latest_transactions(&pool, query.limit(), query.offset(), order).await {
+        Ok(transactions) => transactions,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let total_count = match db::transactions::count_transactions(&pool).await {
+        Ok(count) => count,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let summaries = transactions.into_iter()
+        .map(|tx| tx.to_summary())
+        .collect();
+    let response = TransactionList::new(summaries, total_count);
+    Ok(paginated(response, total_count, query.page(), &uri))
 }
 
 /*
 * Retrieves transactions for a specific block height.
 *
-* Returns all transactions associated with a given block height.
+* Returns all transactions associated with a given block height, or a
+* page of them when limit or offset is given. Unknown blocks are a 404;
+* a block without transactions, or an offset past the end, returns an
+* empty list.
 *
 * @param pool Database connection pool
+* @param uri Original request URI used for the page links
 * @param height Block height to query
-* @return JSON response containing transactions for the specified block
+* @param query Optional limit and offset
+* @return JSON response containing transactions for the specified block with pagination headers
 */
 #[utoipa::path(
     get,
     path = "/api/blocks/{height}/transactions",
     tag = "Transactions",
     params(
-        ("height" = i64, Path, description = "Block height to retrieve transactions for")
+        ("height" = i64, Path, description = "Block height to retrieve transactions for"),
+        LatestTransactionsQuery
     ),
     responses(
-        (status = 200, description = "Transactions retrieved successfully", body = TransactionList),
-        (status = 404, description = "No transactions found for the specified block height", body = ErrorResponse),
+        (status = 200, description = "Transactions retrieved successfully", body = TransactionList, headers(
+            ("x-total-count" = i64, description = "Number of transactions in the block"),
+            ("link" = String, description = "Next and previous page URLs when limit or offset is given")
+        )),
+        (status = 400, description = "Invalid block height", body = ErrorResponse),
+        (status = 404, description = "Block not found", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 pub async fn get_transactions_by_block_height(
     State(pool): State<Pool<Postgres>>,
-    Path(height): Path<i64>,
-) -> Result<(StatusCode, Json<TransactionList>), (StatusCode, Json<ErrorResponse>)> {
-    match db::transactions::get_transactions_by_block_height(&pool, height).await {
-        Ok(transactions) => {
-            if transactions.is_empty() {
-                return Err(not_found_error(format!("No transactions found for block at height {}", height)));
-            }
-            let summaries = transactions.into_iter()
-                .map(|tx| tx.to_summary())
-                .collect();
-            let response = TransactionList::new(summaries);
-            Ok((StatusCode::OK, Json(response)))
+    OriginalUri(uri): OriginalUri,
+    Path(height): Path<String>,
+    Query(query): Query<LatestTransactionsQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let height = parse_height(&height)?;
+    let page = query.page();
+
+    let transactions = match page {
+        Some(page) => db::transactions::get_transactions_by_block_height_paged(&pool, height, page.limit, page.offset).await,
+        None => db::transactions::get_transactions_by_block_height(&pool, height).await,
+    };
+    let transactions = match transactions {
+        Ok(transactions) => transactions,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    // Only an empty result needs the block lookup, to tell an empty block from an unknown one
+    if transactions.is_empty() {
+        match db::blocks::get_block_by_height(&pool, height).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return Err(not_found_error(format!("Block at height {} not found", height))),
+            Err(e) => return Err(database_error(e)),
         }
+    }
+
+    let total_count = match page {
+        Some(_) => match db::transactions::count_transactions_by_block_height(&pool, height).await {
+            Ok(count) => count,
+            Err(e) => return Err(database_error(e)),
+        },
+        // All of the block's transactions are returned, so the page is the total
+        None => transactions.len() as i64,
+    };
+
+    let summaries = transactions.into_iter()
+        .map(|tx| tx.to_summary())
+        .collect();
+    let response = TransactionList::new(summaries, total_count);
+    Ok(paginated(response, total_count, page, &uri))
+}
+
+/*
+* Retrieves the latest IBC transfers.
+*
+* Returns inbound and outbound ICS-20 transfers with their channel
+* details in the metadata field.
+*
+* @param pool Database connection pool
+* @param uri Original request URI used for the page links
+* @param query Optional limit and offset
+* @return JSON response containing recent IBC transfers with pagination headers
+*/
+#[utoipa::path(
+    get,
+    path = "/api/transactions/ibc",
+    tag = "Transactions",
+    params(LatestTransactionsQuery),
+    responses(
+        (status = 200, description = "Latest IBC transfers retrieved successfully", body = TransactionList, headers(
+            ("x-total-count" = i64, description = "Total number of IBC transfers"),
+            ("link" = String, description = "Next and previous page URLs when limit or offset is given")
+        )),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_ibc_transfers(
+    State(pool): State<Pool<Postgres>>,
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<LatestTransactionsQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let transactions = match db::transactions::get_transactions_by_action(
+        &pool,
+        &[IBC_TRANSFER_ACTION],
+        query.limit(),
+        query.offset(),
+    ).await {
+        Ok(transactions) => transactions,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let total_count = match db::transactions::count_transactions_by_action(&pool, &[IBC_TRANSFER_ACTION]).await {
+        Ok(count) => count,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let summaries = transactions.into_iter()
+        .map(|tx| tx.to_summary())
+        .collect();
+    let response = TransactionList::new(summaries, total_count);
+    Ok(paginated(response, total_count, query.page(), &uri))
+}
+
+/*
+* Retrieves the latest staking transactions.
+*
+* Returns delegations and undelegations with the validator identity and
+* the unbonded and delegation token amounts in the metadata field.
+*
+* @param pool Database connection pool
+* @param uri Original request URI used for the page links
+* @param query Optional limit and offset
+* @return JSON response containing recent staking transactions with pagination headers
+*/
+#[utoipa::path(
+    get,
+    path = "/api/transactions/staking",
+    tag = "Transactions",
+    params(LatestTransactionsQuery),
+    responses(
+        (status = 200, description = "Latest staking transactions retrieved successfully", body = TransactionList, headers(
+            ("x-total-count" = i64, description = "Total number of delegations and undelegations"),
+            ("link" = String, description = "Next and previous page URLs when limit or offset is given")
+        )),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_staking_transactions(
+    State(pool): State<Pool<Postgres>>,
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<LatestTransactionsQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let action_types = [DELEGATE_ACTION, UNDELEGATE_ACTION];
+
+    let transactions = match db::transactions::get_transactions_by_action(
+        &pool,
+        &action_types,
+        query.limit(),
+        query.offset(),
+    ).await {
+        Ok(transactions) => transactions,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let total_count = match db::transactions::count_transactions_by_action(&pool, &action_types).await {
+        Ok(count) => count,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let summaries = transactions.into_iter()
+        .map(|tx| tx.to_summary())
+        .collect();
+    let response = TransactionList::new(summaries, total_count);
+    Ok(paginated(response, total_count, query.page(), &uri))
+}
+
+/*
+* Retrieves the net UM delegated to each validator.
+*
+* Sums the unbonded amount of every indexed delegation minus every
+* undelegation, grouped by validator identity key.
+*
+* @param pool Database connection pool
+* @param query Optional limit
+* @return JSON response containing validators ordered by net delegation, largest first
+*/
+#[utoipa::path(
+    get,
+    path = "/api/transactions/staking/net-delegations",
+    tag = "Transactions",
+    params(NetDelegationsQuery),
+    responses(
+        (status = 200, description = "Net delegations retrieved successfully", body = NetDelegationList),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_net_delegations(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<NetDelegationsQuery>,
+) -> Result<(StatusCode, Json<NetDelegationList>), (StatusCode, Json<ErrorResponse>)> {
+    match db::transactions::get_net_delegations(&pool, DELEGATE_ACTION, UNDELEGATE_ACTION, query.limit()).await {
+        Ok(validators) => Ok((StatusCode::OK, Json(NetDelegationList::new(validators)))),
         Err(e) => Err(database_error(e)),
     }
-}
\ No newline at end of file
+}
+
+/*
+* Retrieves the largest transactions above an amount threshold.
+*
+* Transactions without an amount are never included.
+*
+* @param pool Database connection pool
+* @param uri Original request URI used for the page links
+* @param query Optional limit and offset
+* @param threshold Minimum amount to include
+* @return JSON response containing matching transactions, largest first, with pagination headers
+*/
+#[utoipa::path(
+    get,
+    path = "/api/transactions/large",
+    tag = "Transactions",
+    params(LatestTransactionsQuery, AmountThresholdQuery),
+    responses(
+        (status = 200, description = "Large transactions retrieved successfully", body = TransactionList, headers(
+            ("x-total-count" = i64, description = "Total number of transactions at or above min_amount"),
+            ("link" = String, description = "Next and previous page URLs when limit or offset is given")
+        )),
+        (status = 400, description = "Missing or invalid min_amount", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_large_transactions(
+    State(pool): State<Pool<Postgres>>,
+    OriginalUri(uri): OriginalUri,
+    Query(query): Query<LatestTransactionsQuery>,
+    Query(threshold): Query<AmountThresholdQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let min_amount = match threshold.min_amount.as_deref() {
+        Some(raw) => match raw.parse::<TokenAmount>() {
+            Ok(amount) => amount,
+            Err(_) => return Err(bad_request_error(format!(
+                "Invalid min_amount '{}': expected a non-negative integer", raw
+            ))),
+        },
+        None => return Err(bad_request_error("Missing required 'min_amount'")),
+    };
+
+    let transactions = match db::transactions::get_transactions_above_amount(
+        &pool,
+        min_amount,
+        query.limit(),
+        query.offset(),
+    ).await {
+        Ok(transactions) => transactions,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let total_count = match db::transactions::count_transactions_above_amount(&pool, min_amount).await {
+        Ok(count) => count,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let summaries = transactions.into_iter()
+        .map(|tx| tx.to_summary())
+        .collect();
+    let response = TransactionList::new(summaries, total_count);
+    Ok(paginated(response, total_count, query.page(), &uri))
+}
+
+/*
+* Retrieves the decoded contents of a transaction.
+*
+* Decodes the stored base64 protobuf data into a structured list of
+* actions.
+*
+* @param pool Database connection pool
+* @param tx_hash Hash of the transaction to decode
+* @return JSON response containing the decoded actions
+*/
+#[utoipa::path(
+    get,
+    path = "/api/transactions/{tx_hash}/decoded",
+    tag = "Transactions",
+    params(
+        ("tx_hash" = String, Path, description = "Hash of the transaction to decode")
+    ),
+    responses(
+        (status = 200, description = "Transaction decoded successfully", body = DecodedTransaction),
+        (status = 404, description = "Transaction not found", body = ErrorResponse),
+        (status = 422, description = "Stored transaction data could not be decoded", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_decoded_transaction(
+    State(pool): State<Pool<Postgres>>,
+    Path(tx_hash): Path<String>,
+) -> Result<(StatusCode, Json<DecodedTransaction>), (StatusCode, Json<ErrorResponse>)> {
+    let transaction = match db::transactions::get_transaction_by_hash(&pool, &tx_hash).await {
+        Ok(Some(transaction)) => transaction,
+        Ok(None) => return Err(not_found_error(format!("Transaction {} not found", tx_hash))),
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let decoded = match decode::decode_transaction(&transaction.data) {
+        Ok(decoded) => decoded,
+        Err(e) => {
+            return Err(unprocessable_entity_error(format!(
+                "Stored data for transaction {} could not be decoded: {}",
+                tx_hash, e
+            )));
+        }
+    };
+
+    let response = DecodedTransaction {
+        tx_hash: transaction.tx_hash,
+        block_height: transaction.block_height,
+        actions: decode::describe_actions(&decoded),
+    };
+    Ok((StatusCode::OK, Json(response)))
+}
@@ -0,0 +1,201 @@
+/*
+* Validator API module.
+*
+* Provides endpoints for retrieving statistics about block proposers
+* and the blocks each of them proposed.
+*/
+
+use axum::{extract::{Path, Query, State}, http::StatusCode, response::IntoResponse, Json};
+use serde::Deserialize;
+use sqlx::{Pool, Postgres};
+use utoipa::IntoParams;
+use crate::{db::{self, stats::StatsQueries}, models::{block::BlockList, stats::{ProposerMatchList, ProposerStatsList}}};
+use super::common::{bad_request_error, database_error, ErrorResponse};
+use super::pagination::with_total_count;
+
+/* Default number of blocks returned by the proposer blocks endpoint */
+const DEFAULT_PROPOSER_BLOCKS_LIMIT: i64 = 100;
+
+/* Maximum number of blocks returned by the proposer blocks endpoint */
+const MAX_PROPOSER_BLOCKS_LIMIT: i64 = 1000;
+
+/* Default number of proposers returned by the search endpoint */
+const DEFAULT_SEARCH_LIMIT: i64 = 10;
+
+/* Maximum number of proposers returned by the search endpoint */
+const MAX_SEARCH_LIMIT: i64 = 100;
+
+/*
+* Query parameters for the validator leaderboard.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ValidatorQuery {
+    /// Maximum number of validators to return (at least 1, all when omitted)
+    #[param(minimum = 1)]
+    pub limit: Option<i64>,
+}
+
+/*
+* Query parameters for a proposer's blocks.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ProposerBlocksQuery {
+    /// Maximum number of blocks to return (1-1000, default 100)
+    #[param(default = 100, minimum = 1, maximum = 1000)]
+    pub limit: Option<i64>,
+
+    /// Only return blocks below this height, for paging to older blocks
+    pub before_height: Option<i64>,
+}
+
+/*
+* Query parameters for the proposer address search.
+*/
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ValidatorSearchQuery {
+    /// Hex prefix of the proposer address, matched case-insensitively
+    #[param(value_type = String, pattern = "^[0-9A-Fa-f]+$")]
+    pub prefix: Option<String>,
+
+    /// Maximum number of proposers to return (1-100, default 10)
+    #[param(default = 10, minimum = 1, maximum = 100)]
+    pub limit: Option<i64>,
+}
+
+/*
+* Retrieves per-proposer block statistics.
+*
+* Returns the number of blocks proposed and transactions included by
+* each validator, ordered by blocks proposed. The total count covers
+* every proposer, not just those within the limit.
+*
+* @param pool Database connection pool
+* @param query Optional limit on the leaderboard length
+* @return JSON response containing proposer statistics
+*/
+#[utoipa::path(
+    get,
+    path = "/api/validators",
+    tag = "Validators",
+    params(ValidatorQuery),
+    responses(
+        (status = 200, description = "Validator statistics retrieved successfully", body = ProposerStatsList, headers(
+            ("x-total-count" = i64, description = "Total number of distinct proposers")
+        )),
+        (status = 400, description = "Invalid limit", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_validators(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<ValidatorQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    if let Some(limit) = query.limit {
+        if limit < 1 {
+            return Err(bad_request_error(format!("Limit must be at least 1, got {}", limit)));
+        }
+    }
+
+    let validators = match StatsQueries::get_proposer_stats(&pool, query.limit).await {
+        Ok(validators) => validators,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let total_count = match StatsQueries::count_proposers(&pool).await {
+        Ok(count) => count,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let response = ProposerStatsList::new(validators, total_count);
+    Ok(with_total_count(response, total_count))
+}
+
+/*
+* Retrieves the blocks proposed by a validator.
+*
+* Returns block summaries newest first. Passing the lowest returned
+* height as `before_height` fetches the next page. Unknown proposers
+* yield an empty list.
+*
+* @param pool Database connection pool
+* @param address Proposer address
+* @param query Optional limit and paging height
+* @return JSON response containing the proposer's blocks
+*/
+#[utoipa::path(
+    get,
+    path = "/api/validators/{address}/blocks",
+    tag = "Validators",
+    params(
+        ("address" = String, Path, description = "Proposer address"),
+        ProposerBlocksQuery
+    ),
+    responses(
+        (status = 200, description = "Proposer blocks retrieved successfully", body = BlockList, headers(
+            ("x-total-count" = i64, description = "Total number of blocks proposed by the validator")
+        )),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_proposer_blocks(
+    State(pool): State<Pool<Postgres>>,
+    Path(address): Path<String>,
+    Query(query): Query<ProposerBlocksQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let limit = query.limit.unwrap_or(DEFAULT_PROPOSER_BLOCKS_LIMIT).clamp(1, MAX_PROPOSER_BLOCKS_LIMIT);
+
+    let blocks = match db::blocks::get_blocks_by_proposer(&pool, &address, limit, query.before_height).await {
+        Ok(blocks) => blocks,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let total_count = match db::blocks::count_blocks_by_proposer(&pool, &address).await {
+        Ok(count) => count,
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let response = BlockList::new(blocks, total_count);
+    Ok(with_total_count(response, total_count))
+}
+
+/*
+* Searches proposer addresses by hex prefix.
+*
+* Returns the distinct proposers whose address starts with the prefix,
+* ignoring case, together with their block counts. Intended for address
+* autocompletion.
+*
+* @param pool Database connection pool
+* @param query Address prefix and optional limit
+* @return JSON response containing the matching proposers
+*/
+#[utoipa::path(
+    get,
+    path = "/api/validators/search",
+    tag = "Validators",
+    params(ValidatorSearchQuery),
+    responses(
+        (status = 200, description = "Matching proposers retrieved successfully", body = ProposerMatchList),
+        (status = 400, description = "Missing or invalid prefix", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn search_validators(
+    State(pool): State<Pool<Postgres>>,
+    Query(query): Query<ValidatorSearchQuery>,
+) -> Result<(StatusCode, Json<ProposerMatchList>), (StatusCode, Json<ErrorResponse>)> {
+    let prefix = query.prefix.unwrap_or_default();
+    if prefix.is_empty() {
+        return Err(bad_request_error("Prefix must not be empty"));
+    }
+    if !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(bad_request_error(format!("Prefix must be hexadecimal, got {}", prefix)));
+    }
+
+    let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, MAX_SEARCH_LIMIT);
+
+    match StatsQueries::search_proposers(&pool, &prefix, limit).await {
+        Ok(validators) => Ok((StatusCode::OK, Json(ProposerMatchList::new(validators)))),
+        Err(e) => Err(database_error(e)),
+    }
+}
@@ -0,0 +1,115 @@
+/*
+* WebSocket API module.
+*
+* Streams newly indexed blocks to connected clients as they are
+* stored by the block synchronizer, or by any indexer sharing the
+* database when the feed is relayed from database notifications.
+*/
+
+use std::time::Duration;
+use axum::{
+    extract::{ws::{Message, WebSocket, WebSocketUpgrade}, State},
+    response::Response,
+};
+use futures_util::StreamExt;
+use sqlx::{Pool, Postgres};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{error, info, warn};
+use crate::{db, models::block::BlockSummary};
+
+/* Time in seconds to wait before listening again after the listener fails */
+const RELAY_RECONNECT_DELAY_SECS: u64 = 5;
+
+/*
+* Upgrades the connection to a WebSocket streaming new blocks.
+*
+* Each connected client receives a BlockSummary JSON message for every
+* block stored by the indexer. Clients that fall behind the broadcast
+* buffer are disconnected rather than slowing down the indexer.
+*
+* @param ws WebSocket upgrade request
+* @param block_tx Broadcast channel the indexer publishes new blocks on
+* @return Response switching the connection to the WebSocket protocol
+*/
+pub async fn stream_blocks(
+    ws: WebSocketUpgrade,
+    State(block_tx): State<broadcast::Sender<BlockSummary>>,
+) -> Response {
+    let block_rx = block_tx.subscribe();
+    ws.on_upgrade(move |socket| forward_blocks(socket, block_rx))
+}
+
+/*
+* Forwards broadcast blocks to a single client until it disconnects.
+*/
+async fn forward_blocks(mut socket: WebSocket, mut block_rx: broadcast::Receiver<BlockSummary>) {
+    loop {
+        tokio::select! {
+            received = block_rx.recv() => match received {
+                Ok(summary) => {
+                    let payload = match serde_json::to_string(&summary) {
+                        Ok(payload) => payload,
+                        Err(e) => {
+                            error!(height = summary.height, error = %e, "Failed to serialize block");
+                            continue;
+                        }
+                    };
+                    if socket.send(Message::Text(payload)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "Dropping lagging WebSocket client");
+                    let _ = socket.send(Message::Close(None)).await;
+                    break;
+                }
+                Err(RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/*
+* Publishes blocks stored by any indexer on the WebSocket feed.
+*
+* Listens for the database notification sent for every stored block and
+* broadcasts the block's summary, so the feed also covers blocks written
+* by other instances sharing the database. Runs until the process exits,
+* listening again after failures.
+*
+* @param pool Database connection pool
+* @param block_tx Broadcast channel WebSocket clients subscribe to
+*/
+pub async fn relay_stored_blocks(pool: Pool<Postgres>, block_tx: broadcast::Sender<BlockSummary>) {
+    loop {
+        match db::listen_for_blocks(&pool).await {
+            Ok(heights) => {
+                info!("Relaying stored blocks from database notifications");
+                let mut heights = std::pin::pin!(heights);
+                while let Some(height) = heights.next().await {
+                    let height = match height {
+                        Ok(height) => height,
+                        Err(e) => {
+                            warn!(error = %e, "Error receiving block notification");
+                            continue;
+                        }
+                    };
+                    match db::blocks::get_block_by_height(&pool, height).await {
+                        Ok(Some(block)) => {
+                            // No receivers just means no clients are connected
+                            let _ = block_tx.send(block.to_summary());
+                        }
+                        Ok(None) => {}
+                        Err(e) => warn!(height, error = %e, "Error loading notified block"),
+                    }
+                }
+            }
+            Err(e) => error!(error = %e, "Error listening for block notifications"),
+        }
+        tokio::time::sleep(Duration::from_secs(RELAY_RECONNECT_DELAY_SECS)).await;
+    }
+}
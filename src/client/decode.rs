@@ -0,0 +1,681 @@
+/*
+* Transaction decoding for the Penumbra blockchain.
+*
+* Decodes the base64 transaction strings returned by the Tendermint RPC
+* into the protobuf messages defined in proto.rs and classifies each
+* transaction by the actions it performs.
+*/
+
+use std::error::Error;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use prost::Message;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use crate::client::proto::{action::Kind, Amount, AssetId, IbcRelay, Ics20Withdrawal, IdentityKey, MsgRecvPacket, Transaction};
+
+/* Action type stored for IBC token transfers in either direction */
+pub const IBC_TRANSFER_ACTION: &str = "ibc_transfer";
+
+/* Action types that mark a block as containing swaps */
+pub const SWAP_ACTIONS: [&str; 2] = ["swap", "swap_claim"];
+
+/* Action types that mark a block as containing transfers */
+pub const TRANSFER_ACTIONS: [&str; 3] = ["spend", "output", IBC_TRANSFER_ACTION];
+
+/* Action type stored for transactions that could not be decoded */
+pub const UNKNOWN_ACTION: &str = "unknown";
+
+/* Denomination stored for amounts in the staking token, UM */
+pub const UM_DENOM: &str = "penumbra";
+
+/* Base denomination of UM as it appears in transfers */
+const UM_BASE_DENOM: &str = "upenumbra";
+
+/* Action types stored for staking transactions */
+pub const DELEGATE_ACTION: &str = "delegate";
+pub const UNDELEGATE_ACTION: &str = "undelegate";
+
+/* Human-readable prefix of bech32m-encoded asset IDs */
+const ASSET_ID_PREFIX: &str = "passet";
+
+/* Human-readable prefix of bech32m-encoded validator identity keys */
+const IDENTITY_KEY_PREFIX: &str = "penumbravalid";
+
+/* Characters of the bech32 alphabet, indexed by 5-bit value */
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/* Constant XORed into bech32m checksums */
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/* Type URL of the IBC message delivering an inbound packet */
+const MSG_RECV_PACKET_TYPE_URL: &str = "/ibc.core.channel.v1.MsgRecvPacket";
+
+/*
+* Classification of a transaction as stored in the transactions table.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionInfo {
+    /// Normalized action type
+    pub action_type: String,
+
+    /// Amount in base units, when the action carries one
+    pub amount: Option<u128>,
+
+    /// Denomination of the amount, "penumbra" for UM
+    pub denom: Option<String>,
+
+    /// Action-specific details such as IBC channels
+    pub metadata: Option<Value>,
+
+    /// Fee paid by the transaction in base units
+    pub fee_amount: Option<u128>,
+
+    /// Denomination of the fee, "penumbra" for UM or the "passet1..." asset ID otherwise
+    pub fee_denom: Option<String>,
+}
+
+impl ActionInfo {
+    /*
+    * Classification used when a transaction cannot be decoded.
+    */
+    pub fn unknown() -> Self {
+        Self {
+            action_type: UNKNOWN_ACTION.to_string(),
+            amount: None,
+            denom: None,
+            metadata: None,
+            fee_amount: None,
+            fee_denom: None,
+        }
+    }
+}
+
+/*
+* ICS-20 FungibleTokenPacketData carried as JSON in transfer packets.
+*/
+#[derive(Debug, Deserialize)]
+struct FungibleTokenPacketData {
+    denom: String,
+    amount: String,
+    sender: String,
+    receiver: String,
+}
+
+/*
+* Decodes a base64-encoded transaction as returned by the RPC.
+*
+* @param tx_data Base64-encoded transaction bytes
+* @return The decoded transaction or an error if it is malformed
+*/
+pub fn decode_transaction(tx_data: &str) -> Result<Transaction, Box<dyn Error + Send + Sync>> {
+    let bytes = STANDARD.decode(tx_data)?;
+    Ok(Transaction::decode(bytes.as_slice())?)
+}
+
+/*
+* Computes the chain's hash of a base64-encoded transaction.
+*
+* Tendermint identifies a transaction by the SHA-256 of its raw bytes,
+* shown as upper-case hex.
+*
+* @param tx_data Base64-encoded transaction bytes
+* @return The hex-encoded transaction hash or an error if the data is not base64
+*/
+pub fn transaction_hash(tx_data: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let bytes = STANDARD.decode(tx_data)?;
+    Ok(hex::encode_upper(Sha256::digest(&bytes)))
+}
+
+/*
+* Returns the snake_case name of an action kind.
+*/
+pub fn action_name(kind: &Kind) -> &'static str {
+    match kind {
+        Kind::Spend(_) => "spend",
+        Kind::Output(_) => "output",
+        Kind::Swap(_) => "swap",
+        Kind::SwapClaim(_) => "swap_claim",
+        Kind::ValidatorDefinition(_) => "validator_definition",
+        Kind::IbcRelay(_) => "ibc_relay",
+        Kind::ProposalSubmit(_) => "proposal_submit",
+        Kind::ProposalWithdraw(_) => "proposal_withdraw",
+        Kind::ValidatorVote(_) => "validator_vote",
+        Kind::DelegatorVote(_) => "delegator_vote",
+        Kind::ProposalDepositClaim(_) => "proposal_deposit_claim",
+        Kind::PositionOpen(_) => "position_open",
+        Kind::PositionClose(_) => "position_close",
+        Kind::PositionWithdraw(_) => "position_withdraw",
+        Kind::PositionRewardClaim(_) => "position_reward_claim",
+        Kind::Delegate(_) => DELEGATE_ACTION,
+        Kind::Undelegate(_) => UNDELEGATE_ACTION,
+        Kind::UndelegateClaim(_) => "undelegate_claim",
+        Kind::CommunityPoolSpend(_) => "community_pool_spend",
+        Kind::CommunityPoolOutput(_) => "community_pool_output",
+        Kind::CommunityPoolDeposit(_) => "community_pool_deposit",
+        Kind::DutchAuctionSchedule(_) => "dutch_auction_schedule",
+        Kind::DutchAuctionEnd(_) => "dutch_auction_end",
+        Kind::DutchAuctionWithdraw(_) => "dutch_auction_withdraw",
+        Kind::LiquidityTournamentVote(_) => "liquidity_tournament_vote",
+        Kind::Ics20Withdrawal(_) => "ics20_withdrawal",
+    }
+}
+
+/*
+* Classifies a decoded transaction.
+*
+* IBC transfers take precedence and are stored as "ibc_transfer" with
+* their amount and channel metadata. Otherwise the first action that is
+* not a plain spend or output names the transaction, falling back to the
+* first action. Delegations and undelegations carry their validator and
+* UM amount. The transaction fee is recorded whatever the action.
+*
+* @param tx Decoded transaction
+* @return Action type, amount, fee and metadata to store
+*/
+pub fn classify_transaction(tx: &Transaction) -> ActionInfo {
+    let (fee_amount, fee_denom) = transaction_fee(tx).unzip();
+    ActionInfo {
+        fee_amount,
+        fee_denom,
+        ..classify_actions(tx)
+    }
+}
+
+/*
+* Extracts the fee of a decoded transaction.
+*
+* A fee without an asset ID is paid in the staking token, UM. Other
+* assets are identified by their bech32m asset ID, as the denomination
+* is not part of the transaction.
+*
+* @param tx Decoded transaction
+* @return Fee amount in base units and its denomination, or None without a fee
+*/
+fn transaction_fee(tx: &Transaction) -> Option<(u128, String)> {
+    let fee = tx.body.as_ref()?.transaction_parameters.as_ref()?.fee.as_ref()?;
+    let amount = fee.amount.as_ref().map_or(0, Amount::value);
+    let denom = match &fee.asset_id {
+        Some(asset_id) if !asset_id.inner.is_empty() => encode_asset_id(asset_id),
+        _ => UM_DENOM.to_string(),
+    };
+    Some((amount, denom))
+}
+
+/*
+* Classifies a decoded transaction by its actions.
+*/
+fn classify_actions(tx: &Transaction) -> ActionInfo {
+    let actions: Vec<&Kind> = tx.body
+        .iter()
+        .flat_map(|body| body.actions.iter())
+        .filter_map(|action| action.kind.as_ref())
+        .collect();
+
+    for kind in &actions {
+        let transfer = match kind {
+            Kind::IbcRelay(relay) => inbound_ibc_transfer(relay),
+            Kind::Ics20Withdrawal(withdrawal) => Some(outbound_ibc_transfer(withdrawal)),
+            _ => None,
+        };
+        if let Some(info) = transfer {
+            return info;
+        }
+    }
+
+    let primary = actions
+        .iter()
+        .find(|kind| !matches!(kind, Kind::Spend(_) | Kind::Output(_)))
+        .or(actions.first());
+
+    match primary {
+        Some(Kind::Delegate(delegate)) => {
+            return staking_action(DELEGATE_ACTION, &delegate.validator_identity, &delegate.unbonded_amount, &delegate.delegation_amount);
+        }
+        Some(Kind::Undelegate(undelegate)) => {
+            return staking_action(UNDELEGATE_ACTION, &undelegate.validator_identity, &undelegate.unbonded_amount, &undelegate.delegation_amount);
+        }
+        _ => {}
+    }
+
+    ActionInfo {
+        action_type: primary.map_or(UNKNOWN_ACTION, |kind| action_name(kind)).to_string(),
+        amount: None,
+        denom: None,
+        metadata: None,
+        fee_amount: None,
+        fee_denom: None,
+    }
+}
+
+/*
+* Describes each action of a decoded transaction as JSON.
+*
+* Every action carries its "type". IBC relays add the relayed message
+* type and, for token transfers, the transfer details; ICS-20
+* withdrawals add their amount, denom and destination. Other actions
+* are not decoded further.
+*
+* @param tx Decoded transaction
+* @return One JSON object per action, in transaction order
+*/
+pub fn describe_actions(tx: &Transaction) -> Vec<Value> {
+    tx.body
+        .iter()
+        .flat_map(|body| body.actions.iter())
+        .filter_map(|action| action.kind.as_ref())
+        .map(|kind| {
+            let mut description = json!({ "type": action_name(kind) });
+            match kind {
+                Kind::IbcRelay(relay) => {
+                    description["type_url"] = json!(relay.raw_action.as_ref().map(|any| any.type_url.clone()));
+                    if let Some(transfer) = inbound_ibc_transfer(relay) {
+                        description["amount"] = json!(transfer.amount.map(|amount| amount.to_string()));
+                        description["transfer"] = transfer.metadata.unwrap_or(Value::Null);
+                    }
+                }
+                Kind::Delegate(delegate) => {
+                    description["validator_identity"] = json!(delegate.validator_identity.as_ref().map(encode_identity_key));
+                    description["unbonded_amount"] = json!(delegate.unbonded_amount.as_ref().map(|amount| amount.value().to_string()));
+                    description["delegation_amount"] = json!(delegate.delegation_amount.as_ref().map(|amount| amount.value().to_string()));
+                }
+                Kind::Undelegate(undelegate) => {
+                    description["validator_identity"] = json!(undelegate.validator_identity.as_ref().map(encode_identity_key));
+                    description["unbonded_amount"] = json!(undelegate.unbonded_amount.as_ref().map(|amount| amount.value().to_string()));
+                    description["delegation_amount"] = json!(undelegate.delegation_amount.as_ref().map(|amount| amount.value().to_string()));
+                }
+                Kind::Ics20Withdrawal(withdrawal) => {
+                    description["amount"] = json!(withdrawal.amount.as_ref().map(|amount| amount.value().to_string()));
+                    description["denom"] = json!(withdrawal.denom.as_ref().map(|denom| denom.denom.clone()));
+                    description["destination_chain_address"] = json!(withdrawal.destination_chain_address);
+                    description["source_channel"] = json!(withdrawal.source_channel);
+                }
+                _ => {}
+            }
+            description
+        })
+        .collect()
+}
+
+/*
+* Extracts an inbound ICS-20 transfer from a relayed MsgRecvPacket.
+*
+* Returns None for relay actions that are not token transfers, such as
+* client updates or acknowledgements.
+*/
+fn inbound_ibc_transfer(relay: &IbcRelay) -> Option<ActionInfo> {
+    let raw_action = relay.raw_action.as_ref()?;
+    if raw_action.type_url != MSG_RECV_PACKET_TYPE_URL {
+        return None;
+    }
+
+    let packet = MsgRecvPacket::decode(raw_action.value.as_slice()).ok()?.packet?;
+    let data: FungibleTokenPacketData = serde_json::from_slice(&packet.data).ok()?;
+
+    // Tokens returning to Penumbra carry the sending side's port and channel as a prefix
+    let return_prefix = format!("{}/{}/", packet.source_port, packet.source_channel);
+    let local_denom = data.denom.strip_prefix(&return_prefix).unwrap_or(&data.denom);
+
+    Some(ActionInfo {
+        action_type: IBC_TRANSFER_ACTION.to_string(),
+        amount: data.amount.parse::<u128>().ok(),
+        denom: Some(normalize_denom(local_denom)),
+        metadata: Some(json!({
+            "direction": "inbound",
+            "sequence": packet.sequence,
+            "source_port": packet.source_port,
+            "source_channel": packet.source_channel,
+            "destination_port": packet.destination_port,
+            "destination_channel": packet.destination_channel,
+            "denom": data.denom,
+            "sender": data.sender,
+            "receiver": data.receiver,
+        })),
+        fee_amount: None,
+        fee_denom: None,
+    })
+}
+
+/*
+* Describes an outbound ICS-20 transfer initiated from Penumbra.
+*/
+fn outbound_ibc_transfer(withdrawal: &Ics20Withdrawal) -> ActionInfo {
+    ActionInfo {
+        action_type: IBC_TRANSFER_ACTION.to_string(),
+        amount: withdrawal.amount.as_ref().map(Amount::value),
+        denom: withdrawal.denom.as_ref().map(|denom| normalize_denom(&denom.denom)),
+        metadata: Some(json!({
+            "direction": "outbound",
+            "source_channel": withdrawal.source_channel,
+            "destination_chain_address": withdrawal.destination_chain_address,
+            "denom": withdrawal.denom.as_ref().map(|denom| denom.denom.clone()),
+        })),
+        fee_amount: None,
+        fee_denom: None,
+    }
+}
+
+/*
+* Describes a delegation or undelegation.
+*
+* The stored amount is the unbonded UM moved into or out of the
+* validator's pool; the delegation token amount is kept in the metadata.
+*/
+fn staking_action(
+    action_type: &str,
+    validator_identity: &Option<IdentityKey>,
+    unbonded_amount: &Option<Amount>,
+    delegation_amount: &Option<Amount>,
+) -> ActionInfo {
+    ActionInfo {
+        action_type: action_type.to_string(),
+        amount: unbonded_amount.as_ref().map(Amount::value),
+        denom: Some(UM_DENOM.to_string()),
+        metadata: Some(json!({
+            "validator_identity": validator_identity.as_ref().map(encode_identity_key),
+            "unbonded_amount": unbonded_amount.as_ref().map(|amount| amount.value().to_string()),
+            "delegation_amount": delegation_amount.as_ref().map(|amount| amount.value().to_string()),
+        })),
+        fee_amount: None,
+        fee_denom: None,
+    }
+}
+
+/*
+* Encodes a validator identity key in its bech32m form, "penumbravalid1...".
+*/
+pub fn encode_identity_key(identity: &IdentityKey) -> String {
+    bech32m_encode(IDENTITY_KEY_PREFIX, &identity.ik)
+}
+
+/*
+* Encodes an asset ID in its bech32m form, "passet1...".
+*/
+fn encode_asset_id(asset_id: &AssetId) -> String {
+    bech32m_encode(ASSET_ID_PREFIX, &asset_id.inner)
+}
+
+/*
+* Encodes bytes as bech32m (BIP-350) without a length limit.
+*/
+fn bech32m_encode(hrp: &str, data: &[u8]) -> String {
+    // Regroup the 8-bit bytes into 5-bit values, zero-padding the last one
+    let mut values = Vec::with_capacity(data.len() * 8 / 5 + 1);
+    let mut accumulator: u32 = 0;
+    let mut bits = 0;
+    for byte in data {
+        accumulator = (accumulator << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            values.push(((accumulator >> bits) & 31) as u8);
+        }
+    }
+    if bits > 0 {
+        values.push(((accumulator << (5 - bits)) & 31) as u8);
+    }
+
+    let mut checksum_input: Vec<u8> = hrp.bytes().map(|c| c >> 5).collect();
+    checksum_input.push(0);
+    checksum_input.extend(hrp.bytes().map(|c| c & 31));
+    checksum_input.extend(&values);
+    checksum_input.extend([0; 6]);
+    let polymod = bech32_polymod(&checksum_input) ^ BECH32M_CONST;
+    values.extend((0..6).map(|i| ((polymod >> (5 * (5 - i))) & 31) as u8));
+
+    let mut encoded = format!("{}1", hrp);
+    encoded.extend(values.iter().map(|value| BECH32_CHARSET[*value as usize] as char));
+    encoded
+}
+
+/*
+* Computes the bech32 checksum polynomial over 5-bit values.
+*/
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    let mut checksum: u32 = 1;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x01ff_ffff) << 5) ^ *value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/*
+* Maps a transfer denomination to the one stored with amounts.
+*
+* UM is stored as "penumbra"; any other asset keeps its denomination.
+*/
+fn normalize_denom(denom: &str) -> String {
+    if denom == UM_BASE_DENOM {
+        UM_DENOM.to_string()
+    } else {
+        denom.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::proto::{Action, Any, Delegate, Denom, Fee, Packet, TransactionBody, TransactionParameters, Undelegate};
+
+    /* Encodes a transaction the way the RPC returns it */
+    fn fixture(actions: Vec<Kind>) -> String {
+        let tx = Transaction {
+            body: Some(TransactionBody {
+                actions: actions.into_iter().map(|kind| Action { kind: Some(kind) }).collect(),
+                transaction_parameters: None,
+            }),
+        };
+        STANDARD.encode(tx.encode_to_vec())
+    }
+
+    fn amount(value: u128) -> Amount {
+        Amount { lo: value as u64, hi: (value >> 64) as u64 }
+    }
+
+    fn classify(tx_data: &str) -> ActionInfo {
+        classify_transaction(&decode_transaction(tx_data).unwrap())
+    }
+
+    /* A delegation as it appears in a block's `txs`, and the hash of its raw bytes */
+    const DELEGATE_TX: &str = "CjUKM8ICMAoiCiAAAQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHxoECMCEPSIECNj8PA==";
+    const DELEGATE_TX_HASH: &str = "717139113E6E7EA8368652926A9A331E07172AECB7C1BF9E8097D222DCB27031";
+
+    #[test]
+    fn transaction_hash_covers_the_decoded_transaction_bytes() {
+        assert_eq!(classify(DELEGATE_TX).action_type, "delegate");
+
+        // Computed outside this crate, e.g. with Python's hashlib over the base64-decoded bytes
+        assert_eq!(transaction_hash(DELEGATE_TX).unwrap(), DELEGATE_TX_HASH);
+    }
+
+    #[test]
+    fn transaction_hash_rejects_data_that_is_not_base64() {
+        assert!(transaction_hash("not base64!").is_err());
+    }
+
+    #[test]
+    fn decodes_an_inbound_ibc_transfer() {
+        let packet = Packet {
+            sequence: 42,
+            source_port: "transfer".to_string(),
+            source_channel: "channel-141".to_string(),
+            destination_port: "transfer".to_string(),
+            destination_channel: "channel-4".to_string(),
+            data: br#"{"denom":"uosmo","amount":"2500000","sender":"osmo1sender","receiver":"penumbra1receiver"}"#.to_vec(),
+        };
+        let relay = IbcRelay {
+            raw_action: Some(Any {
+                type_url: MSG_RECV_PACKET_TYPE_URL.to_string(),
+                value: MsgRecvPacket { packet: Some(packet) }.encode_to_vec(),
+            }),
+        };
+
+        let info = classify(&fixture(vec![Kind::Spend(Vec::new()), Kind::IbcRelay(relay)]));
+        assert_eq!(info.action_type, IBC_TRANSFER_ACTION);
+        assert_eq!(info.amount, Some(2_500_000));
+        assert_eq!(info.denom.as_deref(), Some("uosmo"));
+
+        let metadata = info.metadata.unwrap();
+        assert_eq!(metadata["direction"], "inbound");
+        assert_eq!(metadata["source_channel"], "channel-141");
+        assert_eq!(metadata["destination_channel"], "channel-4");
+        assert_eq!(metadata["sender"], "osmo1sender");
+    }
+
+    #[test]
+    fn decodes_returning_um_as_penumbra() {
+        let packet = Packet {
+            sequence: 7,
+            source_port: "transfer".to_string(),
+            source_channel: "channel-0".to_string(),
+            destination_port: "transfer".to_string(),
+            destination_channel: "channel-2".to_string(),
+            data: br#"{"denom":"transfer/channel-0/upenumbra","amount":"10","sender":"a","receiver":"b"}"#.to_vec(),
+        };
+        let relay = IbcRelay {
+            raw_action: Some(Any {
+                type_url: MSG_RECV_PACKET_TYPE_URL.to_string(),
+                value: MsgRecvPacket { packet: Some(packet) }.encode_to_vec(),
+            }),
+        };
+
+        let info = classify(&fixture(vec![Kind::IbcRelay(relay)]));
+        assert_eq!(info.denom.as_deref(), Some(UM_DENOM));
+    }
+
+    #[test]
+    fn decodes_an_outbound_ibc_transfer() {
+        let withdrawal = Ics20Withdrawal {
+            amount: Some(amount(1_000_000)),
+            denom: Some(Denom { denom: "upenumbra".to_string() }),
+            destination_chain_address: "osmo1destination".to_string(),
+            source_channel: "channel-4".to_string(),
+        };
+
+        let info = classify(&fixture(vec![Kind::Ics20Withdrawal(withdrawal)]));
+        assert_eq!(info.action_type, IBC_TRANSFER_ACTION);
+        assert_eq!(info.amount, Some(1_000_000));
+        assert_eq!(info.denom.as_deref(), Some(UM_DENOM));
+
+        let metadata = info.metadata.unwrap();
+        assert_eq!(metadata["direction"], "outbound");
+        assert_eq!(metadata["source_channel"], "channel-4");
+        assert_eq!(metadata["destination_chain_address"], "osmo1destination");
+    }
+
+    #[test]
+    fn relays_that_are_not_transfers_keep_their_action_name() {
+        let relay = IbcRelay {
+            raw_action: Some(Any {
+                type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+                value: Vec::new(),
+            }),
+        };
+
+        let info = classify(&fixture(vec![Kind::IbcRelay(relay)]));
+        assert_eq!(info.action_type, "ibc_relay");
+        assert_eq!(info.amount, None);
+    }
+
+    fn identity_key() -> IdentityKey {
+        IdentityKey { ik: (0..32).collect() }
+    }
+
+    #[test]
+    fn decodes_a_delegation() {
+        let delegate = Delegate {
+            validator_identity: Some(identity_key()),
+            unbonded_amount: Some(amount(5_000_000)),
+            delegation_amount: Some(amount(4_900_000)),
+        };
+
+        let info = classify(&fixture(vec![Kind::Spend(Vec::new()), Kind::Delegate(delegate), Kind::Output(Vec::new())]));
+        assert_eq!(info.action_type, DELEGATE_ACTION);
+        assert_eq!(info.amount, Some(5_000_000));
+        assert_eq!(info.denom.as_deref(), Some(UM_DENOM));
+
+        let metadata = info.metadata.unwrap();
+        assert_eq!(metadata["validator_identity"], encode_identity_key(&identity_key()));
+        assert_eq!(metadata["unbonded_amount"], "5000000");
+        assert_eq!(metadata["delegation_amount"], "4900000");
+    }
+
+    #[test]
+    fn decodes_an_undelegation() {
+        let undelegate = Undelegate {
+            validator_identity: Some(identity_key()),
+            unbonded_amount: Some(amount(u64::MAX as u128 + 1)),
+            delegation_amount: Some(amount(3)),
+        };
+
+        let info = classify(&fixture(vec![Kind::Undelegate(undelegate), Kind::Output(Vec::new())]));
+        assert_eq!(info.action_type, UNDELEGATE_ACTION);
+        assert_eq!(info.amount, Some(u64::MAX as u128 + 1));
+
+        let metadata = info.metadata.unwrap();
+        assert_eq!(metadata["unbonded_amount"], "18446744073709551616");
+        assert_eq!(metadata["delegation_amount"], "3");
+    }
+
+    #[test]
+    fn encodes_validator_identities_as_bech32m() {
+        // Valid bech32m test vector from BIP-350
+        assert_eq!(bech32m_encode("a", &[]), "a1lqfn3a");
+
+        let encoded = encode_identity_key(&identity_key());
+        assert!(encoded.starts_with("penumbravalid1"));
+        assert!(encoded[14..].bytes().all(|c| BECH32_CHARSET.contains(&c)));
+        // 32 bytes regroup into 52 five-bit values, followed by a 6-character checksum
+        assert_eq!(encoded.len(), "penumbravalid1".len() + 52 + 6);
+    }
+
+    /* Encodes a spend and output paying the given fee */
+    fn fixture_with_fee(fee: Fee) -> String {
+        let tx = Transaction {
+            body: Some(TransactionBody {
+                actions: vec![
+                    Action { kind: Some(Kind::Spend(Vec::new())) },
+                    Action { kind: Some(Kind::Output(Vec::new())) },
+                ],
+                transaction_parameters: Some(TransactionParameters { fee: Some(fee) }),
+            }),
+        };
+        STANDARD.encode(tx.encode_to_vec())
+    }
+
+    #[test]
+    fn decodes_a_fee_paid_in_um() {
+        let info = classify(&fixture_with_fee(Fee { amount: Some(amount(1_234)), asset_id: None }));
+        assert_eq!(info.action_type, "spend");
+        assert_eq!(info.fee_amount, Some(1_234));
+        assert_eq!(info.fee_denom.as_deref(), Some(UM_DENOM));
+    }
+
+    #[test]
+    fn decodes_a_fee_paid_in_another_asset() {
+        let asset_id = AssetId { inner: vec![7; 32] };
+        let fee = Fee { amount: Some(amount(50)), asset_id: Some(asset_id.clone()) };
+
+        let info = classify(&fixture_with_fee(fee));
+        assert_eq!(info.fee_amount, Some(50));
+        assert_eq!(info.fee_denom, Some(encode_asset_id(&asset_id)));
+        assert!(info.fee_denom.unwrap().starts_with("passet1"));
+    }
+
+    #[test]
+    fn a_fee_without_an_amount_is_zero_and_no_fee_is_none() {
+        let info = classify(&fixture_with_fee(Fee { amount: None, asset_id: None }));
+        assert_eq!(info.fee_amount, Some(0));
+
+        let info = classify(&fixture(vec![Kind::Spend(Vec::new())]));
+        assert_eq!(info.fee_amount, None);
+        assert_eq!(info.fee_denom, None);
+    }
+}
@@ -0,0 +1,93 @@
+/*
+* Error type for the Penumbra client.
+*
+* Separates failures talking to the node from requests the node rejected,
+* malformed node responses and database failures, so callers can retry
+* transient errors and give up on the rest.
+*/
+
+use thiserror::Error;
+
+/*
+* Errors returned by the RPC client and block synchronizer.
+*/
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// The node could not be reached or answered with an error status
+    #[error("RPC error: {0}")]
+    Rpc(String),
+
+    /// The node rejected the request with a JSON-RPC error object
+    #[error("Node returned error {code} ({message}): {}", .data.as_deref().unwrap_or("no details"))]
+    Node {
+        code: i64,
+        message: String,
+        data: Option<String>,
+    },
+
+    /// The node's response could not be parsed
+    #[error("Invalid node response: {0}")]
+    Parse(String),
+
+    /// Reading or writing indexed data failed
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    /// The node returned a different block than the one requested
+    #[error("Node returned block at height '{returned}' when height {requested} was requested")]
+    HeightMismatch {
+        requested: u64,
+        returned: String,
+    },
+
+    /// A backfill finished with blocks that could not be indexed
+    #[error("{failed} blocks between heights {start} and {end} could not be indexed")]
+    BackfillIncomplete {
+        start: u64,
+        end: u64,
+        failed: i64,
+    },
+
+    /// A verification run found stored blocks that still differ from the chain
+    #[error("{unresolved} stored blocks do not match the chain")]
+    VerifyMismatch {
+        unresolved: u64,
+    },
+
+    /// Parallel sync workers stopped before finishing their ranges
+    #[error("{failed} of {workers} parallel sync workers failed, first error: {first_error}")]
+    WorkersFailed {
+        failed: u64,
+        workers: u64,
+        first_error: String,
+    },
+}
+
+impl ClientError {
+    /*
+    * Whether the failure is transient and the operation worth retrying.
+    */
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Rpc(_))
+    }
+}
+
+/*
+* Classifies HTTP client errors, treating body decoding failures as
+* parse errors and everything else as RPC errors.
+*/
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_decode() {
+            Self::Parse(err.to_string())
+        } else {
+            Self::Rpc(err.to_string())
+        }
+    }
+}
+
+impl From<serde_json::Error> for ClientError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Parse(err.to_string())
+    }
+}
@@ -0,0 +1,77 @@
+/*
+* Most recent indexer error tracking.
+*
+* Keeps the last failure of the sync loop in memory so operators can see
+* why the indexer is falling behind without scraping logs. The error is
+* cleared as soon as a block is stored successfully again.
+*/
+
+use std::fmt::Display;
+use std::sync::{Arc, RwLock};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/*
+* A failure recorded by the indexer.
+*/
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexerError {
+    /// Error message
+    pub message: String,
+
+    /// Height being processed when the error occurred, if any
+    pub height: Option<u64>,
+
+    /// When the error occurred
+    pub occurred_at: DateTime<Utc>,
+}
+
+/*
+* Shared slot holding the most recent indexer error.
+*
+* Cloning shares the same slot, so the indexer and the API can hold
+* their own handles.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct LastErrorTracker {
+    last: Arc<RwLock<Option<IndexerError>>>,
+}
+
+impl LastErrorTracker {
+    /*
+    * Creates a tracker with no recorded error.
+    */
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /*
+    * Replaces the recorded error.
+    *
+    * @param height Height being processed, None for errors outside block processing
+    * @param error The error that occurred
+    */
+    pub fn record(&self, height: Option<u64>, error: &dyn Display) {
+        let mut last = self.last.write().unwrap_or_else(|e| e.into_inner());
+        *last = Some(IndexerError {
+            message: error.to_string(),
+            height,
+            occurred_at: Utc::now(),
+        });
+    }
+
+    /*
+    * Clears the recorded error.
+    */
+    pub fn clear(&self) {
+        let mut last = self.last.write().unwrap_or_else(|e| e.into_inner());
+        *last = None;
+    }
+
+    /*
+    * Returns the recorded error, None if the last block was stored successfully.
+    */
+    pub fn get(&self) -> Option<IndexerError> {
+        self.last.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
@@ -0,0 +1,130 @@
+/*
+* Minimal HTTP server standing in for a Penumbra node in tests.
+*
+* Answers every GET request with the status and JSON body returned by a
+* handler for the request path, keeping connections alive between
+* requests. Counts requests and accepted connections so tests can check
+* caching and connection reuse.
+*/
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/* Handler mapping a request path and query to a status code and JSON body */
+type Handler = dyn Fn(&str) -> (u16, String) + Send + Sync;
+
+/*
+* A running mock node, shut down when the test's runtime ends.
+*/
+pub struct MockNode {
+    /// Base URL of the node, e.g. "http://127.0.0.1:34567"
+    pub url: String,
+    requests: Arc<AtomicUsize>,
+    connections: Arc<AtomicUsize>,
+}
+
+impl MockNode {
+    /*
+    * Starts a mock node on a free local port.
+    *
+    * @param handler Returns the status code and JSON body for a request path
+    * @return The running node
+    */
+    pub async fn start(handler: impl Fn(&str) -> (u16, String) + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handler: Arc<Handler> = Arc::new(handler);
+        let requests = Arc::new(AtomicUsize::new(0));
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        tokio::spawn({
+            let requests = requests.clone();
+            let connections = connections.clone();
+            async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    connections.fetch_add(1, Ordering::SeqCst);
+                    tokio::spawn(serve_connection(stream, handler.clone(), requests.clone()));
+                }
+            }
+        });
+
+        Self { url, requests, connections }
+    }
+
+    /*
+    * Number of requests answered so far.
+    */
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+
+    /*
+    * Number of TCP connections accepted so far.
+    */
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+/*
+* Answers requests on a connection until the client closes it.
+*/
+async fn serve_connection(stream: TcpStream, handler: Arc<Handler>, requests: Arc<AtomicUsize>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+            return;
+        }
+
+        // Requests are bodiless GETs, so the head ends at the first empty line
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header).await.unwrap_or(0) == 0 {
+                return;
+            }
+            if header == "\r\n" {
+                break;
+            }
+        }
+
+        let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+        let (status, body) = handler(path);
+        requests.fetch_add(1, Ordering::SeqCst);
+
+        let response = format!(
+            "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        if writer.write_all(response.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+/*
+* Returns a node status response reporting the given latest height.
+*/
+pub fn status_body(height: u64) -> String {
+    format!(
+        r#"{{"result":{{"sync_info":{{"latest_block_height":"{}","latest_block_time":"2024-01-01T00:00:00Z","catching_up":false}}}}}}"#,
+        height
+    )
+}
+
+/*
+* Returns a block response for the given height and hash with no transactions.
+*/
+pub fn block_body(height: u64, hash: &str) -> String {
+    format!(
+        r#"{{"result":{{"block_id":{{"hash":"{}"}},"block":{{"header":{{"height":"{}","time":"2024-01-01T00:00:00Z","last_block_id":null,"proposer_address":"PROPOSER"}},"data":{{"txs":[]}}}}}}}}"#,
+        hash,
+        height
+    )
+}
@@ -2,11 +2,23 @@
  * Penumbra blockchain client module.
  *
  * Provides interfaces for interacting with the Penumbra blockchain
- * through RPC endpoints and manages block synchronization.
+ * through RPC endpoints, manages block synchronization and tracks
+ * indexing throughput, the most recent indexer error and manual resync jobs.
  */
 
+pub mod decode;
+pub mod error;
+pub mod last_error;
+#[cfg(test)]
+pub(crate) mod mock_node;
 pub mod models;
+pub mod proto;
+pub mod resync;
 pub mod rpc;
 pub mod sync;
+pub mod throughput;
+pub mod validation;
+pub mod verify;
 
+pub use error::ClientError;
 pub use sync::PenumbraClient;
@@ -7,6 +7,7 @@
 
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::client::error::ClientError;
 
 /*
 * Response wrapper for block-related RPC calls.
@@ -27,30 +28,81 @@ pub struct BlockResult {
 
 /*
 * Represents a block in the Penumbra blockchain.
+*
+* Evidence and the last commit are not indexed, but are kept verbatim when
+* the node sends them so the stored payload is the complete block.
 */
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Block {
     pub header: BlockHeader,
     pub data: BlockData,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commit: Option<serde_json::Value>,
 }
 
 /*
 * Header information for a block.
+*
+* The fields other than height, time, last block ID and proposer are only
+* needed to recompute the header hash and are kept when the node sends them.
 */
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct BlockHeader {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<ConsensusVersion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
     pub height: String,
     pub time: DateTime<Utc>,
     pub last_block_id: Option<BlockId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commit_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validators_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_validators_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consensus_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_results_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence_hash: Option<String>,
     pub proposer_address: String,
 }
 
+/*
+* Block and app protocol versions of a block header.
+*/
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ConsensusVersion {
+    pub block: String,
+    #[serde(default)]
+    pub app: String,
+}
+
 /*
 * Unique identifier for a block.
 */
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct BlockId {
     pub hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parts: Option<PartSetHeader>,
+}
+
+/*
+* Header of the part set a block was gossiped in.
+*/
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PartSetHeader {
+    pub total: u32,
+    pub hash: String,
 }
 
 /*
@@ -61,18 +113,86 @@ pub struct BlockData {
     pub txs: Option<Vec<String>>,
 }
 
+impl BlockData {
+    /*
+    * Returns the block's transactions in block order.
+    *
+    * Nodes send `null` or an empty array for a block without transactions;
+    * both yield none. Empty entries carry no transaction bytes and are
+    * skipped rather than stored as empty transactions.
+    */
+    pub fn transactions(&self) -> Vec<&str> {
+        self.txs
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|tx_data| !tx_data.trim().is_empty())
+            .collect()
+    }
+
+    /*
+    * Returns the number of empty transaction entries that are skipped.
+    */
+    pub fn empty_transaction_count(&self) -> usize {
+        self.txs.as_ref().map_or(0, Vec::len) - self.transactions().len()
+    }
+}
+
+/*
+* JSON-RPC error response, sent by the node in place of a result when it
+* rejects a request, e.g. for a pruned or future height.
+*/
+#[derive(Debug, Deserialize, Clone)]
+pub struct RpcErrorResponse {
+    pub error: RpcError,
+}
+
+/*
+* Error object of a JSON-RPC error response.
+*/
+#[derive(Debug, Deserialize, Clone)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default)]
+    pub data: Option<String>,
+}
+
+impl From<RpcError> for ClientError {
+    fn from(error: RpcError) -> Self {
+        ClientError::Node {
+            code: error.code,
+            message: error.message,
+            data: error.data,
+        }
+    }
+}
+
 /*
 * Response structure for node status queries.
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct StatusResponse {
     pub result: NodeStatus,
 }
 
+impl StatusResponse {
+    /*
+    * Parses the latest block height reported by the node.
+    *
+    * @return The chain height or an error naming the unparsable value
+    */
+    pub fn chain_height(&self) -> Result<u64, ClientError> {
+        let raw = &self.result.sync_info.latest_block_height;
+        raw.parse::<u64>()
+            .map_err(|e| ClientError::Parse(format!("invalid latest_block_height '{}': {}", raw, e)))
+    }
+}
+
 /*
 * Contains node-specific status information.
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct NodeStatus {
     pub sync_info: SyncInfo,
 }
@@ -80,10 +200,84 @@ pub struct NodeStatus {
 /*
 * Information about the node's synchronization status.
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 #[allow(dead_code)]
 pub struct SyncInfo {
     pub latest_block_height: String,
     pub latest_block_time: DateTime<Utc>,
     pub catching_up: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_with_height(height: &str) -> StatusResponse {
+        serde_json::from_value(serde_json::json!({
+            "result": {
+                "sync_info": {
+                    "latest_block_height": height,
+                    "latest_block_time": "2024-01-01T00:00:00Z",
+                    "catching_up": false
+                }
+            }
+        })).unwrap()
+    }
+
+    #[test]
+    fn chain_height_parses_a_valid_height() {
+        assert_eq!(status_with_height("12345").chain_height().unwrap(), 12345);
+    }
+
+    #[test]
+    fn chain_height_rejects_unparsable_heights() {
+        for raw in ["", "abc", "-1", "1.5"] {
+            match status_with_height(raw).chain_height() {
+                Err(ClientError::Parse(message)) => assert!(message.contains(&format!("'{}'", raw))),
+                other => panic!("expected a parse error for '{}', got {:?}", raw, other),
+            }
+        }
+    }
+
+    fn block_data(txs: serde_json::Value) -> BlockData {
+        serde_json::from_value(serde_json::json!({ "txs": txs })).unwrap()
+    }
+
+    #[test]
+    fn null_and_empty_txs_both_mean_no_transactions() {
+        for txs in [serde_json::Value::Null, serde_json::json!([])] {
+            let data = block_data(txs);
+            assert!(data.transactions().is_empty());
+            assert_eq!(data.empty_transaction_count(), 0);
+        }
+    }
+
+    #[test]
+    fn empty_transaction_entries_are_skipped_and_counted() {
+        let data = block_data(serde_json::json!(["", "  ", "abc"]));
+        assert_eq!(data.transactions(), vec!["abc"]);
+        assert_eq!(data.empty_transaction_count(), 2);
+    }
+
+    #[test]
+    fn keeps_evidence_and_last_commit_in_the_stored_payload() {
+        let mut body: serde_json::Value = serde_json::from_str(&crate::client::mock_node::block_body(5, "HASH")).unwrap();
+        let evidence = serde_json::json!({ "evidence": [] });
+        let last_commit = serde_json::json!({ "height": "4", "round": 0, "signatures": [{ "block_id_flag": 2 }] });
+        body["result"]["block"]["evidence"] = evidence.clone();
+        body["result"]["block"]["last_commit"] = last_commit.clone();
+
+        let block: BlockResponse = serde_json::from_value(body).unwrap();
+        let stored = serde_json::to_value(&block.result).unwrap();
+        assert_eq!(stored["block"]["evidence"], evidence);
+        assert_eq!(stored["block"]["last_commit"], last_commit);
+    }
+
+    #[test]
+    fn omits_evidence_and_last_commit_the_node_did_not_send() {
+        let block: BlockResponse = serde_json::from_str(&crate::client::mock_node::block_body(5, "HASH")).unwrap();
+        let stored = serde_json::to_value(&block.result).unwrap();
+        assert!(stored["block"].get("evidence").is_none());
+        assert!(stored["block"].get("last_commit").is_none());
+    }
+}
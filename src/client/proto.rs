@@ -0,0 +1,228 @@
+/*
+* Minimal Penumbra protobuf message definitions.
+*
+* Hand-written prost mirrors of the subset of the definitions under proto/
+* that the indexer needs to classify transactions. Only the fields we read
+* are declared; all other fields are skipped when decoding. Actions whose
+* contents we don't inspect are kept as raw bytes so their kind is still
+* known.
+*/
+
+use prost::Message;
+
+/* penumbra.core.transaction.v1.Transaction */
+#[derive(Clone, PartialEq, Message)]
+pub struct Transaction {
+    #[prost(message, optional, tag = "1")]
+    pub body: Option<TransactionBody>,
+}
+
+/* penumbra.core.transaction.v1.TransactionBody */
+#[derive(Clone, PartialEq, Message)]
+pub struct TransactionBody {
+    #[prost(message, repeated, tag = "1")]
+    pub actions: Vec<Action>,
+    #[prost(message, optional, tag = "2")]
+    pub transaction_parameters: Option<TransactionParameters>,
+}
+
+/* penumbra.core.transaction.v1.TransactionParameters */
+#[derive(Clone, PartialEq, Message)]
+pub struct TransactionParameters {
+    #[prost(message, optional, tag = "3")]
+    pub fee: Option<Fee>,
+}
+
+/* penumbra.core.component.fee.v1.Fee */
+#[derive(Clone, PartialEq, Message)]
+pub struct Fee {
+    #[prost(message, optional, tag = "1")]
+    pub amount: Option<Amount>,
+    #[prost(message, optional, tag = "2")]
+    pub asset_id: Option<AssetId>,
+}
+
+/* penumbra.core.transaction.v1.Action */
+#[derive(Clone, PartialEq, Message)]
+pub struct Action {
+    #[prost(
+        oneof = "action::Kind",
+        tags = "1, 2, 3, 4, 16, 17, 18, 19, 20, 21, 22, 30, 31, 32, 34, 40, 41, 42, 50, 51, 52, 53, 54, 55, 70, 200"
+    )]
+    pub kind: Option<action::Kind>,
+}
+
+pub mod action {
+    use prost::Oneof;
+
+    /* The variants of the Action oneof, keyed by their proto field numbers */
+    #[derive(Clone, PartialEq, Oneof)]
+    pub enum Kind {
+        #[prost(bytes = "vec", tag = "1")]
+        Spend(Vec<u8>),
+        #[prost(bytes = "vec", tag = "2")]
+        Output(Vec<u8>),
+        #[prost(bytes = "vec", tag = "3")]
+        Swap(Vec<u8>),
+        #[prost(bytes = "vec", tag = "4")]
+        SwapClaim(Vec<u8>),
+        #[prost(bytes = "vec", tag = "16")]
+        ValidatorDefinition(Vec<u8>),
+        #[prost(message, tag = "17")]
+        IbcRelay(super::IbcRelay),
+        #[prost(bytes = "vec", tag = "18")]
+        ProposalSubmit(Vec<u8>),
+        #[prost(bytes = "vec", tag = "19")]
+        ProposalWithdraw(Vec<u8>),
+        #[prost(bytes = "vec", tag = "20")]
+        ValidatorVote(Vec<u8>),
+        #[prost(bytes = "vec", tag = "21")]
+        DelegatorVote(Vec<u8>),
+        #[prost(bytes = "vec", tag = "22")]
+        ProposalDepositClaim(Vec<u8>),
+        #[prost(bytes = "vec", tag = "30")]
+        PositionOpen(Vec<u8>),
+        #[prost(bytes = "vec", tag = "31")]
+        PositionClose(Vec<u8>),
+        #[prost(bytes = "vec", tag = "32")]
+        PositionWithdraw(Vec<u8>),
+        #[prost(bytes = "vec", tag = "34")]
+        PositionRewardClaim(Vec<u8>),
+        #[prost(message, tag = "40")]
+        Delegate(super::Delegate),
+        #[prost(message, tag = "41")]
+        Undelegate(super::Undelegate),
+        #[prost(bytes = "vec", tag = "42")]
+        UndelegateClaim(Vec<u8>),
+        #[prost(bytes = "vec", tag = "50")]
+        CommunityPoolSpend(Vec<u8>),
+        #[prost(bytes = "vec", tag = "51")]
+        CommunityPoolOutput(Vec<u8>),
+        #[prost(bytes = "vec", tag = "52")]
+        CommunityPoolDeposit(Vec<u8>),
+        #[prost(bytes = "vec", tag = "53")]
+        DutchAuctionSchedule(Vec<u8>),
+        #[prost(bytes = "vec", tag = "54")]
+        DutchAuctionEnd(Vec<u8>),
+        #[prost(bytes = "vec", tag = "55")]
+        DutchAuctionWithdraw(Vec<u8>),
+        #[prost(bytes = "vec", tag = "70")]
+        LiquidityTournamentVote(Vec<u8>),
+        #[prost(message, tag = "200")]
+        Ics20Withdrawal(super::Ics20Withdrawal),
+    }
+}
+
+/* penumbra.core.num.v1.Amount, a 128-bit integer split into two halves */
+#[derive(Clone, PartialEq, Message)]
+pub struct Amount {
+    #[prost(uint64, tag = "1")]
+    pub lo: u64,
+    #[prost(uint64, tag = "2")]
+    pub hi: u64,
+}
+
+impl Amount {
+    /*
+    * Combines both halves into the full base-unit value.
+    */
+    pub fn value(&self) -> u128 {
+        ((self.hi as u128) << 64) | self.lo as u128
+    }
+}
+
+/* penumbra.core.asset.v1.AssetId */
+#[derive(Clone, PartialEq, Message)]
+pub struct AssetId {
+    #[prost(bytes = "vec", tag = "1")]
+    pub inner: Vec<u8>,
+}
+
+/* penumbra.core.keys.v1.IdentityKey */
+#[derive(Clone, PartialEq, Message)]
+pub struct IdentityKey {
+    #[prost(bytes = "vec", tag = "1")]
+    pub ik: Vec<u8>,
+}
+
+/* penumbra.core.component.stake.v1.Delegate */
+#[derive(Clone, PartialEq, Message)]
+pub struct Delegate {
+    #[prost(message, optional, tag = "1")]
+    pub validator_identity: Option<IdentityKey>,
+    #[prost(message, optional, tag = "3")]
+    pub unbonded_amount: Option<Amount>,
+    #[prost(message, optional, tag = "4")]
+    pub delegation_amount: Option<Amount>,
+}
+
+/* penumbra.core.component.stake.v1.Undelegate */
+#[derive(Clone, PartialEq, Message)]
+pub struct Undelegate {
+    #[prost(message, optional, tag = "1")]
+    pub validator_identity: Option<IdentityKey>,
+    #[prost(message, optional, tag = "3")]
+    pub unbonded_amount: Option<Amount>,
+    #[prost(message, optional, tag = "4")]
+    pub delegation_amount: Option<Amount>,
+}
+
+/* penumbra.core.asset.v1.Denom */
+#[derive(Clone, PartialEq, Message)]
+pub struct Denom {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+}
+
+/* google.protobuf.Any */
+#[derive(Clone, PartialEq, Message)]
+pub struct Any {
+    #[prost(string, tag = "1")]
+    pub type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub value: Vec<u8>,
+}
+
+/* penumbra.core.component.ibc.v1.IbcRelay */
+#[derive(Clone, PartialEq, Message)]
+pub struct IbcRelay {
+    #[prost(message, optional, tag = "1")]
+    pub raw_action: Option<Any>,
+}
+
+/* penumbra.core.component.ibc.v1.Ics20Withdrawal */
+#[derive(Clone, PartialEq, Message)]
+pub struct Ics20Withdrawal {
+    #[prost(message, optional, tag = "1")]
+    pub amount: Option<Amount>,
+    #[prost(message, optional, tag = "2")]
+    pub denom: Option<Denom>,
+    #[prost(string, tag = "3")]
+    pub destination_chain_address: String,
+    #[prost(string, tag = "7")]
+    pub source_channel: String,
+}
+
+/* ibc.core.channel.v1.MsgRecvPacket */
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgRecvPacket {
+    #[prost(message, optional, tag = "1")]
+    pub packet: Option<Packet>,
+}
+
+/* ibc.core.channel.v1.Packet */
+#[derive(Clone, PartialEq, Message)]
+pub struct Packet {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    #[prost(string, tag = "2")]
+    pub source_port: String,
+    #[prost(string, tag = "3")]
+    pub source_channel: String,
+    #[prost(string, tag = "4")]
+    pub destination_port: String,
+    #[prost(string, tag = "5")]
+    pub destination_channel: String,
+    #[prost(bytes = "vec", tag = "6")]
+    pub data: Vec<u8>,
+}
@@ -0,0 +1,57 @@
+/*
+* Manual resync jobs.
+*
+* Operators can ask for a height range to be fetched and stored again
+* through the admin API. Jobs wait on a bounded queue and a single worker
+* runs them one at a time, so requests never start concurrent resyncs.
+*/
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tracing::{error, info};
+use crate::client::PenumbraClient;
+
+/* Number of resync jobs that can wait before new ones are rejected */
+pub const RESYNC_QUEUE_CAPACITY: usize = 16;
+
+/* Largest number of heights a single resync job may cover */
+pub const MAX_RESYNC_SPAN: u64 = 10_000;
+
+/*
+* A height range to fetch and store again.
+*/
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct ResyncJob {
+    /// First height to refetch (inclusive)
+    pub from: u64,
+
+    /// Last height to refetch (inclusive)
+    pub to: u64,
+}
+
+/*
+* Creates the queue handing resync jobs from the API to the worker.
+*/
+pub fn channel() -> (mpsc::Sender<ResyncJob>, mpsc::Receiver<ResyncJob>) {
+    mpsc::channel(RESYNC_QUEUE_CAPACITY)
+}
+
+/*
+* Runs queued resync jobs one at a time until the queue is closed.
+*
+* Each job re-runs fetch_blocks over its range, overwriting the stored
+* blocks; heights that keep failing are recorded in failed_blocks.
+*
+* @param client Client the blocks are fetched and stored with
+* @param jobs Receiving end of the resync queue
+* @param batch_size Number of blocks to fetch in each batch
+*/
+pub async fn run_worker(client: PenumbraClient, mut jobs: mpsc::Receiver<ResyncJob>, batch_size: u64) {
+    while let Some(job) = jobs.recv().await {
+        info!(from = job.from, to = job.to, "Starting manual resync");
+        match client.fetch_blocks(job.from, job.to, batch_size).await {
+            Ok(()) => info!(from = job.from, to = job.to, "Manual resync finished"),
+            Err(e) => error!(from = job.from, to = job.to, error = %e, "Manual resync failed"),
+        }
+    }
+}
@@ -2,57 +2,439 @@
 * Penumbra RPC client implementation.
 *
 * Handles low-level communication with the Penumbra blockchain RPC endpoints,
-* including request formatting and response parsing.
+* including request formatting, response parsing and retrying transient failures.
 */
 
+use metrics::gauge;
+use rand::Rng;
 use reqwest::Client as HttpClient;
-use std::error::Error;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
 use std::time::Duration;
-use crate::client::models::{BlockResponse, StatusResponse};
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tracing::{instrument, warn};
+use crate::client::error::ClientError;
+use crate::client::models::{BlockResponse, RpcErrorResponse, StatusResponse};
+use crate::telemetry;
 
-/* Default timeout for HTTP requests in seconds */
-const DEFAULT_TIMEOUT: u64 = 30;
+/* Default timeout in seconds for a whole HTTP request, including the response body */
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/* Default timeout in seconds for establishing a connection to the node */
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 30;
+
+/* Default number of retries after the first failed attempt */
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/* Default base delay for exponential backoff in milliseconds */
+pub const DEFAULT_RETRY_BASE_DELAY_MS: u64 = 500;
+
+/* Default number of RPC requests allowed in flight at once */
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/* Default seconds an unused pooled connection to the node is kept open */
+pub const DEFAULT_POOL_IDLE_TIMEOUT_SECS: u64 = 90;
+
+/* Interval in seconds between TCP keep-alive probes on connections to the node */
+const TCP_KEEPALIVE_SECS: u64 = 60;
+
+/* Upper bound on the backoff exponent to avoid overflowing the delay */
+const MAX_BACKOFF_EXPONENT: u32 = 10;
+
+/*
+* HTTP timeouts applied to RPC requests.
+*
+* The connect timeout is kept separate so an unreachable node fails fast
+* while slow responses from a reachable one are still allowed to finish.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct RpcTimeouts {
+    /// Maximum time for a whole request, including the response body
+    pub request: Duration,
+
+    /// Maximum time for establishing a connection
+    pub connect: Duration,
+}
+
+impl Default for RpcTimeouts {
+    fn default() -> Self {
+        Self {
+            request: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+            connect: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS),
+        }
+    }
+}
+
+/*
+* Connection reuse settings for the HTTP client.
+*
+* At most max_concurrent requests run at once, so keeping that many idle
+* connections lets every batch reuse the connections of the previous one
+* instead of opening new ones.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct RpcConnectionPool {
+    /// Maximum idle connections kept open to the node
+    pub max_idle_per_host: usize,
+
+    /// Time an idle connection is kept before it is closed
+    pub idle_timeout: Duration,
+}
+
+impl Default for RpcConnectionPool {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            idle_timeout: Duration::from_secs(DEFAULT_POOL_IDLE_TIMEOUT_SECS),
+        }
+    }
+}
+
+/*
+* A request holding one of the client's concurrency permits.
+*
+* Keeps the in-flight gauge in step with the permits handed out.
+*/
+struct InFlightRequest<'a> {
+    _permit: SemaphorePermit<'a>,
+}
+
+impl Drop for InFlightRequest<'_> {
+    fn drop(&mut self) {
+        gauge!(telemetry::RPC_REQUESTS_IN_FLIGHT).decrement(1.0);
+    }
+}
 
 /*
 * Client for making RPC requests to the Penumbra blockchain.
+*
+* Clones share the same concurrency limit, so parallel sync workers
+* cannot exceed it together.
 */
 #[derive(Debug, Clone)]
 pub struct RpcClient {
     client: HttpClient,
     base_url: String,
+    max_retries: u32,
+    retry_base_delay: Duration,
+    request_permits: Arc<Semaphore>,
 }
 
 impl RpcClient {
     /*
     * Creates a new RPC client instance.
+    *
+    * @param base_url Base URL of the Penumbra RPC endpoint
+    * @param max_retries Number of retries after the first failed attempt
+    * @param retry_base_delay Base delay for exponential backoff between retries
+    * @param timeouts Request and connect timeouts for each attempt
+    * @param max_concurrent Maximum number of requests in flight at once
+    * @param pool Idle connection limits for reusing connections to the node
     */
-    pub fn new(base_url: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+    pub fn new(
+        base_url: &str,
+        max_retries: u32,
+        retry_base_delay: Duration,
+        timeouts: RpcTimeouts,
+        max_concurrent: usize,
+        pool: RpcConnectionPool,
+    ) -> Result<Self, ClientError> {
         let client = HttpClient::builder()
-            .timeout(Duration::from_secs(DEFAULT_TIMEOUT))
-            .connect_timeout(Duration::from_secs(DEFAULT_TIMEOUT))
+            .timeout(timeouts.request)
+            .connect_timeout(timeouts.connect)
+            .pool_max_idle_per_host(pool.max_idle_per_host)
+            .pool_idle_timeout(pool.idle_timeout)
+            .tcp_keepalive(Duration::from_secs(TCP_KEEPALIVE_SECS))
             .build()?;
 
         Ok(Self {
             client,
             base_url: base_url.to_string(),
+            max_retries,
+            retry_base_delay,
+            request_permits: Arc::new(Semaphore::new(max_concurrent.max(1))),
         })
     }
 
     /*
     * Fetches the current node status.
     */
-    pub async fn get_status(&self) -> Result<StatusResponse, Box<dyn Error + Send + Sync>> {
+    pub async fn get_status(&self) -> Result<StatusResponse, ClientError> {
         let url = format!("{}/status", self.base_url);
-        let response = self.client.get(&url).send().await?.json().await?;
-        Ok(response)
+        self.request_with_retry(&url).await
+    }
+
+    /*
+    * Fetches the current node status once, without retries.
+    *
+    * Used to check that the node is reachable, so a misconfigured URL
+    * fails within the given timeout instead of the regular request timeout.
+    * The probe holds a concurrency permit like any other request, and the
+    * timeout covers waiting for the permit too.
+    *
+    * @param timeout Time allowed for the whole request
+    */
+    pub async fn probe_status(&self, timeout: Duration) -> Result<StatusResponse, ClientError> {
+        let url = format!("{}/status", self.base_url);
+        let probe = async {
+            let _in_flight = self.start_request().await?;
+            let response = self.client.get(&url).send().await?;
+            Ok(response.error_for_status()?.json().await?)
+        };
+
+        tokio::time::timeout(timeout, probe)
+            .await
+            .map_err(|_| ClientError::Rpc(format!("status probe to {} timed out after {:?}", url, timeout)))?
     }
 
     /*
     * Fetches a block at the specified height.
+    *
+    * Some nodes answer with their latest block when asked for a pruned or
+    * future height, so the returned header height is checked against the
+    * requested one.
+    *
+    * @param height Block height to fetch
+    * @return The block, or an error if the node returned a different height
     */
-    pub async fn get_block(&self, height: u64) -> Result<BlockResponse, Box<dyn Error + Send + Sync>> {
+    #[instrument(skip(self))]
+    pub async fn get_block(&self, height: u64) -> Result<BlockResponse, ClientError> {
         let url = format!("{}/block?height={}", self.base_url, height);
-        let response = self.client.get(&url).send().await?.json().await?;
+        let response: BlockResponse = self.request_with_retry(&url).await?;
+
+        let returned = &response.result.block.header.height;
+        if returned.parse::<u64>().ok() != Some(height) {
+            return Err(ClientError::HeightMismatch {
+                requested: height,
+                returned: returned.clone(),
+            });
+        }
+
         Ok(response)
     }
+
+    /*
+    * Performs a GET request and parses the JSON response, retrying on failure.
+    *
+    * A JSON-RPC error object in the body is returned as the node's error,
+    * whatever the status. Otherwise connection errors, timeouts, bodies
+    * that fail to arrive in full and 5xx responses are retried with
+    * exponential backoff and jitter, while 4xx responses and parse errors
+    * are returned immediately.
+    *
+    * Each attempt holds a concurrency permit until its response is read;
+    * permits are released while backing off.
+    *
+    * @param url Full URL to request
+    * @return Parsed response body or the final error
+    */
+    async fn request_with_retry<T: DeserializeOwned>(&self, url: &str) -> Result<T, ClientError> {
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            let error = {
+                let _in_flight = self.start_request().await?;
+                match self.client.get(url).send().await {
+                    Ok(response) => {
+                        let status = response.status();
+                        let status_error = response.error_for_status_ref().err();
+                        // A connection dropped mid-body is as transient as one that never connected
+                        match response.bytes().await {
+                            Err(e) => format!("failed to read response body: {}", e),
+                            Ok(body) => {
+                                // Tendermint reports rejected requests as JSON-RPC errors, usually with a 500 status
+                                if let Ok(rejected) = serde_json::from_slice::<RpcErrorResponse>(&body) {
+                                    return Err(rejected.error.into());
+                                }
+
+                                match status_error {
+                                    Some(_) if status.is_server_error() => format!("server returned {}", status),
+                                    Some(e) => return Err(e.into()),
+                                    None => return Ok(serde_json::from_slice(&body)?),
+                                }
+                            }
+                        }
+                    }
+                    Err(e) if e.is_connect() || e.is_timeout() || e.is_request() => e.to_string(),
+                    Err(e) => return Err(e.into()),
+                }
+            };
+
+            if attempt > self.max_retries {
+                return Err(ClientError::Rpc(format!("request to {} failed after {} attempts: {}", url, attempt, error)));
+            }
+
+            let delay = self.backoff_delay(attempt);
+            warn!(url = %url, attempt, error = %error, delay_ms = delay.as_millis() as u64, "RPC request failed, retrying");
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /*
+    * Waits for a free concurrency permit and counts the request as in flight.
+    */
+    async fn start_request(&self) -> Result<InFlightRequest<'_>, ClientError> {
+        let permit = self.request_permits
+            .acquire()
+            .await
+            .map_err(|e| ClientError::Rpc(e.to_string()))?;
+        gauge!(telemetry::RPC_REQUESTS_IN_FLIGHT).increment(1.0);
+        Ok(InFlightRequest { _permit: permit })
+    }
+
+    /*
+    * Computes the backoff delay before the next attempt.
+    *
+    * Doubles the base delay per attempt and adds up to one base delay of jitter.
+    */
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exponent = (attempt - 1).min(MAX_BACKOFF_EXPONENT);
+        let base_ms = self.retry_base_delay.as_millis() as u64;
+        let jitter_ms = rand::thread_rng().gen_range(0..=base_ms);
+        Duration::from_millis(base_ms * (1 << exponent) + jitter_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock_node::{self, MockNode};
+
+    fn client(node: &MockNode) -> RpcClient {
+        client_with_pool(node, RpcConnectionPool::default())
+    }
+
+    fn client_with_pool(node: &MockNode, pool: RpcConnectionPool) -> RpcClient {
+        RpcClient::new(
+            &node.url,
+            0,
+            Duration::from_millis(1),
+            RpcTimeouts::default(),
+            1,
+            pool,
+        ).unwrap()
+    }
+
+    #[tokio::test]
+    async fn status_probe_waits_for_a_concurrency_permit() {
+        let node = MockNode::start(|_| (200, mock_node::status_body(100))).await;
+        let client = client(&node);
+
+        let in_flight = client.start_request().await.unwrap();
+        assert!(matches!(client.probe_status(Duration::from_millis(50)).await, Err(ClientError::Rpc(_))));
+        assert_eq!(node.requests(), 0);
+
+        drop(in_flight);
+        let status = client.probe_status(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(status.chain_height().unwrap(), 100);
+    }
+
+    #[tokio::test]
+    async fn returns_the_requested_block() {
+        let node = MockNode::start(|_| (200, mock_node::block_body(5, "AAAA"))).await;
+        let block = client(&node).get_block(5).await.unwrap();
+        assert_eq!(block.result.block_id.hash, "AAAA");
+    }
+
+    #[tokio::test]
+    async fn rejects_a_block_for_another_height() {
+        // Nodes may answer a pruned or future height with their latest block
+        let node = MockNode::start(|_| (200, mock_node::block_body(900, "AAAA"))).await;
+        match client(&node).get_block(5).await {
+            Err(ClientError::HeightMismatch { requested, returned }) => {
+                assert_eq!(requested, 5);
+                assert_eq!(returned, "900");
+            }
+            other => panic!("expected a height mismatch, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn surfaces_the_json_rpc_error_of_a_pruned_height() {
+        let body = r#"{"jsonrpc":"2.0","id":-1,"error":{"code":-32603,"message":"Internal error","data":"height 5 is not available, lowest height is 1000"}}"#;
+        let node = MockNode::start(move |_| (500, body.to_string())).await;
+
+        match client(&node).get_block(5).await {
+            Err(ClientError::Node { code, message, data }) => {
+                assert_eq!(code, -32603);
+                assert_eq!(message, "Internal error");
+                assert_eq!(data.as_deref(), Some("height 5 is not available, lowest height is 1000"));
+            }
+            other => panic!("expected the node's error, got {:?}", other),
+        }
+        // Rejections are final, so the request is not retried
+        assert_eq!(node.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn surfaces_a_json_rpc_error_sent_with_a_success_status() {
+        let body = r#"{"jsonrpc":"2.0","id":-1,"error":{"code":-32602,"message":"Invalid params"}}"#;
+        let node = MockNode::start(move |_| (200, body.to_string())).await;
+
+        match client(&node).get_block(5).await {
+            Err(ClientError::Node { code, data, .. }) => {
+                assert_eq!(code, -32602);
+                assert_eq!(data, None);
+            }
+            other => panic!("expected the node's error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn sequential_requests_reuse_one_connection() {
+        let node = MockNode::start(|_| (200, mock_node::status_body(100))).await;
+        let client = client(&node);
+
+        for _ in 0..20 {
+            client.get_status().await.unwrap();
+        }
+        assert_eq!(node.requests(), 20);
+        assert_eq!(node.connections(), 1);
+    }
+
+    #[tokio::test]
+    async fn without_idle_connections_every_request_connects() {
+        let node = MockNode::start(|_| (200, mock_node::status_body(100))).await;
+        let pool = RpcConnectionPool { max_idle_per_host: 0, ..RpcConnectionPool::default() };
+        let client = client_with_pool(&node, pool);
+
+        for _ in 0..5 {
+            client.get_status().await.unwrap();
+        }
+        assert_eq!(node.connections(), 5);
+    }
+
+    #[tokio::test]
+    async fn retries_a_response_whose_body_is_cut_off() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Promises a longer body than it sends, then closes the connection
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let attempts = Arc::new(AtomicUsize::new(0));
+        tokio::spawn({
+            let attempts = attempts.clone();
+            async move {
+                while let Ok((mut stream, _)) = listener.accept().await {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    let mut request = [0; 1024];
+                    let _ = stream.read(&mut request).await;
+                    let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\n{\"result\"").await;
+                }
+            }
+        });
+
+        let client = RpcClient::new(&url, 2, Duration::from_millis(1), RpcTimeouts::default(), 1, RpcConnectionPool::default()).unwrap();
+        match client.get_status().await {
+            Err(ClientError::Rpc(message)) => {
+                assert!(message.contains("failed after 3 attempts"), "{}", message);
+                assert!(message.contains("failed to read response body"), "{}", message);
+            }
+            other => panic!("expected a retryable RPC error, got {:?}", other),
+        }
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
 }
@@ -6,19 +6,129 @@
 * and retry logic.
 */
 
-use std::error::Error;
-use std::time::Duration;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use chrono::Utc;
+use metrics::{counter, histogram};
 use sqlx::{Pool, Postgres};
-use crate::client::rpc::RpcClient;
-use crate::client::models::BlockResponse;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, error, info, instrument, warn};
+use crate::client::error::ClientError;
+use crate::client::rpc::{RpcClient, RpcConnectionPool, RpcTimeouts, DEFAULT_MAX_RETRIES, DEFAULT_RETRY_BASE_DELAY_MS};
+use crate::client::decode::{self, ActionInfo};
+use crate::client::models::{BlockResponse, StatusResponse};
+use crate::client::last_error::LastErrorTracker;
+use crate::client::throughput::ThroughputMeter;
+use crate::client::validation::{self, DecodeOutcome, DecodeValidationStats};
+use crate::client::verify;
 use crate::models::StoredBlock;
+use crate::models::amount::TokenAmount;
+use crate::models::block::BlockSummary;
+use crate::models::transaction::TransactionSummary;
+use crate::telemetry;
 
 /* Default retry delay in seconds */
 const RETRY_DELAY: u64 = 5;
 
+/* Maximum number of ancestors re-fetched when a chain reorganization is detected */
+pub const MAX_REORG_DEPTH: u64 = 10;
+
+/* Number of blocks beyond the known chain tip a requested height may be */
+const CHAIN_TIP_MARGIN: u64 = 10;
+
+/* Number of stored blocks between throughput log lines */
+const THROUGHPUT_LOG_INTERVAL: u64 = 100;
+
+/* Number of stored blocks between decode validation summaries */
+const DECODE_SUMMARY_INTERVAL: u64 = 100;
+
+/* Default number of attempts for a block before it is recorded as failed */
+pub const DEFAULT_BLOCK_MAX_ATTEMPTS: u32 = 3;
+
+/* Maximum number of failed blocks re-attempted per retry pass */
+const FAILED_BLOCK_RETRY_BATCH: i64 = 100;
+
+/* Number of verified blocks between verification progress logs */
+const VERIFY_PROGRESS_INTERVAL: u64 = 1000;
+
+/* Number of attempts to commit a batch of blocks before giving up on it */
+const BATCH_COMMIT_ATTEMPTS: u32 = 3;
+
+/* Default time in seconds a node status response is reused before refetching */
+pub const DEFAULT_STATUS_CACHE_TTL_SECS: u64 = 2;
+
 /* Default batch size for block synchronization */
-const DEFAULT_BATCH_SIZE: u64 = 100;
+pub const DEFAULT_BATCH_SIZE: u64 = 100;
+
+/* Largest batch size honored, bounding the blocks a batch holds in memory */
+pub const MAX_BATCH_SIZE: u64 = 10_000;
+
+/* Pooled connections parallel sync leaves to the API server and background tasks */
+const RESERVED_DB_CONNECTIONS: usize = 2;
+
+/*
+* Outcome of processing a single block.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockOutcome {
+    /// The block was fetched and stored
+    Stored,
+
+    /// The height is beyond the known chain tip and was not fetched
+    Skipped,
+}
+
+/*
+* A fetched block converted into the rows it is stored as.
+*/
+#[derive(Debug)]
+struct PreparedBlock {
+    block: StoredBlock,
+    transactions: Vec<PreparedTransaction>,
+}
+
+/*
+* A transaction row derived from a fetched block.
+*/
+#[derive(Debug)]
+struct PreparedTransaction {
+    tx_hash: String,
+    action: ActionInfo,
+    data: String,
+}
+
+/*
+* Counts from verifying stored blocks against the chain.
+*/
+#[derive(Debug, Default, Clone, Copy)]
+pub struct VerifySummary {
+    /// Blocks whose stored hash and transactions match the chain
+    pub matched: u64,
+
+    /// Stored blocks that differ from the chain
+    pub mismatched: u64,
+
+    /// Heights not stored at all
+    pub missing: u64,
+
+    /// Mismatched or missing blocks re-synced from the chain
+    pub fixed: u64,
+
+    /// Heights that could not be fetched from the node
+    pub errors: u64,
+}
+
+impl VerifySummary {
+    /*
+    * Number of differing or missing blocks left in place, plus heights
+    * that could not be checked.
+    */
+    pub fn unresolved(&self) -> u64 {
+        self.mismatched + self.missing - self.fixed + self.errors
+    }
+}
 
 /*
 * Main client for interacting with the Penumbra blockchain.
@@ -27,11 +137,28 @@ const DEFAULT_BATCH_SIZE: u64 = 100;
 * - RPC communication with the node
 * - Block fetching and parsing
 * - Database storage of block data
+* - Publishing newly stored blocks to subscribers
+* - Caching node status responses for a short TTL
 */
 #[derive(Debug, Clone)]
 pub struct PenumbraClient {
     rpc_client: RpcClient,
     pub db_pool: Pool<Postgres>,
+    block_tx: broadcast::Sender<BlockSummary>,
+    status_cache: Arc<RwLock<Option<(Instant, StatusResponse)>>>,
+    status_cache_ttl: Duration,
+    chain_tip: Arc<AtomicU64>,
+    compress_block_data: bool,
+    throughput: ThroughputMeter,
+    validate_decode: bool,
+    decode_stats: Arc<DecodeValidationStats>,
+    batch_commit: bool,
+    last_error: LastErrorTracker,
+    tx_feed: Option<broadcast::Sender<TransactionSummary>>,
+    max_block_attempts: u32,
+    store_raw_block_data: bool,
+    verify_block_hashes: bool,
+    confirmations: u64,
 }
 
 impl PenumbraClient {
@@ -40,26 +167,217 @@ impl PenumbraClient {
     *
     * @param addr Base URL of the Penumbra RPC endpoint
     * @param pool PostgreSQL connection pool for database operations
+    * @param block_tx Broadcast channel on which newly stored blocks are published
+    * @param rpc_timeouts Request and connect timeouts for RPC calls
+    * @param rpc_max_concurrent Maximum number of RPC requests in flight at once
+    * @param rpc_pool Idle connection limits for the RPC HTTP client
+    * @param status_cache_ttl How long a node status response is reused
+    * @param compress_block_data Store block payloads zstd-compressed instead of as JSONB
+    * @param throughput Meter recording every processed block
     * @return Result containing either the client instance or an error
     */
-    pub async fn connect(addr: &str, pool: Pool<Postgres>) -> Result<Self, Box<dyn Error + Send + Sync>> {
-        println!("Attempting to connect with RPC config...");
+    #[allow(clippy::too_many_arguments)]
+    pub async fn connect(
+        addr: &str,
+        pool: Pool<Postgres>,
+        block_tx: broadcast::Sender<BlockSummary>,
+        rpc_timeouts: RpcTimeouts,
+        rpc_max_concurrent: usize,
+        rpc_pool: RpcConnectionPool,
+        status_cache_ttl: Duration,
+        compress_block_data: bool,
+        throughput: ThroughputMeter,
+    ) -> Result<Self, ClientError> {
+        info!("Attempting to connect with RPC config...");
 
-        let rpc_client = RpcClient::new(addr)?;
+        let rpc_client = RpcClient::new(
+            addr,
+            DEFAULT_MAX_RETRIES,
+            Duration::from_millis(DEFAULT_RETRY_BASE_DELAY_MS),
+            rpc_timeouts,
+            rpc_max_concurrent,
+            rpc_pool,
+        )?;
 
-        println!("HTTP client created successfully");
+        info!("HTTP client created successfully");
 
         Ok(Self {
             rpc_client,
             db_pool: pool,
+            block_tx,
+            status_cache: Arc::new(RwLock::new(None)),
+            status_cache_ttl,
+            chain_tip: Arc::new(AtomicU64::new(0)),
+            compress_block_data,
+            throughput,
+            validate_decode: false,
+            decode_stats: Arc::new(DecodeValidationStats::default()),
+            batch_commit: false,
+            last_error: LastErrorTracker::new(),
+            tx_feed: None,
+            max_block_attempts: DEFAULT_BLOCK_MAX_ATTEMPTS,
+            store_raw_block_data: true,
+            verify_block_hashes: false,
+            confirmations: 0,
         })
     }
 
+    /*
+    * Enables re-decoding every stored transaction to validate the stored
+    * hash and action type, logging discrepancies without failing blocks.
+    *
+    * @param enabled Whether decode validation runs
+    * @return The client with decode validation configured
+    */
+    pub fn with_decode_validation(mut self, enabled: bool) -> Self {
+        self.validate_decode = enabled;
+        self
+    }
+
+    /*
+    * Enables committing each fetch batch in a single database transaction
+    * instead of one transaction per block.
+    *
+    * @param enabled Whether batches are committed as a whole
+    * @return The client with batch commits configured
+    */
+    pub fn with_batch_commit(mut self, enabled: bool) -> Self {
+        self.batch_commit = enabled;
+        self
+    }
+
+    /*
+    * Shares a tracker that records block processing failures.
+    *
+    * @param last_error Tracker also read by the API
+    * @return The client recording errors into the tracker
+    */
+    pub fn with_error_tracker(mut self, last_error: LastErrorTracker) -> Self {
+        self.last_error = last_error;
+        self
+    }
+
+    /*
+    * Sets how many blocks the indexer trails the chain tip by.
+    *
+    * Blocks within the confirmation window are not indexed until enough
+    * blocks have been built on top of them. Reorg repair still overwrites
+    * stored ancestors that turn out to have been replaced.
+    *
+    * @param confirmations Number of blocks to trail the tip by
+    * @return The client with the confirmation depth configured
+    */
+    pub fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /*
+    * Highest height considered safe to index for a chain height.
+    *
+    * @param chain_height Latest height reported by the node
+    * @return The chain height minus the confirmation depth, 0 when no block is confirmed yet
+    */
+    pub fn confirmed_height(&self, chain_height: u64) -> u64 {
+        chain_height.saturating_sub(self.confirmations)
+    }
+
+    /*
+    * Enables recomputing every block's header hash and comparing it to the
+    * block ID reported by the node, logging mismatches without failing blocks.
+    *
+    * @param enabled Whether block hashes are verified
+    * @return The client with hash verification configured
+    */
+    pub fn with_block_hash_verification(mut self, enabled: bool) -> Self {
+        self.verify_block_hashes = enabled;
+        self
+    }
+
+    /*
+    * Controls whether the raw block payload is stored alongside the
+    * derived block fields.
+    *
+    * @param enabled Whether block payloads are stored
+    * @return The client with raw block storage configured
+    */
+    pub fn with_raw_block_data(mut self, enabled: bool) -> Self {
+        self.store_raw_block_data = enabled;
+        self
+    }
+
+    /*
+    * Sets how many times a block is attempted before it is recorded as failed.
+    *
+    * @param max_attempts Attempts per block, at least one
+    * @return The client with the retry ceiling configured
+    */
+    pub fn with_max_block_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_block_attempts = max_attempts.max(1);
+        self
+    }
+
+    /*
+    * Publishes every stored transaction on a broadcast channel.
+    *
+    * @param tx_feed Channel the SSE transaction feed subscribes to
+    * @return The client publishing new transactions
+    */
+    pub fn with_transaction_feed(mut self, tx_feed: broadcast::Sender<TransactionSummary>) -> Self {
+        self.tx_feed = Some(tx_feed);
+        self
+    }
+
     /*
     * Retrieves the current status of the Penumbra node.
+    *
+    * Returns the cached response if it is younger than the status cache
+    * TTL, otherwise queries the node and refreshes the cache.
     */
-    pub async fn get_status(&self) -> Result<crate::client::models::StatusResponse, Box<dyn Error + Send + Sync>> {
-        self.rpc_client.get_status().await
+    pub async fn get_status(&self) -> Result<StatusResponse, ClientError> {
+        if let Some((fetched_at, status)) = self.status_cache.read().await.as_ref() {
+            if fetched_at.elapsed() < self.status_cache_ttl {
+                return Ok(status.clone());
+            }
+        }
+
+        self.get_status_uncached().await
+    }
+
+    /*
+    * Checks that the node answers a status request with a parsable height.
+    *
+    * Makes a single attempt with a short timeout, for startup checks that
+    * do their own retrying.
+    *
+    * @param timeout Time allowed for the status request
+    * @return The node's latest block height
+    */
+    pub async fn check_node(&self, timeout: Duration) -> Result<u64, ClientError> {
+        self.rpc_client.probe_status(timeout).await?.chain_height()
+    }
+
+    /*
+    * Returns a shared handle to the highest chain height seen in node
+    * status responses, 0 until the first one.
+    */
+    pub fn chain_tip(&self) -> Arc<AtomicU64> {
+        self.chain_tip.clone()
+    }
+
+    /*
+    * Queries the node status, bypassing the cache.
+    *
+    * The fresh response still replaces the cached one and, when its
+    * height parses, the known chain tip.
+    */
+    pub async fn get_status_uncached(&self) -> Result<StatusResponse, ClientError> {
+        let status = self.rpc_client.get_status().await?;
+        if let Ok(height) = status.chain_height() {
+            self.chain_tip.fetch_max(height, Ordering::Relaxed);
+        }
+        *self.status_cache.write().await = Some((Instant::now(), status.clone()));
+        Ok(status)
     }
 
     /*
@@ -67,61 +385,335 @@ impl PenumbraClient {
     * Used for initial sync when the indexer first starts.
     *
     * @param batch_size Number of blocks to fetch in each batch
+    * @param start_height First height to index when the database is empty
+    * @param workers Number of parallel sync workers, 1 for a sequential sync
     */
-    pub async fn sync_from_genesis(&self, batch_size: u64) -> Result<(), Box<dyn Error + Send + Sync>> {
-        // Get the current blockchain height
-        let status = self.get_status().await?;
-        let chain_height: u64 = status.result.sync_info.latest_block_height
-            .parse()
-            .unwrap_or(0);
-
-        if chain_height == 0 {
-            return Err("Failed to parse chain height".into());
-        }
+    pub async fn sync_from_genesis(
+        &self,
+        batch_size: u64,
+        start_height: u64,
+        workers: usize,
+    ) -> Result<(), ClientError> {
+        // Get the current blockchain height, fresh since the sync target depends on it
+        let status = self.get_status_uncached().await?;
+        let chain_height = self.confirmed_height(status.chain_height()?);
 
-        println!("Current blockchain height: {}", chain_height);
+        info!(chain_height, confirmations = self.confirmations, "Current confirmed blockchain height");
 
-        // Get the highest block we have in our database
-        let latest_blocks = crate::db::blocks::get_latest_blocks(&self.db_pool).await?;
-        let db_height = if !latest_blocks.is_empty() {
-            latest_blocks[0].height as u64
-        } else {
-            0 // Database is empty
-        };
+        // Resume above the highest height with no gaps below it, not the highest stored block
+        let watermark = self.advance_contiguous_watermark().await?;
 
-        println!("Latest indexed height: {}", db_height);
+        info!(last_contiguous_height = ?watermark, "Contiguously indexed height");
 
         // If database is up to date
-        if db_height >= chain_height {
-            println!("Database is already up to date with blockchain");
+        if watermark.is_some_and(|height| height >= chain_height) {
+            info!("Database is already up to date with blockchain");
             return Ok(());
         }
 
-        // Start from genesis (block 1) if database is empty
-        let start_height = if db_height == 0 {
-            println!("Starting sync from genesis...");
-            1 // Genesis block (adjust if your chain starts at block 0)
-        } else {
-            println!("Continuing sync from last indexed block...");
-            db_height + 1
+        // Start from the configured start height if database is empty
+        let start_height = match watermark {
+            None => {
+                info!(start_height, "Starting sync from configured start height...");
+                start_height
+            }
+            Some(height) => {
+                info!("Continuing sync from last contiguous block...");
+                height + 1
+            }
         };
 
+        // A configured start height above the confirmed tip has nothing to fetch yet
+        if start_height > chain_height {
+            info!(start_height, chain_height, "Start height is above the confirmed chain height, nothing to sync");
+            return Ok(());
+        }
+
         // Use existing fetch_blocks method with progress reporting
-        println!("Fetching blocks from {} to {} (total: {} blocks)",
-                 start_height, chain_height, chain_height - start_height + 1);
+        info!(
+            start_height,
+            end_height = chain_height,
+            total = chain_height - start_height + 1,
+            "Fetching blocks"
+        );
+
+        if workers > 1 {
+            self.sync_range_parallel(start_height, chain_height, workers, batch_size).await?;
+        } else {
+            self.fetch_blocks(start_height, chain_height, batch_size).await?;
+        }
+
+        info!("Initial blockchain synchronization completed");
+        Ok(())
+    }
+
+    /*
+    * Indexes a fixed height range once, for one-shot reindex jobs.
+    *
+    * Blocks already stored in the range are re-synced. Blocks that exhaust
+    * their attempts get one more retry pass before the backfill is
+    * reported as incomplete.
+    *
+    * @param start_height First height of the range
+    * @param end_height Last height of the range (inclusive), the confirmed chain tip when None
+    * @param batch_size Number of blocks to fetch in each batch
+    * @param workers Number of parallel sync workers, 1 for a sequential sync
+    */
+    pub async fn backfill(
+        &self,
+        start_height: u64,
+        end_height: Option<u64>,
+        batch_size: u64,
+        workers: usize,
+    ) -> Result<(), ClientError> {
+        let end_height = match end_height {
+            Some(height) => height,
+            None => self.confirmed_height(self.get_status_uncached().await?.chain_height()?),
+        };
+
+        info!(
+            start_height,
+            end_height,
+            total = end_height.saturating_sub(start_height) + 1,
+            "Backfilling blocks"
+        );
 
-        // Sync blocks using existing fetch_blocks method
-        self.fetch_blocks(start_height, chain_height, batch_size).await?;
+        if workers > 1 {
+            self.sync_range_parallel(start_height, end_height, workers, batch_size).await?;
+        } else {
+            self.fetch_blocks(start_height, end_height, batch_size).await?;
+        }
+
+        self.retry_failed_blocks().await?;
+
+        let failed = crate::db::failed_blocks::count_failed_blocks_in_range(
+            &self.db_pool,
+            start_height as i64,
+            end_height as i64,
+        ).await?;
+        if failed > 0 {
+            return Err(ClientError::BackfillIncomplete { start: start_height, end: end_height, failed });
+        }
 
-        println!("Initial blockchain synchronization completed");
+        info!(start_height, end_height, "Backfill completed");
         Ok(())
     }
 
+    /*
+    * Verifies stored blocks against the chain, for auditing past indexing.
+    *
+    * Re-fetches every height in the range and compares the stored hash,
+    * transaction count and transaction hashes with the node's block.
+    * Differences are logged and only overwritten when fixing is enabled,
+    * so the run is idempotent.
+    *
+    * @param start_height First height of the range
+    * @param end_height Last height of the range (inclusive), the confirmed chain tip when None
+    * @param fix Re-sync mismatched and missing blocks from the chain
+    * @return Matched, mismatched, missing, fixed and failed counts
+    */
+    pub async fn verify_range(
+        &self,
+        start_height: u64,
+        end_height: Option<u64>,
+        fix: bool,
+    ) -> Result<VerifySummary, ClientError> {
+        let end_height = match end_height {
+            Some(height) => height,
+            None => self.confirmed_height(self.get_status_uncached().await?.chain_height()?),
+        };
+
+        info!(start_height, end_height, fix, "Verifying stored blocks against the chain");
+
+        let mut summary = VerifySummary::default();
+        for height in start_height..=end_height {
+            let block = match self.fetch_block(height).await {
+                Ok(block) => block,
+                Err(e) => {
+                    warn!(height, error = %e, "Could not fetch block to verify");
+                    summary.errors += 1;
+                    continue;
+                }
+            };
+
+            let needs_repair = match self.compare_stored_block(height, &block).await? {
+                Some(discrepancies) if discrepancies.is_empty() => {
+                    summary.matched += 1;
+                    false
+                }
+                Some(discrepancies) => {
+                    warn!(height, ?discrepancies, "Stored block does not match the chain");
+                    summary.mismatched += 1;
+                    true
+                }
+                None => {
+                    warn!(height, "Block is missing from the index");
+                    summary.missing += 1;
+                    true
+                }
+            };
+
+            if fix && needs_repair {
+                match self.store_fetched_block(height, block).await {
+                    Ok(()) => summary.fixed += 1,
+                    Err(e) => warn!(height, error = %e, "Could not re-sync block"),
+                }
+            }
+
+            if (height - start_height + 1).is_multiple_of(VERIFY_PROGRESS_INTERVAL) {
+                info!(height, ?summary, "Verification progress");
+            }
+        }
+
+        info!(
+            matched = summary.matched,
+            mismatched = summary.mismatched,
+            missing = summary.missing,
+            fixed = summary.fixed,
+            errors = summary.errors,
+            "Verification completed"
+        );
+        Ok(summary)
+    }
+
+    /*
+    * Compares the stored rows of a block with the block fetched from the node.
+    *
+    * @return The differences found, None when the block is not stored
+    */
+    async fn compare_stored_block(
+        &self,
+        height: u64,
+        block: &BlockResponse,
+    ) -> Result<Option<Vec<String>>, ClientError> {
+        let (stored_hash, stored_tx_count) =
+            match crate::db::blocks::get_hash_and_tx_count_at_height(&self.db_pool, height as i64).await? {
+                Some(stored) => stored,
+                None => return Ok(None),
+            };
+
+        let mut discrepancies = Vec::new();
+        let chain_hash = &block.result.block_id.hash;
+        if !stored_hash.eq_ignore_ascii_case(chain_hash) {
+            discrepancies.push(format!("hash: stored {}, chain {}", stored_hash, chain_hash));
+        }
+
+        let txs = block.result.block.data.transactions();
+        if stored_tx_count as usize != txs.len() {
+            discrepancies.push(format!("tx_count: stored {}, chain {}", stored_tx_count, txs.len()));
+        }
+
+        // Derived the same way as when storing, so undecodable transactions compare equal
+        let expected: HashSet<String> = txs.iter()
+            .enumerate()
+            .map(|(i, tx_data)| decode::transaction_hash(tx_data)
+                .unwrap_or_else(|_| format!("{}_{}", chain_hash, i)))
+            .collect();
+        let stored: HashSet<String> = crate::db::transactions::get_transactions_by_block_height(&self.db_pool, height as i64)
+            .await?
+            .into_iter()
+            .map(|tx| tx.tx_hash)
+            .collect();
+        if stored != expected {
+            discrepancies.push(format!(
+                "transactions: {} stored hashes not on chain, {} chain hashes not stored",
+                stored.difference(&expected).count(),
+                expected.difference(&stored).count()
+            ));
+        }
+
+        Ok(Some(discrepancies))
+    }
+
+    /*
+    * Synchronizes a height range with several workers in parallel.
+    *
+    * Splits the range into contiguous sub-ranges, one per worker, each
+    * driven on its own task. Every worker holds at most one database
+    * connection at a time. The API server shares the pool, so the worker
+    * count is capped at the pool size minus RESERVED_DB_CONNECTIONS, which
+    * keeps API requests from waiting on the pool during a backfill.
+    * Aggregate progress is logged after every batch.
+    *
+    * @param start_height First height of the range
+    * @param end_height Last height of the range (inclusive)
+    * @param workers Requested number of workers
+    * @param batch_size Number of blocks each worker fetches per batch
+    * @return Error counting the workers that failed or panicked, once all have stopped
+    */
+    pub async fn sync_range_parallel(
+        &self,
+        start_height: u64,
+        end_height: u64,
+        workers: usize,
+        batch_size: u64,
+    ) -> Result<(), ClientError> {
+        if start_height > end_height {
+            return Ok(());
+        }
+
+        let total = end_height - start_height + 1;
+        let max_connections = self.db_pool.options().get_max_connections() as usize;
+        let workers = (max_sync_workers(workers, max_connections) as u64).min(total);
+        let range_size = total.div_ceil(workers);
+        let completed = Arc::new(AtomicU64::new(0));
+
+        info!(workers, range_size, total, "Starting parallel sync");
+
+        let mut handles = Vec::new();
+        for worker in 0..workers {
+            let range_start = start_height + worker * range_size;
+            let range_end = (range_start + range_size - 1).min(end_height);
+            let client = self.clone();
+            let completed = completed.clone();
+
+            handles.push(tokio::spawn(async move {
+                for (batch_start, batch_end) in batches(range_start, range_end, batch_size) {
+                    client.fetch_blocks(batch_start, batch_end, batch_size).await?;
+
+                    let done = completed.fetch_add(batch_end - batch_start + 1, Ordering::Relaxed) + batch_end - batch_start + 1;
+                    info!(
+                        worker,
+                        completed = done,
+                        total,
+                        percent = done as f64 * 100.0 / total as f64,
+                        "Parallel sync progress"
+                    );
+                }
+                Ok::<(), ClientError>(())
+            }));
+        }
+
+        // Join every worker before reporting, so no range is still being written on return
+        let mut failed = 0;
+        let mut first_error = None;
+        for (worker, handle) in handles.into_iter().enumerate() {
+            let message = match handle.await {
+                Ok(Ok(())) => continue,
+                Ok(Err(e)) => e.to_string(),
+                Err(e) => format!("worker panicked: {}", e),
+            };
+            error!(worker, error = %message, "Parallel sync worker failed");
+            failed += 1;
+            first_error.get_or_insert(message);
+        }
+
+        match first_error {
+            Some(first_error) => Err(ClientError::WorkersFailed { failed, workers, first_error }),
+            None => Ok(()),
+        }
+    }
+
     /*
     * Fetches a range of blocks from the Penumbra blockchain.
     *
+    * The range is walked in batches of at most batch_size blocks, capped
+    * at MAX_BATCH_SIZE, and each batch is stored before the next is
+    * fetched. Memory use is therefore bounded by one batch however large
+    * the range is.
+    *
     * @param start_height Starting block height
-    * @param end_height Ending block height
+    * @param end_height Ending block height (inclusive)
     * @param batch_size Number of blocks to fetch in each batch
     */
     pub async fn fetch_blocks(
@@ -129,53 +721,499 @@ impl PenumbraClient {
         start_height: u64,
         end_height: u64,
         batch_size: u64,
-    ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let mut current_height = start_height;
+    ) -> Result<(), ClientError> {
+        for (batch_start, batch_end) in batches(start_height, end_height, batch_size) {
+            if self.batch_commit {
+                self.process_batch(batch_start, batch_end).await;
+            } else {
+                for height in batch_start..=batch_end {
+                    self.process_block_with_retries(height).await;
+                }
+            }
 
-        while current_height <= end_height {
-            let batch_end = std::cmp::min(current_height + batch_size, end_height);
+            if let Err(e) = self.advance_contiguous_watermark().await {
+                warn!(error = %e, "Error advancing contiguous height watermark");
+            }
+        }
 
-            for height in current_height..=batch_end {
-                if let Err(e) = self.process_single_block(height).await {
-                    eprintln!("Error processing block {}: {}", height, e);
-                    tokio::time::sleep(Duration::from_secs(RETRY_DELAY)).await;
-                    continue;
+        Ok(())
+    }
+
+    /*
+    * Advances the persisted last contiguous height watermark.
+    *
+    * Without a recorded watermark, coverage is measured from the lowest
+    * stored block, so existing databases pick up a watermark on upgrade.
+    *
+    * @return The watermark, None if no blocks are stored
+    */
+    async fn advance_contiguous_watermark(&self) -> Result<Option<u64>, ClientError> {
+        let floor = match crate::db::indexer_state::get_last_contiguous_height(&self.db_pool).await? {
+            Some(height) => height,
+            None => match crate::db::blocks::get_lowest_height(&self.db_pool).await? {
+                Some(lowest) => lowest - 1,
+                None => return Ok(None),
+            },
+        };
+
+        let watermark = crate::db::indexer_state::advance_last_contiguous_height(&self.db_pool, floor).await?;
+        Ok(Some(watermark as u64))
+    }
+
+    /*
+    * Re-attempts blocks recorded as failed.
+    *
+    * Each block gets a fresh round of attempts. Recovered blocks are
+    * removed from failed_blocks; blocks that fail again have their
+    * attempts added to the record, and blocks skipped as beyond the
+    * chain tip stay recorded for a later pass.
+    *
+    * @return Number of blocks recovered
+    */
+    pub async fn retry_failed_blocks(&self) -> Result<u64, ClientError> {
+        let failed = crate::db::failed_blocks::get_failed_blocks(&self.db_pool, FAILED_BLOCK_RETRY_BATCH).await?;
+        let mut recovered = 0;
+
+        for block in failed {
+            // Storing the block clears its failed_blocks record
+            if self.process_block_with_retries(block.height as u64).await {
+                recovered += 1;
+            }
+        }
+
+        if recovered > 0 {
+            self.advance_contiguous_watermark().await?;
+        }
+
+        Ok(recovered)
+    }
+
+    /*
+    * Processes a block, retrying up to the configured number of attempts.
+    *
+    * A block that still fails is recorded in failed_blocks rather than
+    * silently skipped.
+    *
+    * @param height Height of the block to process
+    * @return Whether the block was stored; skipped heights were not
+    */
+    async fn process_block_with_retries(&self, height: u64) -> bool {
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+            match self.process_single_block(height).await {
+                Ok(outcome) => return outcome == BlockOutcome::Stored,
+                Err(e) => {
+                    self.handle_block_error(height, &e).await;
+                    if attempts >= self.max_block_attempts {
+                        self.record_failed_block(height, &e, attempts).await;
+                        return false;
+                    }
                 }
             }
+        }
+    }
 
-            current_height = batch_end + 1;
+    /*
+    * Records a block that exhausted its attempts in failed_blocks.
+    */
+    async fn record_failed_block(&self, height: u64, e: &ClientError, attempts: u32) {
+        warn!(height, attempts, error = %e, "Block exhausted its attempts, recording it as failed");
+        let recorded = crate::db::failed_blocks::record_failed_block(
+            &self.db_pool,
+            height as i64,
+            &e.to_string(),
+            attempts as i32,
+        ).await;
+        if let Err(db_error) = recorded {
+            error!(height, error = %db_error, "Error recording failed block");
         }
+    }
 
-        Ok(())
+    /*
+    * Logs a failed block and backs off when the error is transient.
+    */
+    async fn handle_block_error(&self, height: u64, e: &ClientError) {
+        error!(height, error = %e, "Error processing block");
+        self.last_error.record(Some(height), e);
+        // Only back off when the node itself is struggling
+        if e.is_retryable() {
+            tokio::time::sleep(Duration::from_secs(RETRY_DELAY)).await;
+        }
     }
 
     /*
     * Fetches and processes a single block.
+    *
+    * @param height Height of the block to process
+    * @return Whether the block was stored or skipped as beyond the chain tip
+    */
+    #[instrument(skip(self))]
+    async fn process_single_block(&self, height: u64) -> Result<BlockOutcome, ClientError> {
+        let started = Instant::now();
+
+        let block = match self.fetch_checked_block(height).await? {
+            Some(block) => block,
+            None => return Ok(BlockOutcome::Skipped),
+        };
+
+        self.store_fetched_block(height, block).await?;
+        self.record_processed(height, started.elapsed());
+
+        Ok(BlockOutcome::Stored)
+    }
+
+    /*
+    * Fetches and prepares a range of blocks, then commits them in a single
+    * database transaction.
+    *
+    * Blocks that fail to fetch are retried and recorded as failed once they
+    * exhaust their attempts, as in per-block processing. A failed commit
+    * rolls back the whole batch and is retried up to BATCH_COMMIT_ATTEMPTS
+    * times; after that every block of the batch is recorded as failed.
+    *
+    * @param start_height First height of the batch
+    * @param end_height Last height of the batch
+    */
+    async fn process_batch(&self, start_height: u64, end_height: u64) {
+        let started = Instant::now();
+        let mut prepared = Vec::new();
+
+        for height in start_height..=end_height {
+            let mut attempts = 0;
+            loop {
+                attempts += 1;
+                let result = match self.fetch_checked_block(height).await {
+                    Ok(Some(block)) => match self.repair_batch_ancestors(height, &block, &mut prepared).await {
+                        Ok(()) => self.prepare_block(height, block).map(Some),
+                        Err(e) => Err(e),
+                    },
+                    Ok(None) => Ok(None),
+                    Err(e) => Err(e),
+                };
+
+                match result {
+                    Ok(Some(block)) => prepared.push(block),
+                    Ok(None) => {}
+                    Err(e) => {
+                        self.handle_block_error(height, &e).await;
+                        if attempts < self.max_block_attempts {
+                            continue;
+                        }
+                        self.record_failed_block(height, &e, attempts).await;
+                    }
+                }
+                break;
+            }
+        }
+
+        if prepared.is_empty() {
+            return;
+        }
+
+        let mut last_error = None;
+        for attempt in 1..=BATCH_COMMIT_ATTEMPTS {
+            match self.write_blocks(&prepared).await {
+                Ok(()) => {
+                    let per_block = started.elapsed() / prepared.len() as u32;
+                    for block in &prepared {
+                        self.publish_block(block);
+                        self.record_processed(block.block.height as u64, per_block);
+                    }
+                    return;
+                }
+                Err(e) => {
+                    warn!(attempt, start_height, end_height, error = %e, "Batch commit failed and was rolled back");
+                    self.last_error.record(Some(start_height), &e);
+                    if attempt < BATCH_COMMIT_ATTEMPTS {
+                        tokio::time::sleep(Duration::from_secs(RETRY_DELAY)).await;
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        error!(
+            start_height,
+            end_height,
+            blocks = prepared.len(),
+            "Giving up on batch commit, recording its blocks as failed"
+        );
+        if let Some(e) = last_error {
+            for block in &prepared {
+                self.record_failed_block(block.block.height as u64, &e, BATCH_COMMIT_ATTEMPTS).await;
+            }
+        }
+    }
+
+    /*
+    * Fetches a block and repairs any reorganization below it.
+    *
+    * @param height Height of the block to fetch
+    * @return The fetched block, None if the height is beyond the known chain tip
+    */
+    #[instrument(skip(self))]
+    async fn fetch_checked_block(&self, height: u64) -> Result<Option<BlockResponse>, ClientError> {
+        // Guards against garbage heights being fetched forever; 0 means the tip is not known yet
+        let chain_tip = self.chain_tip.load(Ordering::Relaxed);
+        if chain_tip > 0 && height > chain_tip + CHAIN_TIP_MARGIN {
+            warn!(height, chain_tip, "Skipping block beyond the known chain tip");
+            return Ok(None);
+        }
+
+        let block = match self.fetch_block(height).await {
+            Ok(block) => block,
+            Err(e) => {
+                counter!(telemetry::BLOCK_FETCH_ERRORS_TOTAL).increment(1);
+                return Err(e);
+            }
+        };
+
+        self.repair_reorged_ancestors(height, &block).await?;
+        Ok(Some(block))
+    }
+
+    /*
+    * Records the processing time and throughput of a committed block.
+    */
+    fn record_processed(&self, height: u64, elapsed: Duration) {
+        self.last_error.clear();
+        histogram!(telemetry::BLOCK_PROCESSING_SECONDS).record(elapsed.as_secs_f64());
+        debug!(height, elapsed_ms = elapsed.as_millis() as u64, "Block processed");
+
+        let total = self.throughput.record();
+        if total.is_multiple_of(THROUGHPUT_LOG_INTERVAL) {
+            info!(
+                blocks_total = total,
+                blocks_last_minute = self.throughput.blocks_last_minute(),
+                blocks_per_sec = self.throughput.blocks_per_sec(),
+                "Indexing throughput"
+            );
+        }
+
+        if self.validate_decode && total.is_multiple_of(DECODE_SUMMARY_INTERVAL) {
+            let (matched, mismatched, failed) = self.decode_stats.totals();
+            info!(matched, mismatched, failed, "Decode validation summary");
+        }
+    }
+
+    /*
+    * Detects and repairs a chain reorganization below a fetched block.
+    *
+    * Walks back from the block's parent, comparing each expected parent hash
+    * with the hash stored at that height. On mismatch the stored ancestor is
+    * re-fetched and overwritten, up to MAX_REORG_DEPTH blocks. Stops at the
+    * first matching or unindexed ancestor.
+    *
+    * @param height Height of the newly fetched block
+    * @param block The newly fetched block
+    */
+    async fn repair_reorged_ancestors(
+        &self,
+        height: u64,
+        block: &BlockResponse,
+    ) -> Result<(), ClientError> {
+        let mut expected_hash = match &block.result.block.header.last_block_id {
+            Some(parent) => parent.hash.clone(),
+            None => return Ok(()),
+        };
+        let mut ancestor = height.saturating_sub(1);
+        let mut depth = 0;
+
+        while ancestor > 0 {
+            let stored_hash = match crate::db::blocks::get_hash_at_height(&self.db_pool, ancestor as i64).await? {
+                Some(hash) => hash,
+                None => return Ok(()),
+            };
+
+            if stored_hash == expected_hash {
+                return Ok(());
+            }
+
+            if depth == MAX_REORG_DEPTH {
+                warn!(
+                    max_depth = MAX_REORG_DEPTH,
+                    height,
+                    ancestor,
+                    "⚠️ Reorg deeper than the maximum depth, leaving ancestor as is"
+                );
+                return Ok(());
+            }
+
+            warn!(
+                height = ancestor,
+                stored_hash = %stored_hash,
+                expected_hash = %expected_hash,
+                "⚠️ Reorg detected, stored hash does not match expected"
+            );
+
+            let replacement = self.fetch_block(ancestor).await?;
+            expected_hash = match &replacement.result.block.header.last_block_id {
+                Some(parent) => parent.hash.clone(),
+                None => String::new(),
+            };
+
+            self.store_fetched_block(ancestor, replacement).await?;
+
+            if expected_hash.is_empty() {
+                return Ok(());
+            }
+
+            ancestor -= 1;
+            depth += 1;
+        }
+
+        Ok(())
+    }
+
+    /*
+    * Detects and repairs a chain reorganization within an uncommitted batch.
+    *
+    * The parent of a block fetched for a batch is usually part of the same
+    * batch and not stored yet, so the database check misses it. Walks back
+    * through the prepared blocks instead, re-fetching and replacing each one
+    * whose hash differs from the hash its child expects, up to
+    * MAX_REORG_DEPTH blocks. A replaced first block of the batch has its own
+    * stored ancestors checked against the database.
+    *
+    * @param height Height of the newly fetched block
+    * @param block The newly fetched block
+    * @param prepared Blocks prepared so far for the batch, in height order
     */
-    async fn process_single_block(&self, height: u64) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let block = self.fetch_block(height).await?;
+    async fn repair_batch_ancestors(
+        &self,
+        height: u64,
+        block: &BlockResponse,
+        prepared: &mut [PreparedBlock],
+    ) -> Result<(), ClientError> {
+        let mut expected_hash = match &block.result.block.header.last_block_id {
+            Some(parent) => parent.hash.clone(),
+            None => return Ok(()),
+        };
+        let mut ancestor = height.saturating_sub(1);
+        let mut depth = 0;
+
+        while let Some(index) = conflicting_batch_block(prepared, ancestor, &expected_hash) {
+            if depth == MAX_REORG_DEPTH {
+                warn!(
+                    max_depth = MAX_REORG_DEPTH,
+                    height,
+                    ancestor,
+                    "⚠️ Reorg deeper than the maximum depth, leaving batch ancestor as is"
+                );
+                return Ok(());
+            }
+
+            warn!(
+                height = ancestor,
+                batch_hash = %prepared[index].block.hash,
+                expected_hash = %expected_hash,
+                "⚠️ Reorg detected within batch, prepared hash does not match expected"
+            );
 
-        println!("Block {}", height);
-        println!("  Time: {}", block.result.block.header.time);
-        if let Some(last_block) = &block.result.block.header.last_block_id {
-            println!("  Previous block hash: {}", last_block.hash);
+            let replacement = self.fetch_block(ancestor).await?;
+            if index == 0 {
+                self.repair_reorged_ancestors(ancestor, &replacement).await?;
+            }
+            let parent_hash = replacement.result.block.header.last_block_id
+                .as_ref()
+                .map(|parent| parent.hash.clone());
+            prepared[index] = self.prepare_block(ancestor, replacement)?;
+
+            expected_hash = match parent_hash {
+                Some(hash) if ancestor > 1 => hash,
+                _ => return Ok(()),
+            };
+            ancestor -= 1;
+            depth += 1;
         }
 
-        let tx_count = block.result.block.data.txs.as_ref().map_or(0, |txs| txs.len()) as i32;
-        println!("  Transaction count: {}", tx_count);
-        println!("-------------------");
+        Ok(())
+    }
+
+    /*
+    * Stores a fetched block and its transactions atomically.
+    *
+    * Any transactions previously stored for the height are replaced, so
+    * re-syncing a block drops orphaned or legacy synthetic-hash rows.
+    *
+    * @param height Height of the block
+    * @param block Block data returned by the RPC node
+    */
+    #[instrument(skip(self, block))]
+    async fn store_fetched_block(
+        &self,
+        height: u64,
+        block: BlockResponse,
+    ) -> Result<(), ClientError> {
+        let prepared = self.prepare_block(height, block)?;
+        self.write_blocks(std::slice::from_ref(&prepared)).await?;
+        self.publish_block(&prepared);
+        Ok(())
+    }
+
+    /*
+    * Derives the rows stored for a fetched block without touching the database.
+    *
+    * @param height Height of the block
+    * @param block Block data returned by the RPC node
+    * @return The block row and its transaction rows
+    */
+    fn prepare_block(&self, height: u64, block: BlockResponse) -> Result<PreparedBlock, ClientError> {
+        let txs = block.result.block.data.transactions();
+        let tx_count = txs.len() as i32;
+        let empty_entries = block.result.block.data.empty_transaction_count();
+        if empty_entries > 0 {
+            warn!(height, empty_entries, "Skipping empty transaction entries");
+        }
+        debug!(
+            height,
+            time = %block.result.block.header.time,
+            previous_block_hash = ?block.result.block.header.last_block_id.as_ref().map(|id| &id.hash),
+            tx_count,
+            "Storing block"
+        );
+
+        if self.verify_block_hashes {
+            self.verify_block_hash(height, &block);
+        }
 
         let result_json = serde_json::to_value(&block.result)?;
+        let block_size_bytes = serde_json::to_vec(&result_json)?.len() as i64;
 
-        let mut total_burn = 0.0;
-        if let Some(txs) = &block.result.block.data.txs {
-            for tx_data in txs.iter() {
-                if let Some(burn) = self.extract_burn_amount(tx_data) {
-                    total_burn += burn;
-                }
+        let mut total_burn: u128 = 0;
+        for tx_data in &txs {
+            if let Some(burn) = self.extract_burn_amount(tx_data) {
+                total_burn += burn;
             }
         }
 
+        let mut transactions = Vec::with_capacity(txs.len());
+        for (i, tx_data) in txs.iter().enumerate() {
+            // Undecodable data cannot be hashed, so fall back to a position-based key
+            let tx_hash = decode::transaction_hash(tx_data)
+                .unwrap_or_else(|_| format!("{}_{}", block.result.block_id.hash, i));
+            let action = self.analyze_transaction(tx_data);
+
+            if self.validate_decode {
+                self.validate_decoded_transaction(height, i, tx_data, &tx_hash, &action);
+            }
+
+            transactions.push(PreparedTransaction {
+                tx_hash,
+                action,
+                data: tx_data.to_string(),
+            });
+        }
+
+        let mut action_counts: BTreeMap<&str, i64> = BTreeMap::new();
+        for tx in &transactions {
+            *action_counts.entry(tx.action.action_type.as_str()).or_default() += 1;
+        }
+        let has_swaps = decode::SWAP_ACTIONS.iter().any(|action| action_counts.contains_key(action));
+        let has_transfers = decode::TRANSFER_ACTIONS.iter().any(|action| action_counts.contains_key(action));
+
         let stored_block = StoredBlock {
             height: height as i64,
             time: block.result.block.header.time,
@@ -183,60 +1221,168 @@ impl PenumbraClient {
             proposer_address: block.result.block.header.proposer_address.clone(),
             tx_count,
             previous_block_hash: block.result.block.header.last_block_id.map(|id| id.hash),
-            burn_amount: total_burn,
-            data: result_json,
+            burn_amount: TokenAmount(total_burn),
+            block_size_bytes: Some(block_size_bytes),
+            // Derived fields above are still computed from the payload when it is not kept
+            data: if self.store_raw_block_data { result_json } else { serde_json::Value::Null },
+            action_counts: Some(serde_json::json!(action_counts)),
+            has_swaps,
+            has_transfers,
             created_at: Utc::now(),
+            updated_at: None,
         };
 
-        crate::db::blocks::store_block(&self.db_pool, stored_block.clone()).await?;
+        Ok(PreparedBlock { block: stored_block, transactions })
+    }
 
-        if let Some(txs) = &block.result.block.data.txs {
-            for (i, tx_data) in txs.iter().enumerate() {
-                let tx_hash = format!("{}_{}", block.result.block_id.hash, i);
+    /*
+    * Writes prepared blocks and their transactions in a single database
+    * transaction.
+    *
+    * Either every block commits with all of its transactions or, on any
+    * error, the whole write is rolled back. A stored block's failed_blocks
+    * record is removed in the same transaction, so a block recovered by
+    * normal sync or a resync is no longer reported as failed.
+    *
+    * @param blocks Prepared blocks to store
+    */
+    #[instrument(skip_all, fields(blocks = blocks.len()))]
+    async fn write_blocks(&self, blocks: &[PreparedBlock]) -> Result<(), ClientError> {
+        let mut db_tx = self.db_pool.begin().await?;
+        let mut overwritten = 0;
 
-                // Extract transaction type and amount
-                let (action_type, amount) = self.analyze_transaction(tx_data);
+        for prepared in blocks {
+            let block = &prepared.block;
+            if crate::db::blocks::store_block(&mut db_tx, block, self.compress_block_data).await? {
+                debug!(height = block.height, hash = %block.hash, "Overwrote a stored block");
+                overwritten += 1;
+            }
+            crate::db::transactions::delete_transactions_by_block_height(&mut db_tx, block.height).await?;
+            if crate::db::failed_blocks::delete_failed_block(&mut db_tx, block.height).await? {
+                info!(height = block.height, "Stored a block recorded as failed, clearing its record");
+            }
 
+            for tx in &prepared.transactions {
                 crate::db::transactions::store_transaction(
-                    &self.db_pool,
-                    &tx_hash,
-                    height as i64,
-                    block.result.block.header.time,
-                    &action_type,
-                    amount,
-                    tx_data
+                    &mut db_tx,
+                    &tx.tx_hash,
+                    block.height,
+                    block.time,
+                    &tx.action.action_type,
+                    tx.action.amount.map(TokenAmount),
+                    tx.action.denom.as_deref(),
+                    tx.action.fee_amount.map(TokenAmount),
+                    tx.action.fee_denom.as_deref(),
+                    &tx.data,
+                    tx.action.metadata.as_ref(),
                 ).await?;
             }
         }
 
+        db_tx.commit().await?;
+        // Counted only once committed so rolled back attempts are not counted
+        counter!(telemetry::BLOCKS_OVERWRITTEN_TOTAL).increment(overwritten);
         Ok(())
     }
 
     /*
-    * Analyzes a transaction to determine its type and amount.
+    * Records metrics for a committed block and publishes it and its
+    * transactions to subscribers.
+    */
+    fn publish_block(&self, prepared: &PreparedBlock) {
+        counter!(telemetry::BLOCKS_INDEXED_TOTAL).increment(1);
+        counter!(telemetry::TRANSACTIONS_INDEXED_TOTAL).increment(prepared.transactions.len() as u64);
+
+        // Sending only fails when no WebSocket clients are subscribed
+        let _ = self.block_tx.send(prepared.block.to_summary());
+
+        if let Some(tx_feed) = &self.tx_feed {
+            // Skip building summaries while no SSE clients are listening
+            if tx_feed.receiver_count() > 0 {
+                for tx in &prepared.transactions {
+                    let _ = tx_feed.send(TransactionSummary {
+                        tx_hash: tx.tx_hash.clone(),
+                        block_height: prepared.block.height,
+                        action_type: tx.action.action_type.clone(),
+                        amount: tx.action.amount.map(TokenAmount),
+                        denom: tx.action.denom.clone(),
+                        fee_amount: tx.action.fee_amount.map(TokenAmount),
+                        fee_denom: tx.action.fee_denom.clone(),
+                        metadata: tx.action.metadata.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    /*
+    * Analyzes a transaction to determine its type, amount, fee and metadata.
     *
-    * @param tx_data Raw transaction data
-    * @return Tuple of (action_type, optional_amount)
+    * Transactions that fail to decode are stored as "unknown".
+    *
+    * @param tx_data Raw base64-encoded transaction data
+    * @return Classification of the transaction
     */
-    fn analyze_transaction(&self, tx_data: &str) -> (String, Option<f64>) {
-        // Here you would implement the logic to decode the transaction data
-        // and determine the type and amount based on your chain's specifics
+    fn analyze_transaction(&self, tx_data: &str) -> ActionInfo {
+        match decode::decode_transaction(tx_data) {
+            Ok(tx) => decode::classify_transaction(&tx),
+            Err(_) => ActionInfo::unknown(),
+        }
+    }
 
-        // For now, returning placeholder values
-        if tx_data.contains("spend") {
-            ("spend".to_string(), Some(3.0))
-        } else {
-            ("not yet supported act...".to_string(), None)
+    /*
+    * Checks the block ID reported by the node against the hash of the
+    * block's header.
+    *
+    * Mismatches and headers that cannot be hashed are logged, and
+    * mismatches counted; the block is stored regardless.
+    */
+    fn verify_block_hash(&self, height: u64, block: &BlockResponse) {
+        let reported = &block.result.block_id.hash;
+        match verify::header_hash(&block.result.block.header) {
+            Ok(computed) if computed.eq_ignore_ascii_case(reported) => {}
+            Ok(computed) => {
+                warn!(height, reported = %reported, computed = %computed, "Block hash does not match its header");
+                counter!(telemetry::BLOCK_HASH_MISMATCHES_TOTAL).increment(1);
+            }
+            Err(e) => warn!(height, error = %e, "Could not compute block header hash"),
         }
     }
 
+    /*
+    * Validates a transaction's stored values against its decoded contents.
+    *
+    * Discrepancies and decode failures are logged and counted; the
+    * transaction is stored regardless.
+    */
+    fn validate_decoded_transaction(
+        &self,
+        height: u64,
+        index: usize,
+        tx_data: &str,
+        tx_hash: &str,
+        action: &ActionInfo,
+    ) {
+        let outcome = validation::validate_transaction(tx_data, tx_hash, action);
+        match &outcome {
+            DecodeOutcome::Match => {}
+            DecodeOutcome::Mismatch(discrepancies) => {
+                warn!(height, index, tx_hash, ?discrepancies, "Decode validation mismatch");
+            }
+            DecodeOutcome::Failed(error) => {
+                warn!(height, index, tx_hash, error = %error, "Decode validation failed, transaction stored as unknown");
+            }
+        }
+        self.decode_stats.record(&outcome);
+    }
+
     /*
     * Extracts the burn amount from a transaction.
     *
     * @param tx_data Raw transaction data
-    * @return Optional burn amount
+    * @return Optional burn amount in base units
     */
-    fn extract_burn_amount(&self, tx_data: &str) -> Option<f64> {
+    fn extract_burn_amount(&self, tx_data: &str) -> Option<u128> {
         // Here you would implement the logic to decode the transaction data
         // and extract any burn amount based on your chain's specifics
 
@@ -247,7 +1393,334 @@ impl PenumbraClient {
     /*
     * Fetches a single block from the Penumbra blockchain.
     */
-    async fn fetch_block(&self, height: u64) -> Result<BlockResponse, Box<dyn Error + Send + Sync>> {
+    async fn fetch_block(&self, height: u64) -> Result<BlockResponse, ClientError> {
         self.rpc_client.get_block(height).await
     }
 }
+
+/*
+* Parses a configured batch size into the range sync honors.
+*
+* Values below 1, including negative ones, and values above
+* MAX_BATCH_SIZE are clamped with a warning. A missing or non-numeric
+* value falls back to DEFAULT_BATCH_SIZE.
+*
+* @param raw Configured batch size, if any
+* @return Batch size between 1 and MAX_BATCH_SIZE
+*/
+pub fn parse_batch_size(raw: Option<&str>) -> u64 {
+    let Some(raw) = raw else {
+        info!("BATCH_SIZE not set, using default: {}", DEFAULT_BATCH_SIZE);
+        return DEFAULT_BATCH_SIZE;
+    };
+    let Ok(requested) = raw.trim().parse::<i64>() else {
+        warn!(value = raw, "BATCH_SIZE is not a number, using default: {}", DEFAULT_BATCH_SIZE);
+        return DEFAULT_BATCH_SIZE;
+    };
+
+    let batch_size = requested.clamp(1, MAX_BATCH_SIZE as i64) as u64;
+    if batch_size as i64 != requested {
+        warn!(requested, batch_size, "BATCH_SIZE must be between 1 and {}, clamped", MAX_BATCH_SIZE);
+    }
+    batch_size
+}
+
+/*
+* Finds the prepared block of a batch at a height whose hash differs from
+* the hash its child expects.
+*
+* @param prepared Blocks prepared for the batch, in height order
+* @param height Height of the expected parent
+* @param expected_hash Parent hash recorded by the child block
+* @return Index of the conflicting block, None if the batch has no block at
+*         the height or its hash matches
+*/
+fn conflicting_batch_block(prepared: &[PreparedBlock], height: u64, expected_hash: &str) -> Option<usize> {
+    let index = prepared.iter().rposition(|prepared| prepared.block.height as u64 == height)?;
+    (prepared[index].block.hash != expected_hash).then_some(index)
+}
+
+/*
+* Splits a height range into consecutive batches.
+*
+* Every batch holds between 1 and MAX_BATCH_SIZE heights, so walking a
+* range of any size never holds more than one batch.
+*
+* @param start_height First height of the range
+* @param end_height Last height of the range (inclusive)
+* @param batch_size Requested number of blocks per batch
+* @return Iterator over the (first, last) heights of each batch, inclusive
+*/
+fn batches(start_height: u64, end_height: u64, batch_size: u64) -> impl Iterator<Item = (u64, u64)> {
+    let mut next = (start_height <= end_height).then_some(start_height);
+    std::iter::from_fn(move || {
+        let start = next?;
+        let end = batch_end(start, end_height, batch_size);
+        next = end.checked_add(1).filter(|height| *height <= end_height);
+        Some((start, end))
+    })
+}
+
+/*
+* Caps the parallel sync workers so the shared pool keeps connections free.
+*
+* @param requested Requested number of workers
+* @param max_connections Size of the database pool
+* @return Number of workers to run, at least one
+*/
+fn max_sync_workers(requested: usize, max_connections: usize) -> usize {
+    requested
+        .min(max_connections.saturating_sub(RESERVED_DB_CONNECTIONS))
+        .max(1)
+}
+
+/*
+* Returns the last height of the batch starting at a height.
+*
+* Batches hold at least one and at most MAX_BATCH_SIZE blocks and never
+* extend past the end of the range.
+*
+* @param start First height of the batch
+* @param end_height Last height of the range (inclusive)
+* @param batch_size Requested number of blocks per batch
+* @return Last height of the batch (inclusive)
+*/
+fn batch_end(start: u64, end_height: u64, batch_size: u64) -> u64 {
+    let batch_size = batch_size.clamp(1, MAX_BATCH_SIZE);
+    start.saturating_add(batch_size - 1).min(end_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock_node::{self, MockNode};
+
+    /* Connects a client to a mock node; the database pool never connects */
+    async fn client(node: &MockNode, status_cache_ttl: Duration) -> PenumbraClient {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy("postgres://localhost/unused")
+            .unwrap();
+        client_with_db(node, pool, status_cache_ttl).await
+    }
+
+    /* Connects a client to a mock node and the given database */
+    async fn client_with_db(node: &MockNode, pool: Pool<Postgres>, status_cache_ttl: Duration) -> PenumbraClient {
+        PenumbraClient::connect(
+            &node.url,
+            pool,
+            broadcast::channel(1).0,
+            RpcTimeouts::default(),
+            1,
+            RpcConnectionPool::default(),
+            status_cache_ttl,
+            false,
+            ThroughputMeter::new(),
+        ).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn status_within_the_ttl_is_served_from_the_cache() {
+        let node = MockNode::start(|_| (200, mock_node::status_body(100))).await;
+        let client = client(&node, Duration::from_secs(60)).await;
+
+        assert_eq!(client.get_status().await.unwrap().chain_height().unwrap(), 100);
+        assert_eq!(client.get_status().await.unwrap().chain_height().unwrap(), 100);
+        assert_eq!(node.requests(), 1);
+
+        client.get_status_uncached().await.unwrap();
+        assert_eq!(node.requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn status_older_than_the_ttl_is_refetched() {
+        let node = MockNode::start(|_| (200, mock_node::status_body(100))).await;
+        let client = client(&node, Duration::from_millis(20)).await;
+
+        client.get_status().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        client.get_status().await.unwrap();
+        assert_eq!(node.requests(), 2);
+    }
+
+    /* Returns a block response at height 5 carrying the given `txs` value */
+    fn block_with_txs(txs: serde_json::Value) -> BlockResponse {
+        let mut body: serde_json::Value = serde_json::from_str(&mock_node::block_body(5, "HASH")).unwrap();
+        body["result"]["block"]["data"]["txs"] = txs;
+        serde_json::from_value(body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn blocks_with_null_or_empty_txs_store_no_transactions() {
+        let node = MockNode::start(|_| (200, mock_node::status_body(100))).await;
+        let client = client(&node, Duration::ZERO).await;
+
+        for txs in [serde_json::Value::Null, serde_json::json!([])] {
+            let prepared = client.prepare_block(5, block_with_txs(txs)).unwrap();
+            assert_eq!(prepared.block.tx_count, 0);
+            assert!(prepared.transactions.is_empty());
+            assert!(!prepared.block.has_swaps && !prepared.block.has_transfers);
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_transaction_entries_are_not_stored() {
+        let node = MockNode::start(|_| (200, mock_node::status_body(100))).await;
+        let client = client(&node, Duration::ZERO).await;
+
+        let prepared = client.prepare_block(5, block_with_txs(serde_json::json!(["", "  ", "abc"]))).unwrap();
+        assert_eq!(prepared.block.tx_count, 1);
+        assert_eq!(prepared.transactions.len(), 1);
+        assert_eq!(prepared.transactions[0].data, "abc");
+    }
+
+    fn prepared_block(height: i64, hash: &str) -> PreparedBlock {
+        PreparedBlock {
+            block: StoredBlock {
+                height,
+                time: Utc::now(),
+                hash: hash.to_string(),
+                proposer_address: "PROPOSER".to_string(),
+                tx_count: 0,
+                previous_block_hash: None,
+                burn_amount: TokenAmount::default(),
+                block_size_bytes: None,
+                data: serde_json::Value::Null,
+                action_counts: None,
+                has_swaps: false,
+                has_transfers: false,
+                created_at: Utc::now(),
+                updated_at: None,
+            },
+            transactions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn batch_parent_with_the_expected_hash_is_not_a_conflict() {
+        let prepared = vec![prepared_block(10, "AAA"), prepared_block(11, "BBB")];
+        assert_eq!(conflicting_batch_block(&prepared, 11, "BBB"), None);
+    }
+
+    #[test]
+    fn batch_parent_with_a_different_hash_is_a_conflict() {
+        let prepared = vec![prepared_block(10, "AAA"), prepared_block(11, "BBB")];
+        assert_eq!(conflicting_batch_block(&prepared, 11, "CCC"), Some(1));
+        assert_eq!(conflicting_batch_block(&prepared, 10, "CCC"), Some(0));
+    }
+
+    #[test]
+    fn parent_outside_the_batch_is_left_to_the_database_check() {
+        let prepared = vec![prepared_block(10, "AAA")];
+        assert_eq!(conflicting_batch_block(&prepared, 9, "CCC"), None);
+        assert_eq!(conflicting_batch_block(&[], 9, "CCC"), None);
+    }
+
+    /* Walks a range's batches, checking each is contiguous and within bounds */
+    fn assert_batches_cover(start_height: u64, end_height: u64, batch_size: u64, max_len: u64) -> u64 {
+        let mut expected_start = start_height;
+        let mut count = 0;
+        for (batch_start, batch_end) in batches(start_height, end_height, batch_size) {
+            assert_eq!(batch_start, expected_start);
+            assert!(batch_start <= batch_end);
+            assert!(batch_end - batch_start < max_len, "batch {}..={} is too large", batch_start, batch_end);
+            expected_start = batch_end.wrapping_add(1);
+            count += 1;
+        }
+        assert_eq!(expected_start, end_height.wrapping_add(1));
+        count
+    }
+
+    #[test]
+    fn a_large_range_is_walked_in_bounded_batches() {
+        assert_eq!(assert_batches_cover(1, 5_000_000, 1_000, 1_000), 5_000);
+        assert_eq!(assert_batches_cover(7, 2_006, 1_000, 1_000), 2);
+        assert_eq!(assert_batches_cover(7, 2_007, 1_000, 1_000), 3);
+    }
+
+    #[test]
+    fn batches_are_capped_whatever_the_requested_size() {
+        assert_eq!(assert_batches_cover(1, 100_000, u64::MAX, MAX_BATCH_SIZE), 10);
+        assert_eq!(assert_batches_cover(1, 5, 0, 1), 5);
+    }
+
+    #[test]
+    fn batches_handle_empty_ranges_and_the_top_height() {
+        assert_eq!(batches(10, 9, 100).count(), 0);
+        assert_eq!(batches(5, 5, 100).collect::<Vec<_>>(), vec![(5, 5)]);
+        assert_eq!(assert_batches_cover(u64::MAX - 25, u64::MAX, 10, 10), 3);
+    }
+
+    #[test]
+    fn sync_workers_leave_connections_for_the_api() {
+        assert_eq!(max_sync_workers(8, 5), 3);
+        assert_eq!(max_sync_workers(2, 5), 2);
+        assert_eq!(max_sync_workers(3, 20), 3);
+    }
+
+    #[test]
+    fn small_pools_still_get_one_sync_worker() {
+        assert_eq!(max_sync_workers(4, 2), 1);
+        assert_eq!(max_sync_workers(4, 1), 1);
+        assert_eq!(max_sync_workers(0, 10), 1);
+    }
+
+    #[test]
+    fn parse_batch_size_defaults_when_unset_or_not_a_number() {
+        assert_eq!(parse_batch_size(None), DEFAULT_BATCH_SIZE);
+        assert_eq!(parse_batch_size(Some("abc")), DEFAULT_BATCH_SIZE);
+        assert_eq!(parse_batch_size(Some("")), DEFAULT_BATCH_SIZE);
+        assert_eq!(parse_batch_size(Some("1.5")), DEFAULT_BATCH_SIZE);
+    }
+
+    #[test]
+    fn parse_batch_size_clamps_zero_and_negative_values_to_one() {
+        assert_eq!(parse_batch_size(Some("0")), 1);
+        assert_eq!(parse_batch_size(Some("-1")), 1);
+        assert_eq!(parse_batch_size(Some("-500")), 1);
+    }
+
+    #[test]
+    fn parse_batch_size_clamps_oversized_values() {
+        assert_eq!(parse_batch_size(Some("10001")), MAX_BATCH_SIZE);
+        assert_eq!(parse_batch_size(Some("1000000")), MAX_BATCH_SIZE);
+        assert_eq!(MAX_BATCH_SIZE, 10_000);
+    }
+
+    #[test]
+    fn parse_batch_size_keeps_values_in_range() {
+        assert_eq!(parse_batch_size(Some("1")), 1);
+        assert_eq!(parse_batch_size(Some(" 250 ")), 250);
+        assert_eq!(parse_batch_size(Some("10000")), MAX_BATCH_SIZE);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL database in TEST_DATABASE_URL"]
+    async fn storing_a_failed_block_clears_its_record() {
+        // Heights far above any real chain, so the test never touches indexed blocks
+        const RECOVERED: i64 = 9_000_000_000_100;
+        const STILL_FAILED: i64 = 9_000_000_000_101;
+
+        let pool = crate::db::init_db(&std::env::var("TEST_DATABASE_URL").unwrap()).await.unwrap();
+        let node = MockNode::start(|_| (200, mock_node::status_body(100))).await;
+        let client = client_with_db(&node, pool.clone(), Duration::ZERO).await;
+
+        for height in [RECOVERED, STILL_FAILED] {
+            crate::db::failed_blocks::record_failed_block(&pool, height, "timed out", 3).await.unwrap();
+        }
+        client.write_blocks(&[prepared_block(RECOVERED, "RECOVERED")]).await.unwrap();
+
+        let remaining = crate::db::failed_blocks::count_failed_blocks_in_range(&pool, RECOVERED, STILL_FAILED).await.unwrap();
+        let stored = crate::db::blocks::get_hash_and_tx_count_at_height(&pool, RECOVERED).await.unwrap();
+
+        sqlx::query("DELETE FROM failed_blocks WHERE height BETWEEN $1 AND $2")
+            .bind(RECOVERED)
+            .bind(STILL_FAILED)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM blocks WHERE height = $1").bind(RECOVERED).execute(&pool).await.unwrap();
+
+        assert_eq!(remaining, 1);
+        assert_eq!(stored.map(|(hash, _)| hash).as_deref(), Some("RECOVERED"));
+    }
+}
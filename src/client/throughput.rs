@@ -0,0 +1,86 @@
+/*
+* Indexing throughput tracking.
+*
+* Records the time of every stored block in a rolling one-minute window
+* so the current indexing rate can be logged and served by the API.
+*/
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/* Length of the rolling window over which throughput is measured */
+const THROUGHPUT_WINDOW: Duration = Duration::from_secs(60);
+
+/*
+* Rolling window of recent block store events.
+*/
+#[derive(Debug, Default)]
+struct ThroughputWindow {
+    stored_at: VecDeque<Instant>,
+    total: u64,
+}
+
+impl ThroughputWindow {
+    /*
+    * Drops events that have fallen out of the window.
+    */
+    fn prune(&mut self, now: Instant) {
+        while let Some(&oldest) = self.stored_at.front() {
+            if now.duration_since(oldest) <= THROUGHPUT_WINDOW {
+                break;
+            }
+            self.stored_at.pop_front();
+        }
+    }
+}
+
+/*
+* Shared meter of blocks stored over the last minute.
+*
+* Cloning shares the same window, so the indexer and the API can hold
+* their own handles.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct ThroughputMeter {
+    window: Arc<Mutex<ThroughputWindow>>,
+}
+
+impl ThroughputMeter {
+    /*
+    * Creates an empty throughput meter.
+    */
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /*
+    * Records a stored block.
+    *
+    * @return Total number of blocks recorded since startup
+    */
+    pub fn record(&self) -> u64 {
+        let now = Instant::now();
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+        window.prune(now);
+        window.stored_at.push_back(now);
+        window.total += 1;
+        window.total
+    }
+
+    /*
+    * Counts the blocks stored within the last minute.
+    */
+    pub fn blocks_last_minute(&self) -> u64 {
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+        window.prune(Instant::now());
+        window.stored_at.len() as u64
+    }
+
+    /*
+    * Average number of blocks stored per second over the last minute.
+    */
+    pub fn blocks_per_sec(&self) -> f64 {
+        self.blocks_last_minute() as f64 / THROUGHPUT_WINDOW.as_secs_f64()
+    }
+}
@@ -0,0 +1,117 @@
+/*
+* Validation of the protobuf transaction decode path.
+*
+* With VALIDATE_DECODE enabled, every stored transaction is decoded again
+* and the stored hash and action type are checked against what the
+* decoded transaction implies. Discrepancies are only reported, never
+* fatal, so the decoder can be rolled out safely on live data.
+*/
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::client::decode::{self, ActionInfo, IBC_TRANSFER_ACTION};
+
+/*
+* Result of validating a single transaction.
+*/
+#[derive(Debug)]
+pub enum DecodeOutcome {
+    /// The stored values agree with the decoded transaction
+    Match,
+
+    /// The transaction decoded, but stored values disagree with it
+    Mismatch(Vec<String>),
+
+    /// The transaction could not be decoded
+    Failed(String),
+}
+
+/*
+* Running totals of decode validation outcomes.
+*/
+#[derive(Debug, Default)]
+pub struct DecodeValidationStats {
+    matched: AtomicU64,
+    mismatched: AtomicU64,
+    failed: AtomicU64,
+}
+
+impl DecodeValidationStats {
+    /*
+    * Counts a validation outcome.
+    */
+    pub fn record(&self, outcome: &DecodeOutcome) {
+        let counter = match outcome {
+            DecodeOutcome::Match => &self.matched,
+            DecodeOutcome::Mismatch(_) => &self.mismatched,
+            DecodeOutcome::Failed(_) => &self.failed,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /*
+    * Returns the matched, mismatched and failed totals.
+    */
+    pub fn totals(&self) -> (u64, u64, u64) {
+        (
+            self.matched.load(Ordering::Relaxed),
+            self.mismatched.load(Ordering::Relaxed),
+            self.failed.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/*
+* Checks a stored transaction against its decoded contents.
+*
+* @param tx_data Raw base64-encoded transaction data
+* @param stored_hash Hash the transaction is stored under
+* @param stored Classification the transaction is stored with
+* @return Whether the stored values match the decoded transaction
+*/
+pub fn validate_transaction(tx_data: &str, stored_hash: &str, stored: &ActionInfo) -> DecodeOutcome {
+    let tx = match decode::decode_transaction(tx_data) {
+        Ok(tx) => tx,
+        Err(e) => return DecodeOutcome::Failed(e.to_string()),
+    };
+
+    let mut discrepancies = Vec::new();
+
+    if let Ok(hash) = decode::transaction_hash(tx_data) {
+        if hash != stored_hash {
+            discrepancies.push(format!("stored hash {} differs from derived hash {}", stored_hash, hash));
+        }
+    }
+
+    let decoded_types: Vec<&str> = tx.body
+        .iter()
+        .flat_map(|body| body.actions.iter())
+        .filter_map(|action| action.kind.as_ref())
+        .map(decode::action_name)
+        .collect();
+    if decoded_types.is_empty() {
+        discrepancies.push("transaction decoded without any actions".to_string());
+    }
+
+    let expected = decode::classify_transaction(&tx).action_type;
+    if stored.action_type != expected {
+        discrepancies.push(format!("stored action type {} differs from derived {}", stored.action_type, expected));
+    }
+
+    let backed_by_action = if stored.action_type == IBC_TRANSFER_ACTION {
+        decoded_types.iter().any(|name| matches!(*name, "ibc_relay" | "ics20_withdrawal"))
+    } else {
+        decoded_types.contains(&stored.action_type.as_str())
+    };
+    if !decoded_types.is_empty() && !backed_by_action {
+        discrepancies.push(format!(
+            "stored action type {} is not among decoded actions {:?}",
+            stored.action_type, decoded_types
+        ));
+    }
+
+    if discrepancies.is_empty() {
+        DecodeOutcome::Match
+    } else {
+        DecodeOutcome::Mismatch(discrepancies)
+    }
+}
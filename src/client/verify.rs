@@ -0,0 +1,227 @@
+/*
+* Block header hash verification.
+*
+* Recomputes the Tendermint block hash from the header fields returned by
+* the RPC node, so blocks can be checked against the block_id hash the
+* node reported. The hash is the simple Merkle root of the header fields,
+* each encoded as protobuf the way CometBFT's Header.Hash() does.
+*/
+
+use prost::Message;
+use sha2::{Digest, Sha256};
+use crate::client::error::ClientError;
+use crate::client::models::{BlockHeader, BlockId};
+
+/* tendermint.version.Consensus */
+#[derive(Clone, PartialEq, Message)]
+struct Consensus {
+    #[prost(uint64, tag = "1")]
+    block: u64,
+    #[prost(uint64, tag = "2")]
+    app: u64,
+}
+
+/* google.protobuf.Timestamp */
+#[derive(Clone, PartialEq, Message)]
+struct Timestamp {
+    #[prost(int64, tag = "1")]
+    seconds: i64,
+    #[prost(int32, tag = "2")]
+    nanos: i32,
+}
+
+/* tendermint.types.PartSetHeader */
+#[derive(Clone, PartialEq, Message)]
+struct PartSetHeader {
+    #[prost(uint32, tag = "1")]
+    total: u32,
+    #[prost(bytes = "vec", tag = "2")]
+    hash: Vec<u8>,
+}
+
+/* tendermint.types.BlockID, whose part set header is always encoded */
+#[derive(Clone, PartialEq, Message)]
+struct BlockIdProto {
+    #[prost(bytes = "vec", tag = "1")]
+    hash: Vec<u8>,
+    #[prost(message, required, tag = "2")]
+    part_set_header: PartSetHeader,
+}
+
+/* google.protobuf.StringValue */
+#[derive(Clone, PartialEq, Message)]
+struct StringValue {
+    #[prost(string, tag = "1")]
+    value: String,
+}
+
+/* google.protobuf.Int64Value */
+#[derive(Clone, PartialEq, Message)]
+struct Int64Value {
+    #[prost(int64, tag = "1")]
+    value: i64,
+}
+
+/* google.protobuf.BytesValue */
+#[derive(Clone, PartialEq, Message)]
+struct BytesValue {
+    #[prost(bytes = "vec", tag = "1")]
+    value: Vec<u8>,
+}
+
+/*
+* Computes the hash of a block header.
+*
+* @param header Header as returned by the RPC node
+* @return The uppercase hex block hash, or an error when header fields
+*         needed for the hash are missing or malformed
+*/
+pub fn header_hash(header: &BlockHeader) -> Result<String, ClientError> {
+    let version = header.version.as_ref()
+        .ok_or_else(|| missing_field("version"))?;
+    let version = Consensus {
+        block: parse_number(&version.block, "version.block")?,
+        // Nodes omit a zero app version
+        app: if version.app.is_empty() { 0 } else { parse_number(&version.app, "version.app")? },
+    };
+
+    let time = Timestamp {
+        seconds: header.time.timestamp(),
+        nanos: header.time.timestamp_subsec_nanos() as i32,
+    };
+
+    let hash_field = |value: &Option<String>, name: &str| -> Result<Vec<u8>, ClientError> {
+        let value = value.as_ref().ok_or_else(|| missing_field(name))?;
+        Ok(BytesValue { value: decode_hex(value, name)? }.encode_to_vec())
+    };
+
+    let fields = vec![
+        version.encode_to_vec(),
+        StringValue { value: header.chain_id.clone().ok_or_else(|| missing_field("chain_id"))? }.encode_to_vec(),
+        Int64Value { value: parse_number(&header.height, "height")? }.encode_to_vec(),
+        time.encode_to_vec(),
+        encode_block_id(header.last_block_id.as_ref())?,
+        hash_field(&header.last_commit_hash, "last_commit_hash")?,
+        hash_field(&header.data_hash, "data_hash")?,
+        hash_field(&header.validators_hash, "validators_hash")?,
+        hash_field(&header.next_validators_hash, "next_validators_hash")?,
+        hash_field(&header.consensus_hash, "consensus_hash")?,
+        hash_field(&header.app_hash, "app_hash")?,
+        hash_field(&header.last_results_hash, "last_results_hash")?,
+        hash_field(&header.evidence_hash, "evidence_hash")?,
+        BytesValue { value: decode_hex(&header.proposer_address, "proposer_address")? }.encode_to_vec(),
+    ];
+
+    Ok(hex::encode_upper(merkle_root(&fields)))
+}
+
+/*
+* Encodes the previous block ID, empty for the first block of a chain.
+*/
+fn encode_block_id(block_id: Option<&BlockId>) -> Result<Vec<u8>, ClientError> {
+    let Some(block_id) = block_id else {
+        return Ok(BlockIdProto::default().encode_to_vec());
+    };
+
+    let part_set_header = match &block_id.parts {
+        Some(parts) => PartSetHeader {
+            total: parts.total,
+            hash: decode_hex(&parts.hash, "last_block_id.parts.hash")?,
+        },
+        None => PartSetHeader::default(),
+    };
+
+    Ok(BlockIdProto {
+        hash: decode_hex(&block_id.hash, "last_block_id.hash")?,
+        part_set_header,
+    }.encode_to_vec())
+}
+
+/*
+* Computes the RFC 6962 style Merkle root used by Tendermint.
+*
+* Leaves are hashed with a 0x00 prefix and inner nodes with 0x01, and the
+* items are split at the largest power of two below their count.
+*/
+fn merkle_root(items: &[Vec<u8>]) -> Vec<u8> {
+    match items.len() {
+        0 => Sha256::digest([]).to_vec(),
+        1 => Sha256::new().chain_update([0x00]).chain_update(&items[0]).finalize().to_vec(),
+        len => {
+            let split = len.next_power_of_two() / 2;
+            let left = merkle_root(&items[..split]);
+            let right = merkle_root(&items[split..]);
+            Sha256::new().chain_update([0x01]).chain_update(left).chain_update(right).finalize().to_vec()
+        }
+    }
+}
+
+fn decode_hex(value: &str, field: &str) -> Result<Vec<u8>, ClientError> {
+    hex::decode(value).map_err(|e| ClientError::Parse(format!("Invalid {} '{}': {}", field, value, e)))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str, field: &str) -> Result<T, ClientError> {
+    value.parse().map_err(|_| ClientError::Parse(format!("Invalid {} '{}'", field, value)))
+}
+
+fn missing_field(field: &str) -> ClientError {
+    ClientError::Parse(format!("Block header is missing {}", field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sha256_hex(value: &str) -> String {
+        hex::encode_upper(Sha256::digest(value.as_bytes()))
+    }
+
+    /* The header of CometBFT's TestHeaderHash, whose expected hash is published with it */
+    fn known_header() -> BlockHeader {
+        let zero_hash = "00".repeat(32);
+        serde_json::from_value(serde_json::json!({
+            "version": {"block": "1", "app": "2"},
+            "chain_id": "chainId",
+            "height": "3",
+            "time": "2019-10-13T16:14:44Z",
+            "last_block_id": {"hash": zero_hash, "parts": {"total": 6, "hash": zero_hash}},
+            "last_commit_hash": sha256_hex("last_commit_hash"),
+            "data_hash": sha256_hex("data_hash"),
+            "validators_hash": sha256_hex("validators_hash"),
+            "next_validators_hash": sha256_hex("next_validators_hash"),
+            "consensus_hash": sha256_hex("consensus_hash"),
+            "app_hash": sha256_hex("app_hash"),
+            "last_results_hash": sha256_hex("last_results_hash"),
+            "evidence_hash": sha256_hex("evidence_hash"),
+            "proposer_address": sha256_hex("proposer_address")[..40],
+        })).unwrap()
+    }
+
+    #[test]
+    fn computes_the_hash_of_a_known_header() {
+        assert_eq!(
+            header_hash(&known_header()).unwrap(),
+            "F740121F553B5418C3EFBD343C2DBFE9E007BB67B0D020A0741374BAB65242A4"
+        );
+    }
+
+    #[test]
+    fn a_changed_header_field_changes_the_hash() {
+        let mut header = known_header();
+        header.app_hash = Some(sha256_hex("other_app_hash"));
+        assert_ne!(
+            header_hash(&header).unwrap(),
+            "F740121F553B5418C3EFBD343C2DBFE9E007BB67B0D020A0741374BAB65242A4"
+        );
+    }
+
+    #[test]
+    fn a_header_without_hash_fields_cannot_be_verified() {
+        let mut header = known_header();
+        header.data_hash = None;
+        match header_hash(&header) {
+            Err(ClientError::Parse(message)) => assert!(message.contains("data_hash")),
+            other => panic!("expected a missing field error, got {:?}", other),
+        }
+    }
+}
@@ -5,18 +5,40 @@
 * including storing, retrieving, and analyzing block data.
 */
 
-use sqlx::{Pool, Postgres};
-use crate::models::StoredBlock;
+use chrono::{DateTime, Utc};
+use futures_util::Stream;
+use sqlx::{PgConnection, Pool, Postgres};
+use crate::models::{block::{decode_block_data, BlockSummary, BlockTime}, StoredBlock};
+
+/* zstd level used when block payloads are stored compressed */
+const BLOCK_DATA_COMPRESSION_LEVEL: i32 = 3;
+
+/*
+* Sections of the stored block payload that can be requested on their
+* own, with their path in the payload. Blocks stored before evidence and
+* the last commit were kept return those sections as null.
+*/
+pub const RAW_BLOCK_FIELDS: &[(&str, &[&str])] = &[
+    ("block_id", &["block_id"]),
+    ("header", &["block", "header"]),
+    ("data", &["block", "data"]),
+    ("evidence", &["block", "evidence"]),
+    ("last_commit", &["block", "last_commit"]),
+];
+
+/* Channel notified with the height of every stored block */
+pub const NEW_BLOCK_CHANNEL: &str = "new_block";
 
 /* SQL queries for blocks */
 
-/* SQL for inserting or updating a block */
+/* SQL for inserting or updating a block, keeping the original created_at and reporting whether a row was overwritten */
 const UPSERT_BLOCK_SQL: &str = r#"
     INSERT INTO blocks (
         height, time, hash, proposer_address,
-        tx_count, previous_block_hash, burn_amount, data, created_at
+        tx_count, previous_block_hash, burn_amount, block_size_bytes, data, data_compressed,
+        action_counts, has_swaps, has_transfers, created_at
     )
-    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
     ON CONFLICT (height) DO UPDATE
     SET time = EXCLUDED.time,
         hash = EXCLUDED.hash,
@@ -24,17 +46,162 @@ const UPSERT_BLOCK_SQL: &str = r#"
         tx_count = EXCLUDED.tx_count,
         previous_block_hash = EXCLUDED.previous_block_hash,
         burn_amount = EXCLUDED.burn_amount,
+        block_size_bytes = EXCLUDED.block_size_bytes,
         data = EXCLUDED.data,
-        created_at = EXCLUDED.created_at
+        data_compressed = EXCLUDED.data_compressed,
+        action_counts = EXCLUDED.action_counts,
+        has_swaps = EXCLUDED.has_swaps,
+        has_transfers = EXCLUDED.has_transfers,
+        created_at = blocks.created_at,
+        updated_at = EXCLUDED.created_at
+    RETURNING (xmax <> 0) AS overwritten
+"#;
+
+/* SQL for notifying listeners of a stored block */
+const NOTIFY_NEW_BLOCK_SQL: &str = r#"
+    SELECT pg_notify($1, $2::text)
+"#;
+
+/* SQL for filling in action counts of older blocks from their stored transactions */
+const BACKFILL_ACTION_COUNTS_SQL: &str = r#"
+    WITH batch AS (
+        SELECT height FROM blocks
+        WHERE action_counts IS NULL
+        ORDER BY height
+        LIMIT $1
+    ),
+    counts AS (
+        SELECT batch.height, COALESCE(
+            (
+                SELECT jsonb_object_agg(action_type, action_count)
+                FROM (
+                    SELECT action_type, COUNT(*) AS action_count
+                    FROM transactions
+                    WHERE block_height = batch.height
+                    GROUP BY action_type
+                ) grouped
+            ),
+            '{}'::jsonb
+        ) AS action_counts
+        FROM batch
+    )
+    UPDATE blocks b
+    SET action_counts = counts.action_counts,
+        has_swaps = counts.action_counts ?| array['swap', 'swap_claim'],
+        has_transfers = counts.action_counts ?| array['spend', 'output', 'ibc_transfer']
+    FROM counts
+    WHERE b.height = counts.height
 "#;
 
-/* SQL for retrieving the latest blocks */
+/* SQL for retrieving summaries of the latest blocks, without their payloads */
 const GET_LATEST_BLOCKS_SQL: &str = r#"
+    SELECT height, time, tx_count, block_size_bytes, tx_count > 0 AS has_transactions, has_swaps, has_transfers
+    FROM blocks
+    ORDER BY height DESC
+    LIMIT $1
+"#;
+
+/* SQL for retrieving the highest block */
+const GET_LATEST_BLOCK_SQL: &str = r#"
     SELECT * FROM blocks
     ORDER BY height DESC
+    LIMIT 1
+"#;
+
+/* SQL for retrieving the lowest indexed block */
+const GET_EARLIEST_BLOCK_SQL: &str = r#"
+    SELECT * FROM blocks
+    ORDER BY height ASC
+    LIMIT 1
+"#;
+
+/* SQL for retrieving summaries of blocks produced within a time window */
+const GET_BLOCKS_IN_TIME_RANGE_SQL: &str = r#"
+    SELECT height, time, tx_count, block_size_bytes, tx_count > 0 AS has_transactions, has_swaps, has_transfers
+    FROM blocks
+    WHERE time >= $1 AND time < $2
+    ORDER BY height
+    LIMIT $3
+"#;
+
+/* SQL for retrieving summaries of the blocks with the most transactions, optionally since a time */
+const GET_BUSIEST_BLOCKS_SQL: &str = r#"
+    SELECT height, time, tx_count, block_size_bytes, tx_count > 0 AS has_transactions, has_swaps, has_transfers
+    FROM blocks
+    WHERE ($2::TIMESTAMPTZ IS NULL OR time >= $2)
+    ORDER BY tx_count DESC, height DESC
     LIMIT $1
 "#;
 
+/* SQL for counting blocks produced within a time window */
+const COUNT_BLOCKS_IN_TIME_RANGE_SQL: &str = r#"
+    SELECT COUNT(*) FROM blocks
+    WHERE time >= $1 AND time < $2
+"#;
+
+/* SQL for counting all indexed blocks */
+const COUNT_BLOCKS_SQL: &str = r#"
+    SELECT COUNT(*) FROM blocks
+"#;
+
+/* SQL for retrieving summaries of the newest blocks of a proposer, optionally below a height */
+const GET_BLOCKS_BY_PROPOSER_SQL: &str = r#"
+    SELECT height, time, tx_count, block_size_bytes, tx_count > 0 AS has_transactions, has_swaps, has_transfers
+    FROM blocks
+    WHERE proposer_address = $1
+      AND ($3::BIGINT IS NULL OR height < $3)
+    ORDER BY height DESC
+    LIMIT $2
+"#;
+
+/* SQL for counting the blocks of a proposer */
+const COUNT_BLOCKS_BY_PROPOSER_SQL: &str = r#"
+    SELECT COUNT(*) FROM blocks
+    WHERE proposer_address = $1
+"#;
+
+/* SQL for retrieving the lowest indexed block height */
+const GET_LOWEST_HEIGHT_SQL: &str = r#"
+    SELECT MIN(height) FROM blocks
+"#;
+
+/* SQL for deleting blocks older than a cutoff */
+const DELETE_BLOCKS_BEFORE_SQL: &str = r#"
+    DELETE FROM blocks
+    WHERE time < $1
+"#;
+
+/* SQL for retrieving the highest indexed block height */
+const GET_LATEST_HEIGHT_SQL: &str = r#"
+    SELECT MAX(height) FROM blocks
+"#;
+
+/* SQL for retrieving the hash of the block at a given height */
+const GET_HASH_AT_HEIGHT_SQL: &str = r#"
+    SELECT hash FROM blocks
+    WHERE height = $1
+"#;
+
+/* SQL for retrieving the hash and transaction count stored at a height */
+const GET_HASH_AND_TX_COUNT_AT_HEIGHT_SQL: &str = r#"
+    SELECT hash, tx_count FROM blocks
+    WHERE height = $1
+"#;
+
+/* SQL for retrieving the blocks at any of a list of heights */
+const GET_BLOCKS_BY_HEIGHTS_SQL: &str = r#"
+    SELECT * FROM blocks
+    WHERE height = ANY($1)
+    ORDER BY height
+"#;
+
+/* SQL for retrieving the blocks in an inclusive height range */
+const GET_BLOCKS_IN_HEIGHT_RANGE_SQL: &str = r#"
+    SELECT * FROM blocks
+    WHERE height BETWEEN $1 AND $2
+    ORDER BY height
+"#;
+
 /* SQL for retrieving a specific block by height */
 const GET_BLOCK_BY_HEIGHT_SQL: &str = r#"
     SELECT *
@@ -42,17 +209,71 @@ const GET_BLOCK_BY_HEIGHT_SQL: &str = r#"
     WHERE height = $1
 "#;
 
+/* SQL for retrieving the transaction count of a block */
+const GET_TX_COUNT_AT_HEIGHT_SQL: &str = r#"
+    SELECT tx_count FROM blocks
+    WHERE height = $1
+"#;
+
+/* SQL for retrieving the last block produced at or before a timestamp */
+const GET_BLOCK_AT_OR_BEFORE_TIME_SQL: &str = r#"
+    SELECT height, time FROM blocks
+    WHERE time <= $1
+    ORDER BY time DESC
+    LIMIT 1
+"#;
+
+/* SQL for retrieving the timestamp of a block */
+const GET_BLOCK_TIME_AT_HEIGHT_SQL: &str = r#"
+    SELECT height, time FROM blocks
+    WHERE height = $1
+"#;
+
+/* SQL for retrieving the stored payload columns of a block */
+const GET_BLOCK_RAW_DATA_SQL: &str = r#"
+    SELECT data, data_compressed
+    FROM blocks
+    WHERE height = $1
+"#;
+
+/*
+* Compresses a block payload as stored in data_compressed.
+*
+* @param data Block payload as JSON
+* @return The zstd-compressed JSON text
+*/
+fn compress_block_data(data: &serde_json::Value) -> Result<Vec<u8>, sqlx::Error> {
+    zstd::encode_all(data.to_string().as_bytes(), BLOCK_DATA_COMPRESSION_LEVEL)
+        .map_err(sqlx::Error::Io)
+}
+
 /*
 * Stores a block in the database.
 *
-* @param pool Database connection pool
+* @param conn Database connection, usually an open transaction
 * @param block Block data to store
+* @param compress Store the payload zstd-compressed in data_compressed instead of as JSONB
+*
+* A null payload leaves both payload columns NULL. Overwriting a stored
+* block keeps its original created_at and sets updated_at. The height is
+* sent on NEW_BLOCK_CHANNEL, delivered to listeners once the write commits.
+*
+* @return Whether an already stored block was overwritten
 */
 pub async fn store_block(
-    pool: &Pool<Postgres>,
-    block: StoredBlock,
-) -> Result<(), sqlx::Error> {
-    sqlx::query(UPSERT_BLOCK_SQL)
+    conn: &mut PgConnection,
+    block: &StoredBlock,
+    compress: bool,
+) -> Result<bool, sqlx::Error> {
+    let (data, data_compressed) = if block.data.is_null() {
+        (None, None)
+    } else if compress {
+        (None, Some(compress_block_data(&block.data)?))
+    } else {
+        (Some(&block.data), None)
+    };
+
+    let overwritten = sqlx::query_scalar::<_, bool>(UPSERT_BLOCK_SQL)
         .bind(block.height)
         .bind(block.time)
         .bind(&block.hash)
@@ -60,24 +281,58 @@ pub async fn store_block(
         .bind(block.tx_count)
         .bind(&block.previous_block_hash)
         .bind(block.burn_amount)
-        .bind(&block.data)
+        .bind(block.block_size_bytes)
+        .bind(data)
+        .bind(data_compressed)
+        .bind(&block.action_counts)
+        .bind(block.has_swaps)
+        .bind(block.has_transfers)
         .bind(block.created_at)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    sqlx::query(NOTIFY_NEW_BLOCK_SQL)
+        .bind(NEW_BLOCK_CHANNEL)
+        .bind(block.height)
+        .execute(conn)
+        .await?;
+
+    Ok(overwritten)
+}
+
+/*
+* Fills in action counts for one batch of blocks stored without them.
+*
+* Counts are derived from the block's stored transactions; blocks
+* without transactions get an empty map. The swap and transfer flags
+* are set from the same counts.
+*
+* @param pool Database connection pool
+* @param batch_size Maximum number of blocks updated
+* @return Number of blocks updated, zero once the backfill is complete
+*/
+pub async fn backfill_action_counts(
+    pool: &Pool<Postgres>,
+    batch_size: i64,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(BACKFILL_ACTION_COUNTS_SQL)
+        .bind(batch_size)
         .execute(pool)
         .await?;
 
-    Ok(())
+    Ok(result.rows_affected())
 }
 
 /*
 * Retrieves the latest blocks from the database.
 *
 * @param pool Database connection pool
-* @return Vector of recent block data
+* @return Summaries of the most recent blocks
 */
 pub async fn get_latest_blocks(
     pool: &Pool<Postgres>,
-) -> Result<Vec<StoredBlock>, sqlx::Error> {
-    sqlx::query_as::<_, StoredBlock>(GET_LATEST_BLOCKS_SQL)
+) -> Result<Vec<BlockSummary>, sqlx::Error> {
+    sqlx::query_as::<_, BlockSummary>(GET_LATEST_BLOCKS_SQL)
         .bind(10) // Fetch last 10 blocks
         .fetch_all(pool)
         .await
@@ -99,3 +354,572 @@ pub async fn get_block_by_height(
         .fetch_optional(pool)
         .await
 }
+
+/*
+* Retrieves the number of transactions in the block at a height.
+*
+* @param pool Database connection pool
+* @param height The blockchain height to query for
+* @return The block's transaction count, None if the block is not indexed
+*/
+pub async fn get_tx_count_at_height(
+    pool: &Pool<Postgres>,
+    height: i64,
+) -> Result<Option<i32>, sqlx::Error> {
+    sqlx::query_scalar::<_, i32>(GET_TX_COUNT_AT_HEIGHT_SQL)
+        .bind(height)
+        .fetch_optional(pool)
+        .await
+}
+
+/*
+* Resolves the block produced closest to a timestamp, at or before it.
+*
+* @param pool Database connection pool
+* @param time Timestamp to resolve
+* @return Height and time of the block, None if no indexed block is that old
+*/
+pub async fn get_block_at_or_before_time(
+    pool: &Pool<Postgres>,
+    time: DateTime<Utc>,
+) -> Result<Option<BlockTime>, sqlx::Error> {
+    sqlx::query_as::<_, BlockTime>(GET_BLOCK_AT_OR_BEFORE_TIME_SQL)
+        .bind(time)
+        .fetch_optional(pool)
+        .await
+}
+
+/*
+* Retrieves the timestamp of a block without loading the block.
+*
+* @param pool Database connection pool
+* @param height Block height to query
+* @return Height and time of the block, None if the block is not indexed
+*/
+pub async fn get_block_time_at_height(
+    pool: &Pool<Postgres>,
+    height: i64,
+) -> Result<Option<BlockTime>, sqlx::Error> {
+    sqlx::query_as::<_, BlockTime>(GET_BLOCK_TIME_AT_HEIGHT_SQL)
+        .bind(height)
+        .fetch_optional(pool)
+        .await
+}
+
+/*
+* Retrieves the persisted Tendermint block JSON at a height.
+*
+* Decompresses the payload when it was stored compressed.
+*
+* @param pool Database connection pool
+* @param height The blockchain height to query for
+* @return The raw block JSON if found, null when the payload was not stored, None if not exists
+*/
+pub async fn get_block_raw_data(
+    pool: &Pool<Postgres>,
+    height: i64,
+) -> Result<Option<serde_json::Value>, sqlx::Error> {
+    let row = sqlx::query_as::<_, (Option<serde_json::Value>, Option<Vec<u8>>)>(GET_BLOCK_RAW_DATA_SQL)
+        .bind(height)
+        .fetch_optional(pool)
+        .await?;
+
+    row.map(|(data, compressed)| decode_block_data(data, compressed))
+        .transpose()
+}
+
+/*
+* Retrieves selected sections of the persisted block JSON at a height.
+*
+* Uncompressed payloads are projected by Postgres so only the requested
+* sections are read back; compressed ones are decompressed and projected
+* here. Sections missing from the payload are returned as null.
+*
+* @param pool Database connection pool
+* @param height The blockchain height to query for
+* @param fields Names from RAW_BLOCK_FIELDS to return; other names are ignored
+* @return Object with the requested sections if found, null when the payload was not stored, None if not exists
+*/
+pub async fn get_block_raw_fields(
+    pool: &Pool<Postgres>,
+    height: i64,
+    fields: &[&str],
+) -> Result<Option<serde_json::Value>, sqlx::Error> {
+    let selected: Vec<(&str, &[&str])> = RAW_BLOCK_FIELDS
+        .iter()
+        .filter(|(name, _)| fields.contains(name))
+        .copied()
+        .collect();
+
+    // Only names and paths from RAW_BLOCK_FIELDS are interpolated, never request input
+    let projection = selected
+        .iter()
+        .map(|(name, path)| format!("'{}', data #> '{{{}}}'", name, path.join(",")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        "SELECT CASE WHEN data IS NOT NULL THEN jsonb_build_object({}) END, \
+                CASE WHEN data IS NULL THEN data_compressed END \
+         FROM blocks \
+         WHERE height = $1",
+        projection
+    );
+
+    let row = sqlx::query_as::<_, (Option<serde_json::Value>, Option<Vec<u8>>)>(&sql)
+        .bind(height)
+        .fetch_optional(pool)
+        .await?;
+
+    let (projected, compressed) = match row {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+    if projected.is_some() {
+        return Ok(projected);
+    }
+
+    let data = decode_block_data(None, compressed)?;
+    if data.is_null() {
+        return Ok(Some(data));
+    }
+
+    let object = selected
+        .iter()
+        .map(|(name, path)| {
+            let value = path.iter()
+                .try_fold(&data, |value, key| value.get(key))
+                .cloned()
+                .unwrap_or(serde_json::Value::Null);
+            (name.to_string(), value)
+        })
+        .collect();
+    Ok(Some(serde_json::Value::Object(object)))
+}
+
+/*
+* Retrieves the highest block stored in the database.
+*
+* @param pool Database connection pool
+* @return The latest block, None if no blocks are stored
+*/
+pub async fn get_latest_block(
+    pool: &Pool<Postgres>,
+) -> Result<Option<StoredBlock>, sqlx::Error> {
+    sqlx::query_as::<_, StoredBlock>(GET_LATEST_BLOCK_SQL)
+        .fetch_optional(pool)
+        .await
+}
+
+/*
+* Retrieves the lowest block stored in the database.
+*
+* Sync starts at SYNC_START_HEIGHT, so this is the start of the indexed
+* range rather than the chain's genesis block.
+*
+* @param pool Database connection pool
+* @return The earliest indexed block, None if no blocks are stored
+*/
+pub async fn get_earliest_block(
+    pool: &Pool<Postgres>,
+) -> Result<Option<StoredBlock>, sqlx::Error> {
+    sqlx::query_as::<_, StoredBlock>(GET_EARLIEST_BLOCK_SQL)
+        .fetch_optional(pool)
+        .await
+}
+
+/*
+* Retrieves the blocks stored at any of the given heights.
+*
+* Heights that are not indexed are skipped.
+*
+* @param pool Database connection pool
+* @param heights Block heights to query
+* @return Vector of the found blocks in ascending height order
+*/
+pub async fn get_blocks_by_heights(
+    pool: &Pool<Postgres>,
+    heights: &[i64],
+) -> Result<Vec<StoredBlock>, sqlx::Error> {
+    sqlx::query_as::<_, StoredBlock>(GET_BLOCKS_BY_HEIGHTS_SQL)
+        .bind(heights)
+        .fetch_all(pool)
+        .await
+}
+
+/*
+* Streams the blocks in a height range without loading them all at once.
+*
+* Rows are decoded as they arrive, so memory use does not grow with the
+* range. A pool connection is held until the stream is dropped.
+*
+* @param pool Database connection pool
+* @param from First height of the range
+* @param to Last height of the range (inclusive)
+* @return Stream of blocks in ascending height order
+*/
+pub fn stream_blocks_in_height_range(
+    pool: &Pool<Postgres>,
+    from: i64,
+    to: i64,
+) -> impl Stream<Item = Result<StoredBlock, sqlx::Error>> + '_ {
+    sqlx::query_as::<_, StoredBlock>(GET_BLOCKS_IN_HEIGHT_RANGE_SQL)
+        .bind(from)
+        .bind(to)
+        .fetch(pool)
+}
+
+/*
+* Retrieves the highest block height stored in the database.
+*
+* @param pool Database connection pool
+* @return The latest indexed height, None if no blocks are stored
+*/
+pub async fn get_latest_height(
+    pool: &Pool<Postgres>,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar::<_, Option<i64>>(GET_LATEST_HEIGHT_SQL)
+        .fetch_one(pool)
+        .await
+}
+
+/*
+* Retrieves the blocks proposed by a validator, newest first.
+*
+* @param pool Database connection pool
+* @param address Proposer address to match
+* @param limit Maximum number of blocks to return
+* @param before_height Only return blocks below this height, for paging
+* @return Summaries of the proposer's blocks in descending height order
+*/
+pub async fn get_blocks_by_proposer(
+    pool: &Pool<Postgres>,
+    address: &str,
+    limit: i64,
+    before_height: Option<i64>,
+) -> Result<Vec<BlockSummary>, sqlx::Error> {
+    sqlx::query_as::<_, BlockSummary>(GET_BLOCKS_BY_PROPOSER_SQL)
+        .bind(address)
+        .bind(limit)
+        .bind(before_height)
+        .fetch_all(pool)
+        .await
+}
+
+/*
+* Counts the blocks proposed by a validator.
+*
+* @param pool Database connection pool
+* @param address Proposer address to match
+* @return Number of blocks with the given proposer
+*/
+pub async fn count_blocks_by_proposer(
+    pool: &Pool<Postgres>,
+    address: &str,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(COUNT_BLOCKS_BY_PROPOSER_SQL)
+        .bind(address)
+        .fetch_one(pool)
+        .await
+}
+
+/*
+* Retrieves the lowest block height stored in the database.
+*
+* @param pool Database connection pool
+* @return The lowest indexed height, None if no blocks are stored
+*/
+pub async fn get_lowest_height(
+    pool: &Pool<Postgres>,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar::<_, Option<i64>>(GET_LOWEST_HEIGHT_SQL)
+        .fetch_one(pool)
+        .await
+}
+
+/*
+* Deletes all blocks older than a cutoff.
+*
+* Their transactions must be deleted first, since they reference the block.
+*
+* @param conn Database connection, usually an open transaction
+* @param cutoff Blocks strictly before this time are removed
+* @return Number of deleted blocks
+*/
+pub async fn delete_blocks_before(
+    conn: &mut PgConnection,
+    cutoff: DateTime<Utc>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(DELETE_BLOCKS_BEFORE_SQL)
+        .bind(cutoff)
+        .execute(conn)
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+/*
+* Retrieves blocks produced within a time window.
+*
+* @param pool Database connection pool
+* @param from Inclusive start of the window
+* @param to Exclusive end of the window
+* @param limit Maximum number of blocks to retrieve
+* @return Block summaries in ascending height order
+*/
+pub async fn get_blocks_in_time_range(
+    pool: &Pool<Postgres>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    limit: i64,
+) -> Result<Vec<BlockSummary>, sqlx::Error> {
+    sqlx::query_as::<_, BlockSummary>(GET_BLOCKS_IN_TIME_RANGE_SQL)
+        .bind(from)
+        .bind(to)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
+
+/*
+* Retrieves the blocks with the most transactions.
+*
+* Ties are broken in favor of the most recent block.
+*
+* @param pool Database connection pool
+* @param limit Maximum number of blocks to retrieve
+* @param since Optional inclusive start time; all blocks when None
+* @return Block summaries ordered by transaction count, most first
+*/
+pub async fn get_busiest_blocks(
+    pool: &Pool<Postgres>,
+    limit: i64,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<BlockSummary>, sqlx::Error> {
+    sqlx::query_as::<_, BlockSummary>(GET_BUSIEST_BLOCKS_SQL)
+        .bind(limit)
+        .bind(since)
+        .fetch_all(pool)
+        .await
+}
+
+/*
+* Retrieves the stored hash of the block at a given height.
+*
+* @param pool Database connection pool
+* @param height The blockchain height to query for
+* @return The block hash if the height is indexed, None otherwise
+*/
+pub async fn get_hash_at_height(
+    pool: &Pool<Postgres>,
+    height: i64,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar::<_, String>(GET_HASH_AT_HEIGHT_SQL)
+        .bind(height)
+        .fetch_optional(pool)
+        .await
+}
+
+/*
+* Retrieves the hash and transaction count stored at a height.
+*
+* @param pool Database connection pool
+* @param height The blockchain height to query for
+* @return The block hash and transaction count if the block exists
+*/
+pub async fn get_hash_and_tx_count_at_height(
+    pool: &Pool<Postgres>,
+    height: i64,
+) -> Result<Option<(String, i32)>, sqlx::Error> {
+    sqlx::query_as::<_, (String, i32)>(GET_HASH_AND_TX_COUNT_AT_HEIGHT_SQL)
+        .bind(height)
+        .fetch_optional(pool)
+        .await
+}
+
+/*
+* Counts all indexed blocks.
+*
+* @param pool Database connection pool
+* @return Total number of blocks in the database
+*/
+pub async fn count_blocks(
+    pool: &Pool<Postgres>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(COUNT_BLOCKS_SQL)
+        .fetch_one(pool)
+        .await
+}
+
+/*
+* Counts blocks produced within a time window.
+*
+* @param pool Database connection pool
+* @param from Inclusive start of the window
+* @param to Exclusive end of the window
+* @return Number of blocks in the window
+*/
+pub async fn count_blocks_in_time_range(
+    pool: &Pool<Postgres>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(COUNT_BLOCKS_IN_TIME_RANGE_SQL)
+        .bind(from)
+        .bind(to)
+        .fetch_one(pool)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /* Builds a block payload shaped like the node's, with random transaction bytes */
+    fn representative_block(tx_count: usize, tx_bytes: usize) -> serde_json::Value {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut hash = || hex::encode_upper(rng.gen::<[u8; 32]>());
+        let header = serde_json::json!({
+            "version": {"block": "11", "app": "0"},
+            "chain_id": "penumbra-1",
+            "height": "1234567",
+            "time": "2024-01-01T00:00:00.123456789Z",
+            "last_block_id": {"hash": hash(), "parts": {"total": 1, "hash": hash()}},
+            "last_commit_hash": hash(),
+            "data_hash": hash(),
+            "validators_hash": hash(),
+            "next_validators_hash": hash(),
+            "consensus_hash": hash(),
+            "app_hash": hash(),
+            "last_results_hash": hash(),
+            "evidence_hash": hash(),
+            "proposer_address": hash()[..40].to_string(),
+        });
+        let block_hash = hash();
+
+        let mut rng = StdRng::seed_from_u64(2);
+        let txs: Vec<String> = (0..tx_count)
+            .map(|_| STANDARD.encode((0..tx_bytes).map(|_| rng.gen::<u8>()).collect::<Vec<u8>>()))
+            .collect();
+
+        serde_json::json!({
+            "block_id": {"hash": block_hash},
+            "block": {"header": header, "data": {"txs": txs}},
+        })
+    }
+
+    #[test]
+    fn compressed_block_data_round_trips() {
+        let data = representative_block(3, 2_000);
+        let compressed = compress_block_data(&data).unwrap();
+        assert_eq!(decode_block_data(None, Some(compressed)).unwrap(), data);
+    }
+
+    #[test]
+    fn compression_shrinks_a_representative_block() {
+        // Transactions are mostly proofs and ciphertexts, so only their base64 and JSON overhead compresses
+        let data = representative_block(20, 4_000);
+        let json_bytes = data.to_string().len();
+        let compressed_bytes = compress_block_data(&data).unwrap().len();
+        let ratio = compressed_bytes as f64 / json_bytes as f64;
+        assert!(
+            ratio < 0.8,
+            "compressed {} JSON bytes to {} bytes, {:.0}% of the JSON size",
+            json_bytes,
+            compressed_bytes,
+            ratio * 100.0
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL database in TEST_DATABASE_URL"]
+    async fn returns_evidence_and_last_commit_sections() {
+        use crate::models::amount::TokenAmount;
+
+        // Heights far above any real chain, so the test never touches indexed blocks
+        const PLAIN: i64 = 9_000_000_000_300;
+        const COMPRESSED: i64 = 9_000_000_000_301;
+
+        let pool = crate::db::init_db(&std::env::var("TEST_DATABASE_URL").unwrap()).await.unwrap();
+        let mut data = representative_block(1, 100);
+        data["block"]["evidence"] = serde_json::json!({ "evidence": [] });
+        data["block"]["last_commit"] = serde_json::json!({ "height": "1234566", "round": 0, "signatures": [] });
+
+        let mut tx = pool.begin().await.unwrap();
+        for (height, compress) in [(PLAIN, false), (COMPRESSED, true)] {
+            let block = StoredBlock {
+                height,
+                time: chrono::Utc::now(),
+                hash: format!("{:064X}", height),
+                proposer_address: "PROPOSER".to_string(),
+                tx_count: 1,
+                previous_block_hash: None,
+                burn_amount: TokenAmount::default(),
+                block_size_bytes: None,
+                data: data.clone(),
+                action_counts: None,
+                has_swaps: false,
+                has_transfers: false,
+                created_at: chrono::Utc::now(),
+                updated_at: None,
+            };
+            store_block(&mut tx, &block, compress).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let mut sections = Vec::new();
+        for height in [PLAIN, COMPRESSED] {
+            sections.push(get_block_raw_fields(&pool, height, &["evidence", "last_commit"]).await.unwrap());
+        }
+
+        sqlx::query("DELETE FROM blocks WHERE height IN ($1, $2)")
+            .bind(PLAIN)
+            .bind(COMPRESSED)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let expected = serde_json::json!({
+            "evidence": data["block"]["evidence"],
+            "last_commit": data["block"]["last_commit"],
+        });
+        for section in sections {
+            assert_eq!(section, Some(expected.clone()));
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL database in TEST_DATABASE_URL"]
+    async fn summary_lists_never_read_block_payloads() {
+        // Heights far above any real chain, so the test never touches indexed blocks
+        const HEIGHT: i64 = 9_000_000_000_400;
+
+        let pool = crate::db::init_db(&std::env::var("TEST_DATABASE_URL").unwrap()).await.unwrap();
+        // A payload that fails to decompress, so any query reading it would error
+        sqlx::query(
+            "INSERT INTO blocks (height, time, hash, proposer_address, tx_count, burn_amount, data_compressed, created_at)
+             VALUES ($1, now(), 'SUMMARY_TEST', 'SUMMARY_TEST_PROPOSER', 2, 0, '\\x00', now())"
+        )
+            .bind(HEIGHT)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let since = Some(chrono::Utc::now() - chrono::Duration::minutes(1));
+        let busiest = get_busiest_blocks(&pool, 1_000, since).await;
+        let latest = get_latest_blocks(&pool).await;
+        let by_proposer = get_blocks_by_proposer(&pool, "SUMMARY_TEST_PROPOSER", 10, None).await;
+        let full = get_block_by_height(&pool, HEIGHT).await;
+
+        sqlx::query("DELETE FROM blocks WHERE height = $1").bind(HEIGHT).execute(&pool).await.unwrap();
+
+        assert!(busiest.unwrap().iter().any(|block| block.height == HEIGHT));
+        assert_eq!(latest.unwrap()[0].height, HEIGHT);
+        let by_proposer = by_proposer.unwrap();
+        assert_eq!(by_proposer.len(), 1);
+        assert_eq!(by_proposer[0].tx_count, 2);
+        assert!(by_proposer[0].has_transactions);
+        assert!(full.is_err(), "loading the full block should decompress the payload");
+    }
+}
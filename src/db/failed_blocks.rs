@@ -0,0 +1,122 @@
+/*
+* Database operations for blocks that exhausted their retries.
+*
+* Blocks that keep failing are recorded here instead of being dropped,
+* so they can be inspected and re-attempted later.
+*/
+
+use sqlx::{PgConnection, Pool, Postgres};
+use crate::models::block::FailedBlock;
+
+/* SQL for recording a failed block, accumulating attempts across failures */
+const UPSERT_FAILED_BLOCK_SQL: &str = r#"
+    INSERT INTO failed_blocks (height, last_error, attempts, last_attempt)
+    VALUES ($1, $2, $3, CURRENT_TIMESTAMP)
+    ON CONFLICT (height) DO UPDATE
+        SET last_error = EXCLUDED.last_error,
+            attempts = failed_blocks.attempts + EXCLUDED.attempts,
+            last_attempt = EXCLUDED.last_attempt
+"#;
+
+/* SQL for retrieving failed blocks in height order */
+const GET_FAILED_BLOCKS_SQL: &str = r#"
+    SELECT height, last_error, attempts, last_attempt
+    FROM failed_blocks
+    ORDER BY height ASC
+    LIMIT $1
+"#;
+
+/* SQL for counting failed blocks within a height range */
+const COUNT_FAILED_BLOCKS_IN_RANGE_SQL: &str = r#"
+    SELECT COUNT(*) FROM failed_blocks
+    WHERE height BETWEEN $1 AND $2
+"#;
+
+/* SQL for removing a block that has since been stored */
+const DELETE_FAILED_BLOCK_SQL: &str = r#"
+    DELETE FROM failed_blocks
+    WHERE height = $1
+"#;
+
+/*
+* Records a block that exhausted its retries.
+*
+* @param pool Database connection pool
+* @param height Height of the failed block
+* @param last_error Error of the final attempt
+* @param attempts Number of attempts made in this round
+*/
+pub async fn record_failed_block(
+    pool: &Pool<Postgres>,
+    height: i64,
+    last_error: &str,
+    attempts: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(UPSERT_FAILED_BLOCK_SQL)
+        .bind(height)
+        .bind(last_error)
+        .bind(attempts)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/*
+* Retrieves recorded failed blocks, lowest height first.
+*
+* @param pool Database connection pool
+* @param limit Maximum number of blocks to return
+* @return Vector of failed blocks
+*/
+pub async fn get_failed_blocks(
+    pool: &Pool<Postgres>,
+    limit: i64,
+) -> Result<Vec<FailedBlock>, sqlx::Error> {
+    sqlx::query_as::<_, FailedBlock>(GET_FAILED_BLOCKS_SQL)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
+
+/*
+* Removes a block from the failed blocks once it has been stored.
+*
+* Runs on the connection storing the block, so the record is only
+* cleared when the block itself commits.
+*
+* @param conn Database connection or open transaction
+* @param height Height of the recovered block
+* @return Whether a failed block record was removed
+*/
+pub async fn delete_failed_block(
+    conn: &mut PgConnection,
+    height: i64,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(DELETE_FAILED_BLOCK_SQL)
+        .bind(height)
+        .execute(conn)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/*
+* Counts recorded failed blocks within a height range.
+*
+* @param pool Database connection pool
+* @param start_height First height of the range
+* @param end_height Last height of the range (inclusive)
+* @return Number of failed blocks in the range
+*/
+pub async fn count_failed_blocks_in_range(
+    pool: &Pool<Postgres>,
+    start_height: i64,
+    end_height: i64,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(COUNT_FAILED_BLOCKS_IN_RANGE_SQL)
+        .bind(start_height)
+        .bind(end_height)
+        .fetch_one(pool)
+        .await
+}
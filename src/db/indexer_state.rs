@@ -0,0 +1,70 @@
+/*
+* Database operations for persisted indexer state.
+*
+* Tracks the last_contiguous_height watermark: the highest height below
+* which every block is indexed. Sync resumes from it after a restart, so
+* blocks skipped after failed fetches are retried instead of left as gaps.
+*/
+
+use sqlx::{Pool, Postgres};
+
+/* indexer_state key of the contiguous coverage watermark */
+const LAST_CONTIGUOUS_HEIGHT_KEY: &str = "last_contiguous_height";
+
+/* SQL for reading an indexer state value */
+const GET_STATE_VALUE_SQL: &str = r#"
+    SELECT value FROM indexer_state
+    WHERE key = $1
+"#;
+
+/* SQL for advancing the watermark through the run of consecutive blocks above a floor */
+const ADVANCE_CONTIGUOUS_HEIGHT_SQL: &str = r#"
+    INSERT INTO indexer_state (key, value)
+    SELECT $1, COALESCE(MAX(height), $2)
+    FROM (
+        SELECT height, height - ROW_NUMBER() OVER (ORDER BY height) AS run_floor
+        FROM blocks
+        WHERE height > $2
+    ) run
+    WHERE run_floor = $2
+    ON CONFLICT (key) DO UPDATE
+        SET value = GREATEST(indexer_state.value, EXCLUDED.value),
+            updated_at = CURRENT_TIMESTAMP
+    RETURNING value
+"#;
+
+/*
+* Retrieves the last contiguous height watermark.
+*
+* @param pool Database connection pool
+* @return The watermark, None if it has never been recorded
+*/
+pub async fn get_last_contiguous_height(
+    pool: &Pool<Postgres>,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(GET_STATE_VALUE_SQL)
+        .bind(LAST_CONTIGUOUS_HEIGHT_KEY)
+        .fetch_optional(pool)
+        .await
+}
+
+/*
+* Advances the last contiguous height watermark.
+*
+* Extends the watermark from `floor` through every consecutive indexed
+* height above it. The stored value never moves backwards.
+*
+* @param pool Database connection pool
+* @param floor Height up to which every block is known to be indexed
+* @return The watermark after advancing
+*/
+pub async fn advance_last_contiguous_height(
+    pool: &Pool<Postgres>,
+    floor: i64,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(ADVANCE_CONTIGUOUS_HEIGHT_SQL)
+        .bind(LAST_CONTIGUOUS_HEIGHT_KEY)
+        .bind(floor)
+        .fetch_one(pool)
+        .await
+}
@@ -6,19 +6,42 @@ pub mod schema;
 pub mod blocks;
 pub mod transactions;
 pub mod stats;
+pub mod indexer_state;
+pub mod failed_blocks;
 
-use sqlx::{Pool, Postgres};
+use std::env;
+use std::str::FromStr;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, StreamExt};
+use sqlx::{postgres::PgListener, Pool, Postgres};
+use tracing::warn;
 
-/* Maximum number of database connections */
-const MAX_DB_CONNECTIONS: u32 = 5;
+/* Default maximum number of database connections */
+const DEFAULT_MAX_DB_CONNECTIONS: u32 = 5;
+
+/* Default minimum number of idle database connections */
+const DEFAULT_MIN_DB_CONNECTIONS: u32 = 0;
+
+/* Default time in seconds to wait for a free connection before failing */
+const DEFAULT_ACQUIRE_TIMEOUT_SECS: u64 = 30;
 
 /*
 * Initializes the database connection and creates all required tables.
+*
+* Pool sizing is read from DB_MAX_CONNECTIONS, DB_MIN_CONNECTIONS and
+* DB_ACQUIRE_TIMEOUT_SECS, falling back to the defaults when unset or invalid.
 */
 pub async fn init_db(database_url: &str) -> Result<Pool<Postgres>, sqlx::Error> {
+    let max_connections = env_or_default("DB_MAX_CONNECTIONS", DEFAULT_MAX_DB_CONNECTIONS);
+    let min_connections = env_or_default("DB_MIN_CONNECTIONS", DEFAULT_MIN_DB_CONNECTIONS);
+    let acquire_timeout = env_or_default("DB_ACQUIRE_TIMEOUT_SECS", DEFAULT_ACQUIRE_TIMEOUT_SECS);
+
     // Create and configure the connection pool
     let pool = sqlx::postgres::PgPoolOptions::new()
-        .max_connections(MAX_DB_CONNECTIONS)
+        .max_connections(max_connections)
+        .min_connections(min_connections.min(max_connections))
+        .acquire_timeout(Duration::from_secs(acquire_timeout))
         .connect(database_url)
         .await?;
 
@@ -27,3 +50,143 @@ pub async fn init_db(database_url: &str) -> Result<Pool<Postgres>, sqlx::Error>
 
     Ok(pool)
 }
+
+/*
+* Verifies that the database is reachable by running a trivial query.
+*/
+pub async fn ping(pool: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT 1")
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/*
+* Deletes blocks and their transactions older than a cutoff.
+*
+* Transactions go first since they reference their block, and both deletes
+* commit together so no block is ever left without its transactions.
+*
+* @param pool Database connection pool
+* @param cutoff Rows strictly before this time are removed
+* @return Numbers of deleted transactions and blocks
+*/
+pub async fn prune_before(
+    pool: &Pool<Postgres>,
+    cutoff: DateTime<Utc>,
+) -> Result<(u64, u64), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let transactions = transactions::delete_transactions_before(&mut tx, cutoff).await?;
+    let blocks = blocks::delete_blocks_before(&mut tx, cutoff).await?;
+    tx.commit().await?;
+
+    Ok((transactions, blocks))
+}
+
+/*
+* Subscribes to the heights of newly stored blocks.
+*
+* Listens on the channel store_block notifies, so blocks written by any
+* indexer instance sharing the database are seen. The listener holds its
+* own connection outside the pool and reconnects after it is lost;
+* notifications sent while disconnected are missed.
+*
+* @param pool Database connection pool the listener connects like
+* @return Stream of block heights in commit order
+*/
+pub async fn listen_for_blocks(
+    pool: &Pool<Postgres>,
+) -> Result<impl Stream<Item = Result<i64, sqlx::Error>>, sqlx::Error> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(blocks::NEW_BLOCK_CHANNEL).await?;
+
+    Ok(listener.into_stream().map(|notification| {
+        let notification = notification?;
+        notification.payload()
+            .parse::<i64>()
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+    }))
+}
+
+/*
+* Reads and parses an environment variable, falling back to a default.
+*
+* Logs a warning when the variable is set but cannot be parsed.
+*/
+fn env_or_default<T: FromStr + Copy + std::fmt::Display>(name: &str, default: T) -> T {
+    match env::var(name) {
+        Ok(value) => value.parse().unwrap_or_else(|_| {
+            warn!("Invalid value {:?} for {}, using default: {}", value, name, default);
+            default
+        }),
+        Err(_) => default,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{amount::TokenAmount, StoredBlock};
+
+    /* Heights far above any real chain, so the test never touches indexed blocks */
+    const ROLLED_BACK_HEIGHT: i64 = 9_000_000_000_000;
+    const COMMITTED_HEIGHT: i64 = 9_000_000_000_001;
+
+    fn block(height: i64) -> StoredBlock {
+        StoredBlock {
+            height,
+            time: Utc::now(),
+            hash: format!("{:064X}", height),
+            proposer_address: "PROPOSER".to_string(),
+            tx_count: 0,
+            previous_block_hash: None,
+            burn_amount: TokenAmount::default(),
+            block_size_bytes: None,
+            data: serde_json::Value::Null,
+            action_counts: None,
+            has_swaps: false,
+            has_transfers: false,
+            created_at: Utc::now(),
+            updated_at: None,
+        }
+    }
+
+    async fn delete_test_blocks(pool: &Pool<Postgres>) {
+        sqlx::query("DELETE FROM blocks WHERE height IN ($1, $2)")
+            .bind(ROLLED_BACK_HEIGHT)
+            .bind(COMMITTED_HEIGHT)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL database in TEST_DATABASE_URL"]
+    async fn storing_a_block_notifies_listeners_once_committed() {
+        let pool = init_db(&env::var("TEST_DATABASE_URL").unwrap()).await.unwrap();
+        delete_test_blocks(&pool).await;
+        let mut heights = Box::pin(listen_for_blocks(&pool).await.unwrap());
+
+        let mut tx = pool.begin().await.unwrap();
+        blocks::store_block(&mut tx, &block(ROLLED_BACK_HEIGHT), false).await.unwrap();
+        tx.rollback().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        blocks::store_block(&mut tx, &block(COMMITTED_HEIGHT), false).await.unwrap();
+        tx.commit().await.unwrap();
+
+        // Other writers may share the database, so skip heights this test did not store
+        let notified = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let height = heights.next().await.unwrap().unwrap();
+                if height == ROLLED_BACK_HEIGHT || height == COMMITTED_HEIGHT {
+                    return height;
+                }
+            }
+        }).await;
+
+        delete_test_blocks(&pool).await;
+        assert_eq!(notified.expect("no notification within 5 seconds"), COMMITTED_HEIGHT);
+    }
+}
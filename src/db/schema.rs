@@ -3,9 +3,24 @@
 *
 * Contains all table definitions and handles schema migrations
 * or updates. Keeps database structure separate from operations.
+*
+* Table changes are applied as ordered, versioned migrations recorded
+* in the schema_migrations table, so each one runs exactly once.
 */
 
 use sqlx::{Pool, Postgres};
+use tracing::info;
+
+/* SQL definitions for the migration bookkeeping table */
+pub const SCHEMA_MIGRATIONS_TABLE_SQL: &str = r#"
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        version INT PRIMARY KEY,
+        applied_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+    )
+"#;
+
+/* Advisory lock key serializing migrations across indexer instances */
+const MIGRATION_LOCK_KEY: i64 = 0x7065_6e75_6d62_7261;
 
 /* SQL definitions for the blocks table */
 pub const BLOCKS_TABLE_SQL: &str = r#"
@@ -22,6 +37,23 @@ pub const BLOCKS_TABLE_SQL: &str = r#"
     )
 "#;
 
+/* SQL for adding the block size column to the blocks table */
+pub const ADD_BLOCK_SIZE_COLUMN_SQL: &str = r#"
+    ALTER TABLE blocks ADD COLUMN IF NOT EXISTS block_size_bytes BIGINT
+"#;
+
+/* SQL for adding the action metadata column to the transactions table */
+pub const ADD_TRANSACTION_METADATA_COLUMN_SQL: &str = r#"
+    ALTER TABLE transactions ADD COLUMN IF NOT EXISTS metadata JSONB
+"#;
+
+/* SQL for adding the compressed block payload column and relaxing the JSONB one */
+pub const ADD_BLOCK_DATA_COMPRESSED_COLUMN_SQL: &str = r#"
+    ALTER TABLE blocks
+        ADD COLUMN IF NOT EXISTS data_compressed BYTEA,
+        ALTER COLUMN data DROP NOT NULL
+"#;
+
 /* SQL definitions for the transactions table */
 pub const TRANSACTIONS_TABLE_SQL: &str = r#"
     CREATE TABLE IF NOT EXISTS transactions (
@@ -36,18 +68,155 @@ pub const TRANSACTIONS_TABLE_SQL: &str = r#"
     )
 "#;
 
-/* SQL definitions for the daily statistics materialized view */
+/* SQL definitions for the sync status snapshot table */
+pub const SYNC_STATUS_TABLE_SQL: &str = r#"
+    CREATE TABLE IF NOT EXISTS sync_status (
+        id SERIAL PRIMARY KEY,
+        observed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        chain_height BIGINT NOT NULL,
+        indexed_height BIGINT NOT NULL
+    )
+"#;
+
+/* SQL for adding the per-block action type counts column */
+pub const ADD_BLOCK_ACTION_COUNTS_COLUMN_SQL: &str = r#"
+    ALTER TABLE blocks ADD COLUMN IF NOT EXISTS action_counts JSONB
+"#;
+
+/* SQL adding the amount denomination to transactions */
+pub const ADD_TRANSACTION_DENOM_COLUMN_SQL: &str = r#"
+    ALTER TABLE transactions ADD COLUMN IF NOT EXISTS denom TEXT
+"#;
+
+/* SQL filling in the denomination of stored transfers from their metadata */
+pub const BACKFILL_TRANSACTION_DENOM_SQL: &str = r#"
+    UPDATE transactions
+    SET denom = CASE WHEN metadata->>'denom' = 'upenumbra' THEN 'penumbra' ELSE metadata->>'denom' END
+    WHERE denom IS NULL AND amount IS NOT NULL AND metadata->>'denom' IS NOT NULL
+"#;
+
+/* SQL adding the swap and transfer flags to blocks */
+pub const ADD_BLOCK_HAS_SWAPS_COLUMN_SQL: &str = r#"
+    ALTER TABLE blocks ADD COLUMN IF NOT EXISTS has_swaps BOOLEAN NOT NULL DEFAULT FALSE
+"#;
+
+pub const ADD_BLOCK_HAS_TRANSFERS_COLUMN_SQL: &str = r#"
+    ALTER TABLE blocks ADD COLUMN IF NOT EXISTS has_transfers BOOLEAN NOT NULL DEFAULT FALSE
+"#;
+
+/* SQL deriving the swap and transfer flags of existing blocks from their action counts */
+pub const BACKFILL_BLOCK_FLAGS_SQL: &str = r#"
+    UPDATE blocks
+    SET has_swaps = action_counts ?| array['swap', 'swap_claim'],
+        has_transfers = action_counts ?| array['spend', 'output', 'ibc_transfer']
+    WHERE action_counts IS NOT NULL
+"#;
+
+/* SQL adding the time a block row was last overwritten */
+pub const ADD_BLOCK_UPDATED_AT_COLUMN_SQL: &str = r#"
+    ALTER TABLE blocks ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP WITH TIME ZONE
+"#;
+
+/* SQL adding the fee paid by each transaction */
+pub const ADD_TRANSACTION_FEE_COLUMNS_SQL: &str = r#"
+    ALTER TABLE transactions
+        ADD COLUMN IF NOT EXISTS fee_amount DOUBLE PRECISION,
+        ADD COLUMN IF NOT EXISTS fee_denom TEXT
+"#;
+
+/* SQL storing transaction amounts as exact integers; existing values keep their rounding */
+pub const TRANSACTION_AMOUNTS_TO_NUMERIC_SQL: &str = r#"
+    ALTER TABLE transactions
+        ALTER COLUMN amount TYPE NUMERIC(39,0) USING round(amount)::NUMERIC(39,0),
+        ALTER COLUMN fee_amount TYPE NUMERIC(39,0) USING round(fee_amount)::NUMERIC(39,0)
+"#;
+
+/* SQL storing block burn amounts as exact integers */
+pub const BLOCK_BURN_AMOUNT_TO_NUMERIC_SQL: &str = r#"
+    ALTER TABLE blocks
+        ALTER COLUMN burn_amount TYPE NUMERIC(39,0) USING round(burn_amount)::NUMERIC(39,0)
+"#;
+
+/* SQL for dropping the daily statistics view before its definition changes */
+pub const DROP_DAILY_STATS_VIEW_SQL: &str = r#"
+    DROP MATERIALIZED VIEW IF EXISTS daily_stats
+"#;
+
+/* SQL definitions for the daily statistics materialized view, filled by a later refresh */
 pub const DAILY_STATS_VIEW_SQL: &str = r#"
-    CREATE MATERIALIZED VIEW IF NOT EXISTS daily_stats AS
+    CREATE MATERIALIZED VIEW daily_stats AS
     SELECT
         date_trunc('day', time) as date,
-        COUNT(*) as tx_count,
+        COUNT(*) as block_count,
+        SUM(tx_count) as tx_count,
         SUM(burn_amount) as total_burn
     FROM blocks
     GROUP BY date_trunc('day', time)
     ORDER BY date_trunc('day', time)
+    WITH NO DATA
+"#;
+
+/* SQL for the unique index concurrent refreshes of the daily statistics view need */
+pub const DAILY_STATS_DATE_INDEX_SQL: &str = r#"
+    CREATE UNIQUE INDEX IF NOT EXISTS idx_daily_stats_date ON daily_stats(date)
+"#;
+
+/* SQL for populating the daily statistics view once it is created */
+pub const REFRESH_DAILY_STATS_VIEW_SQL: &str = r#"
+    REFRESH MATERIALIZED VIEW daily_stats
+"#;
+
+/* SQL definitions for the persisted indexer state table */
+pub const INDEXER_STATE_TABLE_SQL: &str = r#"
+    CREATE TABLE IF NOT EXISTS indexer_state (
+        key TEXT PRIMARY KEY,
+        value BIGINT NOT NULL,
+        updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+    )
+"#;
+
+/* SQL definitions for the table of blocks that exhausted their retries */
+pub const FAILED_BLOCKS_TABLE_SQL: &str = r#"
+    CREATE TABLE IF NOT EXISTS failed_blocks (
+        height BIGINT PRIMARY KEY,
+        last_error TEXT NOT NULL,
+        attempts INT NOT NULL,
+        last_attempt TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+    )
 "#;
 
+/*
+* Ordered schema migrations as (version, SQL) pairs.
+*
+* Versions must be strictly increasing. Never edit an applied migration;
+* append a new one instead.
+*/
+pub const MIGRATIONS: &[(i32, &str)] = &[
+    (1, BLOCKS_TABLE_SQL),
+    (2, TRANSACTIONS_TABLE_SQL),
+    (3, ADD_BLOCK_SIZE_COLUMN_SQL),
+    (4, ADD_TRANSACTION_METADATA_COLUMN_SQL),
+    (5, SYNC_STATUS_TABLE_SQL),
+    (6, ADD_BLOCK_DATA_COMPRESSED_COLUMN_SQL),
+    (7, INDEXER_STATE_TABLE_SQL),
+    (8, ADD_BLOCK_ACTION_COUNTS_COLUMN_SQL),
+    (9, ADD_TRANSACTION_DENOM_COLUMN_SQL),
+    (10, BACKFILL_TRANSACTION_DENOM_SQL),
+    (11, FAILED_BLOCKS_TABLE_SQL),
+    (12, ADD_BLOCK_HAS_SWAPS_COLUMN_SQL),
+    (13, ADD_BLOCK_HAS_TRANSFERS_COLUMN_SQL),
+    (14, BACKFILL_BLOCK_FLAGS_SQL),
+    (15, ADD_BLOCK_UPDATED_AT_COLUMN_SQL),
+    (16, ADD_TRANSACTION_FEE_COLUMNS_SQL),
+    (17, TRANSACTION_AMOUNTS_TO_NUMERIC_SQL),
+    // The view reads burn_amount, so it is dropped before the column changes type
+    (18, DROP_DAILY_STATS_VIEW_SQL),
+    (19, BLOCK_BURN_AMOUNT_TO_NUMERIC_SQL),
+    (20, DAILY_STATS_VIEW_SQL),
+    (21, DAILY_STATS_DATE_INDEX_SQL),
+    (22, REFRESH_DAILY_STATS_VIEW_SQL),
+];
+
 /*
 * Initializes or upgrades the database schema.
 *
@@ -58,22 +227,64 @@ pub const DAILY_STATS_VIEW_SQL: &str = r#"
 * @return Result indicating success or failure
 */
 pub async fn initialize_schema(pool: &Pool<Postgres>) -> Result<(), sqlx::Error> {
-    // Create tables in the proper order (referenced tables first)
-    sqlx::query(BLOCKS_TABLE_SQL)
-        .execute(pool)
-        .await?;
+    // Create and upgrade tables and the statistics view in migration order
+    run_migrations(pool).await?;
 
-    sqlx::query(TRANSACTIONS_TABLE_SQL)
-        .execute(pool)
-        .await?;
+    // Create any necessary indices for better query performance
+    create_indices(pool).await?;
 
-    // Create materialized view for statistics
-    sqlx::query(DAILY_STATS_VIEW_SQL)
+    Ok(())
+}
+
+/*
+* Applies all pending schema migrations in version order.
+*
+* Each migration runs in its own transaction together with its
+* schema_migrations record, so a failed migration leaves no trace and
+* is retried on the next startup. Already applied versions are skipped.
+*
+* @param pool PostgreSQL connection pool
+* @return Result indicating success or failure
+*/
+pub async fn run_migrations(pool: &Pool<Postgres>) -> Result<(), sqlx::Error> {
+    sqlx::query(SCHEMA_MIGRATIONS_TABLE_SQL)
         .execute(pool)
         .await?;
 
-    // Create any necessary indices for better query performance
-    create_indices(pool).await?;
+    for (version, sql) in MIGRATIONS {
+        let mut tx = pool.begin().await?;
+
+        // Hold the lock until commit so concurrent instances apply each version once
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(MIGRATION_LOCK_KEY)
+            .execute(&mut *tx)
+            .await?;
+
+        let applied = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM schema_migrations WHERE version = $1)"
+        )
+            .bind(version)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        if applied {
+            tx.rollback().await?;
+            continue;
+        }
+
+        info!(version, "Applying schema migration");
+
+        sqlx::query(sql)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("INSERT INTO schema_migrations (version) VALUES ($1)")
+            .bind(version)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+    }
 
     Ok(())
 }
@@ -108,9 +319,23 @@ async fn create_indices(pool: &Pool<Postgres>) -> Result<(), sqlx::Error> {
         .execute(pool)
         .await?;
 
-    // Unique index for daily stats materialized view
+    // Index for paging through a proposer's blocks
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_blocks_proposer_height ON blocks(proposer_address, height DESC)"
+    )
+        .execute(pool)
+        .await?;
+
+    // Index for listing the blocks with the most transactions
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_blocks_tx_count ON blocks(tx_count DESC, height DESC)"
+    )
+        .execute(pool)
+        .await?;
+
+    // Index for case-insensitive proposer address prefix searches
     sqlx::query(
-        "CREATE UNIQUE INDEX IF NOT EXISTS idx_daily_stats_date ON daily_stats(date)"
+        "CREATE INDEX IF NOT EXISTS idx_blocks_proposer_prefix ON blocks(upper(proposer_address) text_pattern_ops)"
     )
         .execute(pool)
         .await?;
@@ -6,20 +6,26 @@
 */
 
 use chrono::{DateTime, Utc};
-use sqlx::{Pool, Postgres};
-use crate::models::Transaction;
+use sqlx::{PgConnection, Pool, Postgres};
+use crate::models::{amount::TokenAmount, transaction::{NetDelegation, SortOrder}, Transaction};
 
 /* SQL queries for transactions */
 
 /* SQL for inserting a new transaction */
 const INSERT_TRANSACTION_SQL: &str = r#"
     INSERT INTO transactions (
-        tx_hash, block_height, time, action_type, amount, data, created_at
+        tx_hash, block_height, time, action_type, amount, denom, fee_amount, fee_denom, data, metadata, created_at
     )
-    VALUES ($1, $2, $3, $4, $5, $6, $7)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
     ON CONFLICT (tx_hash) DO NOTHING
 "#;
 
+/* SQL for retrieving a transaction by its hash */
+const GET_TRANSACTION_BY_HASH_SQL: &str = r#"
+    SELECT * FROM transactions
+    WHERE tx_hash = $1
+"#;
+
 /* SQL for retrieving transactions by block height */
 const GET_TRANSACTIONS_BY_BLOCK_HEIGHT_SQL: &str = r#"
     SELECT * FROM transactions
@@ -27,32 +33,123 @@ const GET_TRANSACTIONS_BY_BLOCK_HEIGHT_SQL: &str = r#"
     ORDER BY id ASC
 "#;
 
+/* SQL for retrieving a page of a block's transactions */
+const GET_TRANSACTIONS_BY_BLOCK_HEIGHT_PAGED_SQL: &str = r#"
+    SELECT * FROM transactions
+    WHERE block_height = $1
+    ORDER BY id ASC
+    LIMIT $2 OFFSET $3
+"#;
+
+/* SQL for counting the transactions of a block */
+const COUNT_TRANSACTIONS_BY_BLOCK_HEIGHT_SQL: &str = r#"
+    SELECT COUNT(*) FROM transactions
+    WHERE block_height = $1
+"#;
+
+/* SQL for retrieving the latest transactions of any of a set of action types */
+const GET_TRANSACTIONS_BY_ACTION_SQL: &str = r#"
+    SELECT * FROM transactions
+    WHERE action_type = ANY($1)
+    ORDER BY block_height DESC, id ASC
+    LIMIT $2 OFFSET $3
+"#;
+
+/* SQL for counting transactions of any of a set of action types */
+const COUNT_TRANSACTIONS_BY_ACTION_SQL: &str = r#"
+    SELECT COUNT(*) FROM transactions
+    WHERE action_type = ANY($1)
+"#;
+
+/* SQL for summing delegations and undelegations per validator */
+const GET_NET_DELEGATIONS_SQL: &str = r#"
+    SELECT
+        metadata->>'validator_identity' AS validator_identity,
+        -- Rendered as text so the signed NUMERIC total reaches the API exactly
+        COALESCE(SUM(CASE WHEN action_type = $1 THEN amount ELSE -amount END), 0)::TEXT AS net_amount,
+        COUNT(*) FILTER (WHERE action_type = $1) AS delegations,
+        COUNT(*) FILTER (WHERE action_type = $2) AS undelegations
+    FROM transactions
+    WHERE action_type IN ($1, $2)
+        AND metadata->>'validator_identity' IS NOT NULL
+    GROUP BY metadata->>'validator_identity'
+    ORDER BY COALESCE(SUM(CASE WHEN action_type = $1 THEN amount ELSE -amount END), 0) DESC
+    LIMIT $3
+"#;
+
+/* SQL for retrieving the largest transactions at or above an amount */
+const GET_TRANSACTIONS_ABOVE_AMOUNT_SQL: &str = r#"
+    SELECT * FROM transactions
+    WHERE amount IS NOT NULL AND amount >= $1
+    ORDER BY amount DESC, block_height DESC
+    LIMIT $2 OFFSET $3
+"#;
+
+/* SQL for counting transactions at or above an amount */
+const COUNT_TRANSACTIONS_ABOVE_AMOUNT_SQL: &str = r#"
+    SELECT COUNT(*) FROM transactions
+    WHERE amount IS NOT NULL AND amount >= $1
+"#;
+
+/* SQL for counting all indexed transactions */
+const COUNT_TRANSACTIONS_SQL: &str = r#"
+    SELECT COUNT(*) FROM transactions
+"#;
+
+/* SQL for deleting all transactions of a block */
+const DELETE_TRANSACTIONS_BY_BLOCK_HEIGHT_SQL: &str = r#"
+    DELETE FROM transactions
+    WHERE block_height = $1
+"#;
+
+/* SQL for deleting transactions older than a cutoff */
+const DELETE_TRANSACTIONS_BEFORE_SQL: &str = r#"
+    DELETE FROM transactions
+    WHERE time < $1
+"#;
+
 /* SQL for retrieving the latest transactions */
 const GET_LATEST_TRANSACTIONS_SQL: &str = r#"
     SELECT * FROM transactions
     ORDER BY block_height DESC, id ASC
-    LIMIT $1
+    LIMIT $1 OFFSET $2
+"#;
+
+/* SQL for retrieving the oldest transactions */
+const GET_OLDEST_TRANSACTIONS_SQL: &str = r#"
+    SELECT * FROM transactions
+    ORDER BY block_height ASC, id ASC
+    LIMIT $1 OFFSET $2
 "#;
 
 /*
 * Stores a transaction in the database.
 *
-* @param pool Database connection pool
+* @param conn Database connection, usually an open transaction
 * @param tx_hash Transaction hash identifier
 * @param block_height Block height containing this transaction
 * @param time Transaction timestamp
 * @param action_type Type of transaction action
 * @param amount Optional transaction amount
+* @param denom Denomination of the amount, "penumbra" for UM
+* @param fee_amount Optional fee paid by the transaction
+* @param fee_denom Denomination of the fee
 * @param data Transaction data (usually base64-encoded)
+* @param metadata Optional action-specific details
 */
+#[allow(clippy::too_many_arguments)]
 pub async fn store_transaction(
-    pool: &Pool<Postgres>,
+    conn: &mut PgConnection,
     tx_hash: &str,
     block_height: i64,
     time: DateTime<Utc>,
     action_type: &str,
-    amount: Option<f64>,
+    amount: Option<TokenAmount>,
+    denom: Option<&str>,
+    fee_amount: Option<TokenAmount>,
+    fee_denom: Option<&str>,
     data: &str,
+    metadata: Option<&serde_json::Value>,
 ) -> Result<(), sqlx::Error> {
     sqlx::query(INSERT_TRANSACTION_SQL)
         .bind(tx_hash)
@@ -60,9 +157,13 @@ pub async fn store_transaction(
         .bind(time)
         .bind(action_type)
         .bind(amount)
+        .bind(denom)
+        .bind(fee_amount)
+        .bind(fee_denom)
         .bind(data)
+        .bind(metadata)
         .bind(Utc::now())
-        .execute(pool)
+        .execute(conn)
         .await?;
 
     Ok(())
@@ -73,18 +174,45 @@ pub async fn store_transaction(
 *
 * @param pool Database connection pool
 * @param limit Maximum number of transactions to retrieve
+* @param offset Number of transactions to skip
+* @param order Block height ordering; ascending returns the oldest transactions
 * @return Vector of transaction data
 */
 pub async fn get_latest_transactions(
     pool: &Pool<Postgres>,
     limit: i64,
+    offset: i64,
+    order: SortOrder,
 ) -> Result<Vec<Transaction>, sqlx::Error> {
-    sqlx::query_as::<_, Transaction>(GET_LATEST_TRANSACTIONS_SQL)
+    let sql = match order {
+        SortOrder::Asc => GET_OLDEST_TRANSACTIONS_SQL,
+        SortOrder::Desc => GET_LATEST_TRANSACTIONS_SQL,
+    };
+
+    sqlx::query_as::<_, Transaction>(sql)
         .bind(limit)
+        .bind(offset)
         .fetch_all(pool)
         .await
 }
 
+/*
+* Retrieves a transaction by its hash.
+*
+* @param pool Database connection pool
+* @param tx_hash Transaction hash to query
+* @return The transaction if it exists
+*/
+pub async fn get_transaction_by_hash(
+    pool: &Pool<Postgres>,
+    tx_hash: &str,
+) -> Result<Option<Transaction>, sqlx::Error> {
+    sqlx::query_as::<_, Transaction>(GET_TRANSACTION_BY_HASH_SQL)
+        .bind(tx_hash)
+        .fetch_optional(pool)
+        .await
+}
+
 /*
 * Retrieves transactions for a specific block height.
 *
@@ -101,3 +229,200 @@ pub async fn get_transactions_by_block_height(
         .fetch_all(pool)
         .await
 }
+
+/*
+* Retrieves a page of the transactions for a specific block height.
+*
+* @param pool Database connection pool
+* @param height Block height to query
+* @param limit Maximum number of transactions to retrieve
+* @param offset Number of transactions to skip
+* @return Vector of the block's transactions in storage order
+*/
+pub async fn get_transactions_by_block_height_paged(
+    pool: &Pool<Postgres>,
+    height: i64,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Transaction>, sqlx::Error> {
+    sqlx::query_as::<_, Transaction>(GET_TRANSACTIONS_BY_BLOCK_HEIGHT_PAGED_SQL)
+        .bind(height)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+}
+
+/*
+* Counts the transactions stored for a block.
+*
+* @param pool Database connection pool
+* @param height Block height to count
+* @return Number of transactions at the given height
+*/
+pub async fn count_transactions_by_block_height(
+    pool: &Pool<Postgres>,
+    height: i64,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(COUNT_TRANSACTIONS_BY_BLOCK_HEIGHT_SQL)
+        .bind(height)
+        .fetch_one(pool)
+        .await
+}
+
+/*
+* Deletes all transactions belonging to a block.
+*
+* Used when a block is replaced after a chain reorganization.
+*
+* @param conn Database connection, usually an open transaction
+* @param height Block height whose transactions are removed
+* @return Number of deleted transactions
+*/
+pub async fn delete_transactions_by_block_height(
+    conn: &mut PgConnection,
+    height: i64,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(DELETE_TRANSACTIONS_BY_BLOCK_HEIGHT_SQL)
+        .bind(height)
+        .execute(conn)
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+/*
+* Deletes all transactions older than a cutoff.
+*
+* @param conn Database connection, usually an open transaction
+* @param cutoff Transactions strictly before this time are removed
+* @return Number of deleted transactions
+*/
+pub async fn delete_transactions_before(
+    conn: &mut PgConnection,
+    cutoff: DateTime<Utc>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(DELETE_TRANSACTIONS_BEFORE_SQL)
+        .bind(cutoff)
+        .execute(conn)
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+/*
+* Counts all indexed transactions.
+*
+* @param pool Database connection pool
+* @return Total number of transactions in the database
+*/
+pub async fn count_transactions(
+    pool: &Pool<Postgres>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(COUNT_TRANSACTIONS_SQL)
+        .fetch_one(pool)
+        .await
+}
+
+/*
+* Retrieves the latest transactions of any of a set of action types.
+*
+* @param pool Database connection pool
+* @param action_types Normalized action types, e.g. ["ibc_transfer"]
+* @param limit Maximum number of transactions to retrieve
+* @param offset Number of transactions to skip
+* @return Vector of matching transactions
+*/
+pub async fn get_transactions_by_action(
+    pool: &Pool<Postgres>,
+    action_types: &[&str],
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Transaction>, sqlx::Error> {
+    sqlx::query_as::<_, Transaction>(GET_TRANSACTIONS_BY_ACTION_SQL)
+        .bind(action_types)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+}
+
+/*
+* Counts transactions of any of a set of action types.
+*
+* @param pool Database connection pool
+* @param action_types Normalized action types, e.g. ["ibc_transfer"]
+* @return Number of matching transactions
+*/
+pub async fn count_transactions_by_action(
+    pool: &Pool<Postgres>,
+    action_types: &[&str],
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(COUNT_TRANSACTIONS_BY_ACTION_SQL)
+        .bind(action_types)
+        .fetch_one(pool)
+        .await
+}
+
+/*
+* Sums the UM delegated to each validator, net of undelegations.
+*
+* @param pool Database connection pool
+* @param delegate_action Action type stored for delegations
+* @param undelegate_action Action type stored for undelegations
+* @param limit Maximum number of validators to return
+* @return Net delegations ordered by net amount, largest first
+*/
+pub async fn get_net_delegations(
+    pool: &Pool<Postgres>,
+    delegate_action: &str,
+    undelegate_action: &str,
+    limit: i64,
+) -> Result<Vec<NetDelegation>, sqlx::Error> {
+    sqlx::query_as::<_, NetDelegation>(GET_NET_DELEGATIONS_SQL)
+        .bind(delegate_action)
+        .bind(undelegate_action)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+}
+
+/*
+* Retrieves the largest transactions with an amount at or above a threshold.
+*
+* @param pool Database connection pool
+* @param min_amount Minimum amount in base units
+* @param limit Maximum number of transactions to retrieve
+* @param offset Number of transactions to skip
+* @return Vector of matching transactions ordered by amount, largest first
+*/
+pub async fn get_transactions_above_amount(
+    pool: &Pool<Postgres>,
+    min_amount: TokenAmount,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Transaction>, sqlx::Error> {
+    sqlx::query_as::<_, Transaction>(GET_TRANSACTIONS_ABOVE_AMOUNT_SQL)
+        .bind(min_amount)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+}
+
+/*
+* Counts transactions with an amount at or above a threshold.
+*
+* @param pool Database connection pool
+* @param min_amount Minimum amount in base units
+* @return Number of matching transactions
+*/
+pub async fn count_transactions_above_amount(
+    pool: &Pool<Postgres>,
+    min_amount: TokenAmount,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar::<_, i64>(COUNT_TRANSACTIONS_ABOVE_AMOUNT_SQL)
+        .bind(min_amount)
+        .fetch_one(pool)
+        .await
+}
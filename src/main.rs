@@ -3,7 +3,9 @@
  *
  * Coordinates initialization and startup of core services:
  * - Environment configuration
+ * - Logging
  * - Database connection
+ * - Metrics recorder
  * - API server
  * - Block indexing client
  *
@@ -15,18 +17,82 @@ mod db;
 mod api;
 mod models;
 mod client;
-mod error;
+mod telemetry;
 
 use std::error::Error;
 use std::env;
-use std::time::Duration;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use chrono::Utc;
 use dotenv::dotenv;
 use tokio::net::TcpListener;
+use tokio::sync::broadcast;
 use tokio::time;
-use tracing::{info, error, warn};
-use crate::client::PenumbraClient;
+use metrics::gauge;
+use tracing::{debug, info, error, warn};
+use crate::client::{ClientError, PenumbraClient};
+use crate::client::{last_error::LastErrorTracker, resync, throughput::ThroughputMeter};
+use crate::client::rpc::{RpcConnectionPool, RpcTimeouts, DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_MAX_CONCURRENT_REQUESTS, DEFAULT_POOL_IDLE_TIMEOUT_SECS, DEFAULT_REQUEST_TIMEOUT_SECS};
+use crate::client::sync::{parse_batch_size, DEFAULT_BLOCK_MAX_ATTEMPTS, DEFAULT_STATUS_CACHE_TTL_SECS};
+use crate::db::stats::StatsQueries;
 
-const DEFAULT_BATCH_SIZE: u64 = 100;
+/* Default first height indexed into an empty database */
+const DEFAULT_SYNC_START_HEIGHT: u64 = 1;
+
+/* Number of new blocks buffered per WebSocket subscriber before it is dropped */
+const BLOCK_BROADCAST_CAPACITY: usize = 100;
+
+/* Number of new transactions buffered per SSE subscriber before it is dropped */
+const TRANSACTION_BROADCAST_CAPACITY: usize = 1000;
+
+/* Interval in seconds between node status checks in the synchronization loop */
+const POLL_INTERVAL_SECS: u64 = 1;
+
+/* Default number of API requests per second allowed for each client IP */
+const DEFAULT_API_RATE_LIMIT_PER_SEC: u32 = 20;
+
+/* Number of blocks updated per statement when backfilling action counts */
+const ACTION_COUNTS_BACKFILL_BATCH: i64 = 1000;
+
+/* Time in seconds between passes re-attempting failed blocks */
+const FAILED_BLOCK_RETRY_INTERVAL_SECS: u64 = 300;
+
+/* Default largest API request body in bytes */
+const DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024;
+
+/* Default time in seconds an API request may take before it is aborted */
+const DEFAULT_API_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/* Default number of blocks the indexer trails the chain tip by */
+const DEFAULT_CONFIRMATIONS: u64 = 0;
+
+/* Default number of parallel workers for the initial sync */
+const DEFAULT_SYNC_WORKERS: usize = 1;
+
+/* Default interval in minutes between daily_stats view refreshes */
+const DEFAULT_STATS_REFRESH_MINUTES: u64 = 5;
+
+/* Default number of node reachability checks before startup fails */
+const DEFAULT_RPC_STARTUP_ATTEMPTS: u32 = 10;
+
+/* Default time in seconds allowed for each node reachability check */
+const DEFAULT_RPC_STARTUP_TIMEOUT_SECS: u64 = 5;
+
+/* Time in seconds between passes deleting data older than PRUNE_BEFORE_DAYS */
+const PRUNE_INTERVAL_SECS: u64 = 3600;
+
+/*
+ * How the indexer runs once started.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncMode {
+    /// Sync up to the chain tip, then follow new blocks forever
+    Follow,
+    /// Index a fixed height range, then exit
+    Backfill,
+    /// Compare a height range of stored blocks with the chain, then exit
+    Verify,
+}
 
 /*
  * Main application entry point.
@@ -35,45 +101,301 @@ const DEFAULT_BATCH_SIZE: u64 = 100;
  * 1. Initialize logging
  * 2. Load configuration
  * 3. Connect to database
- * 4. Start API server
- * 5. Start block indexing process
+ * 4. Install metrics recorder
+ * 5. Wait for the Penumbra node to be reachable
+ * 6. Start API server
+ * 7. Start block indexing process
  */
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
-    tracing_subscriber::fmt::init();
-    println!("Starting Penumbra Indexer...");
-
     dotenv().ok();
+    telemetry::init_logging();
+    info!("Starting Penumbra Indexer...");
 
     let database_url = env::var("DB_URL").expect("DB_URL must be set");
-    println!("Database URL: {}", database_url);
+    info!(database_url = %database_url, "Database URL configured");
 
     let rpc_url = env::var("RPC_URL")
         .unwrap_or_else(|_| {
             let default = "http://grpc.penumbra.silentvalidator.com:26657".to_string();
-            println!("RPC_URL not set, using default: {}", default);
+            info!("RPC_URL not set, using default: {}", default);
             default
         });
 
     let api_port = env::var("API_PORT")
         .unwrap_or_else(|_| {
-            println!("API_PORT not set, using default: 3000");
+            info!("API_PORT not set, using default: 3000");
             "3000".to_string()
         })
         .parse::<u16>()
         .expect("API_PORT must be a valid port number");
 
-    let batch_size = env::var("BATCH_SIZE")
+    let batch_size = parse_batch_size(env::var("BATCH_SIZE").ok().as_deref());
+
+    let sync_start_height = match env::var("SYNC_START_HEIGHT") {
+        Ok(value) => value.parse::<u64>().map_err(|_| format!("SYNC_START_HEIGHT must be a block height, got {}", value))?,
+        Err(_) => {
+            info!("SYNC_START_HEIGHT not set, using default: {}", DEFAULT_SYNC_START_HEIGHT);
+            DEFAULT_SYNC_START_HEIGHT
+        }
+    };
+    info!(sync_start_height, "Sync start height configured");
+
+    let sync_end_height = match env::var("SYNC_END_HEIGHT") {
+        Ok(value) => Some(value.parse::<u64>().map_err(|_| format!("SYNC_END_HEIGHT must be a block height, got {}", value))?),
+        Err(_) => None,
+    };
+
+    let sync_mode = match env::var("MODE").map(|value| value.to_ascii_lowercase()).as_deref() {
+        Ok("follow") | Err(_) => SyncMode::Follow,
+        Ok("backfill") => SyncMode::Backfill,
+        Ok("verify") => SyncMode::Verify,
+        Ok(other) => return Err(format!("MODE must be follow, backfill or verify, got {}", other).into()),
+    };
+    // Only overwrite stored blocks during verification when explicitly asked to
+    let verify_fix = env::args().any(|arg| arg == "--fix");
+    if sync_mode != SyncMode::Follow {
+        if let Some(end_height) = sync_end_height.filter(|end_height| *end_height < sync_start_height) {
+            return Err(format!(
+                "SYNC_END_HEIGHT {} is below SYNC_START_HEIGHT {}",
+                end_height, sync_start_height
+            ).into());
+        }
+        match sync_mode {
+            SyncMode::Backfill => info!(sync_start_height, sync_end_height = ?sync_end_height, "MODE=backfill, indexing a fixed range then exiting"),
+            _ => info!(sync_start_height, sync_end_height = ?sync_end_height, fix = verify_fix, "MODE=verify, checking a fixed range then exiting"),
+        }
+    }
+
+    let confirmations = env::var("CONFIRMATIONS")
         .unwrap_or_else(|_| {
-            println!("BATCH_SIZE not set, using default: {}", DEFAULT_BATCH_SIZE);
-            DEFAULT_BATCH_SIZE.to_string()
+            info!("CONFIRMATIONS not set, using default: {}", DEFAULT_CONFIRMATIONS);
+            DEFAULT_CONFIRMATIONS.to_string()
         })
         .parse::<u64>()
-        .unwrap_or(DEFAULT_BATCH_SIZE);
+        .unwrap_or(DEFAULT_CONFIRMATIONS);
+
+    let sync_workers = env::var("SYNC_WORKERS")
+        .unwrap_or_else(|_| {
+            info!("SYNC_WORKERS not set, using default: {}", DEFAULT_SYNC_WORKERS);
+            DEFAULT_SYNC_WORKERS.to_string()
+        })
+        .parse::<usize>()
+        .unwrap_or(DEFAULT_SYNC_WORKERS)
+        .max(1);
 
-    println!("Configuration loaded successfully");
+    let stats_refresh_minutes = env::var("STATS_REFRESH_MINUTES")
+        .unwrap_or_else(|_| {
+            info!("STATS_REFRESH_MINUTES not set, using default: {}", DEFAULT_STATS_REFRESH_MINUTES);
+            DEFAULT_STATS_REFRESH_MINUTES.to_string()
+        })
+        .parse::<u64>()
+        .unwrap_or(DEFAULT_STATS_REFRESH_MINUTES)
+        .max(1);
+
+    // Unset or zero keeps all data
+    let prune_before_days = match env::var("PRUNE_BEFORE_DAYS") {
+        Ok(value) => Some(value.parse::<u32>().map_err(|_| format!("PRUNE_BEFORE_DAYS must be a number of days, got {}", value))?)
+            .filter(|days| *days > 0),
+        Err(_) => None,
+    };
+
+    let api_rate_limit_per_sec = env::var("API_RATE_LIMIT_PER_SEC")
+        .unwrap_or_else(|_| {
+            info!("API_RATE_LIMIT_PER_SEC not set, using default: {}", DEFAULT_API_RATE_LIMIT_PER_SEC);
+            DEFAULT_API_RATE_LIMIT_PER_SEC.to_string()
+        })
+        .parse::<u32>()
+        .unwrap_or(DEFAULT_API_RATE_LIMIT_PER_SEC)
+        .max(1);
+
+    let max_body_bytes = env::var("MAX_BODY_BYTES")
+        .unwrap_or_else(|_| {
+            info!("MAX_BODY_BYTES not set, using default: {}", DEFAULT_MAX_BODY_BYTES);
+            DEFAULT_MAX_BODY_BYTES.to_string()
+        })
+        .parse::<usize>()
+        .unwrap_or(DEFAULT_MAX_BODY_BYTES);
+
+    let request_timeout_secs = env::var("REQUEST_TIMEOUT_SECS")
+        .unwrap_or_else(|_| {
+            info!("REQUEST_TIMEOUT_SECS not set, using default: {}", DEFAULT_API_REQUEST_TIMEOUT_SECS);
+            DEFAULT_API_REQUEST_TIMEOUT_SECS.to_string()
+        })
+        .parse::<u64>()
+        .unwrap_or(DEFAULT_API_REQUEST_TIMEOUT_SECS)
+        .max(1);
+
+    let enable_debug_endpoints = env::var("ENABLE_DEBUG_ENDPOINTS")
+        .map(|value| matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+    if enable_debug_endpoints {
+        warn!("ENABLE_DEBUG_ENDPOINTS is set, exposing /api/debug routes");
+    }
+
+    let api_key = env::var("API_KEY").ok().filter(|key| !key.is_empty());
+    if enable_debug_endpoints && api_key.is_none() {
+        warn!("API_KEY not set, /api/admin routes are not mounted");
+    }
+
+    let cors_allowed_origins = env::var("CORS_ALLOWED_ORIGINS")
+        .ok()
+        .map(|value| {
+            value.split(',')
+                .map(|origin| origin.trim().to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect::<Vec<_>>()
+        });
+    match &cors_allowed_origins {
+        Some(origins) => info!(origins = ?origins, "CORS restricted to allowed origins"),
+        None => info!("CORS_ALLOWED_ORIGINS not set, allowing any origin"),
+    }
+
+    // Normalized to a leading slash and no trailing slash; empty or "/" means the root
+    let base_path = env::var("BASE_PATH")
+        .ok()
+        .map(|value| value.trim().trim_matches('/').to_string())
+        .filter(|value| !value.is_empty())
+        .map(|value| format!("/{}", value));
+    match &base_path {
+        Some(base_path) => info!(base_path = %base_path, "Serving the API under a base path"),
+        None => info!("BASE_PATH not set, serving the API at the root"),
+    }
+
+    let compress_block_data = env::var("COMPRESS_BLOCK_DATA")
+        .map(|value| matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+    info!(compress_block_data, "Block payload storage configured");
+
+    let store_raw_block_data = env::var("STORE_RAW_BLOCK_DATA")
+        .map(|value| !matches!(value.to_ascii_lowercase().as_str(), "0" | "false" | "no"))
+        .unwrap_or(true);
+    if !store_raw_block_data {
+        info!("STORE_RAW_BLOCK_DATA is disabled, storing block summaries without their payload");
+    }
+
+    let validate_decode = env::var("VALIDATE_DECODE")
+        .map(|value| matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+    if validate_decode {
+        info!("VALIDATE_DECODE is set, checking every stored transaction against its decoded contents");
+    }
+
+    let verify_block_hashes = env::var("VERIFY_BLOCK_HASHES")
+        .map(|value| matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+    if verify_block_hashes {
+        info!("VERIFY_BLOCK_HASHES is set, checking every block hash against its header");
+    }
+
+    let block_feed_from_db = env::var("BLOCK_FEED_FROM_DB")
+        .map(|value| matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+    if block_feed_from_db {
+        info!("BLOCK_FEED_FROM_DB is set, relaying the block WebSocket feed from database notifications");
+    }
+
+    let batch_commit = env::var("BATCH_COMMIT")
+        .map(|value| matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+    if batch_commit {
+        info!("BATCH_COMMIT is set, committing each sync batch in a single database transaction");
+    }
+
+    let status_cache_ttl_secs = env::var("STATUS_CACHE_TTL")
+        .unwrap_or_else(|_| {
+            info!("STATUS_CACHE_TTL not set, using default: {}", DEFAULT_STATUS_CACHE_TTL_SECS);
+            DEFAULT_STATUS_CACHE_TTL_SECS.to_string()
+        })
+        .parse::<u64>()
+        .unwrap_or(DEFAULT_STATUS_CACHE_TTL_SECS);
+
+    let rpc_timeout_secs = env::var("RPC_TIMEOUT_SECS")
+        .unwrap_or_else(|_| {
+            info!("RPC_TIMEOUT_SECS not set, using default: {}", DEFAULT_REQUEST_TIMEOUT_SECS);
+            DEFAULT_REQUEST_TIMEOUT_SECS.to_string()
+        })
+        .parse::<u64>()
+        .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS)
+        .max(1);
+
+    let rpc_connect_timeout_secs = env::var("RPC_CONNECT_TIMEOUT_SECS")
+        .unwrap_or_else(|_| {
+            info!("RPC_CONNECT_TIMEOUT_SECS not set, using default: {}", DEFAULT_CONNECT_TIMEOUT_SECS);
+            DEFAULT_CONNECT_TIMEOUT_SECS.to_string()
+        })
+        .parse::<u64>()
+        .unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS)
+        .max(1);
+
+    let rpc_timeouts = RpcTimeouts {
+        request: Duration::from_secs(rpc_timeout_secs),
+        connect: Duration::from_secs(rpc_connect_timeout_secs),
+    };
+    info!(rpc_timeout_secs, rpc_connect_timeout_secs, "RPC timeouts configured");
+
+    let block_max_attempts = env::var("BLOCK_MAX_ATTEMPTS")
+        .unwrap_or_else(|_| {
+            info!("BLOCK_MAX_ATTEMPTS not set, using default: {}", DEFAULT_BLOCK_MAX_ATTEMPTS);
+            DEFAULT_BLOCK_MAX_ATTEMPTS.to_string()
+        })
+        .parse::<u32>()
+        .unwrap_or(DEFAULT_BLOCK_MAX_ATTEMPTS)
+        .max(1);
 
-    println!("Waiting for database to be ready...");
+    let rpc_max_concurrent = env::var("RPC_MAX_CONCURRENT")
+        .unwrap_or_else(|_| {
+            info!("RPC_MAX_CONCURRENT not set, using default: {}", DEFAULT_MAX_CONCURRENT_REQUESTS);
+            DEFAULT_MAX_CONCURRENT_REQUESTS.to_string()
+        })
+        .parse::<usize>()
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS)
+        .max(1);
+
+    // One idle connection per request slot lets each batch reuse the previous batch's connections
+    let rpc_pool_max_idle = env::var("RPC_POOL_MAX_IDLE_PER_HOST")
+        .unwrap_or_else(|_| {
+            info!("RPC_POOL_MAX_IDLE_PER_HOST not set, using RPC_MAX_CONCURRENT: {}", rpc_max_concurrent);
+            rpc_max_concurrent.to_string()
+        })
+        .parse::<usize>()
+        .unwrap_or(rpc_max_concurrent);
+
+    let rpc_pool_idle_timeout_secs = env::var("RPC_POOL_IDLE_TIMEOUT_SECS")
+        .unwrap_or_else(|_| {
+            info!("RPC_POOL_IDLE_TIMEOUT_SECS not set, using default: {}", DEFAULT_POOL_IDLE_TIMEOUT_SECS);
+            DEFAULT_POOL_IDLE_TIMEOUT_SECS.to_string()
+        })
+        .parse::<u64>()
+        .unwrap_or(DEFAULT_POOL_IDLE_TIMEOUT_SECS);
+
+    let rpc_pool = RpcConnectionPool {
+        max_idle_per_host: rpc_pool_max_idle,
+        idle_timeout: Duration::from_secs(rpc_pool_idle_timeout_secs),
+    };
+    info!(rpc_pool_max_idle, rpc_pool_idle_timeout_secs, "RPC connection pool configured");
+
+    let rpc_startup_attempts = env::var("RPC_STARTUP_ATTEMPTS")
+        .unwrap_or_else(|_| {
+            info!("RPC_STARTUP_ATTEMPTS not set, using default: {}", DEFAULT_RPC_STARTUP_ATTEMPTS);
+            DEFAULT_RPC_STARTUP_ATTEMPTS.to_string()
+        })
+        .parse::<u32>()
+        .unwrap_or(DEFAULT_RPC_STARTUP_ATTEMPTS)
+        .max(1);
+
+    let rpc_startup_timeout_secs = env::var("RPC_STARTUP_TIMEOUT_SECS")
+        .unwrap_or_else(|_| {
+            info!("RPC_STARTUP_TIMEOUT_SECS not set, using default: {}", DEFAULT_RPC_STARTUP_TIMEOUT_SECS);
+            DEFAULT_RPC_STARTUP_TIMEOUT_SECS.to_string()
+        })
+        .parse::<u64>()
+        .unwrap_or(DEFAULT_RPC_STARTUP_TIMEOUT_SECS)
+        .max(1);
+
+    info!("Configuration loaded successfully");
+
+    info!("Waiting for database to be ready...");
     let mut retry_count = 0;
     let max_retries = 10;
     let mut pool = None;
@@ -82,15 +404,15 @@ async fn main() -> Result<(), Box<dyn Error>> {
         match db::init_db(&database_url).await {
             Ok(p) => {
                 pool = Some(p);
-                println!("✅ Database connection established successfully");
+                info!("✅ Database connection established successfully");
                 break;
             },
             Err(e) => {
                 retry_count += 1;
-                println!("Database connection attempt {}/{} failed: {}", retry_count, max_retries, e);
+                warn!(attempt = retry_count, max_retries, error = %e, "Database connection attempt failed");
                 if retry_count < max_retries {
                     let wait_time = 2 * retry_count;
-                    println!("Retrying in {} seconds...", wait_time);
+                    info!(wait_secs = wait_time, "Retrying database connection");
                     time::sleep(Duration::from_secs(wait_time)).await;
                 }
             }
@@ -100,95 +422,293 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let pool = match pool {
         Some(p) => p,
         None => {
-            println!("❌ Failed to connect to database after {} attempts. Exiting...", max_retries);
+            error!(attempts = max_retries, "❌ Failed to connect to database. Exiting...");
             return Err("Failed to connect to database".into());
         }
     };
 
-    println!("Creating API router...");
-    let app = api::create_router(pool.clone());
+    info!("Installing metrics recorder...");
+    let metrics_handle = telemetry::install_recorder()?;
+
+    let (block_tx, _) = broadcast::channel(BLOCK_BROADCAST_CAPACITY);
+    // With the database relay publishing every stored block, the synchronizer's own publishes would duplicate it
+    let sync_block_tx = if block_feed_from_db {
+        broadcast::channel(1).0
+    } else {
+        block_tx.clone()
+    };
+    let (tx_feed, _) = broadcast::channel(TRANSACTION_BROADCAST_CAPACITY);
+    let throughput = ThroughputMeter::new();
+    let last_error = LastErrorTracker::new();
+
+    info!(rpc_url = %rpc_url, "Connecting to Penumbra node");
+    let client = match PenumbraClient::connect(
+        &rpc_url,
+        pool.clone(),
+        sync_block_tx,
+        rpc_timeouts,
+        rpc_max_concurrent,
+        rpc_pool,
+        Duration::from_secs(status_cache_ttl_secs),
+        compress_block_data,
+        throughput.clone(),
+    ).await {
+        Ok(client) => client
+            .with_decode_validation(validate_decode)
+            .with_batch_commit(batch_commit)
+            .with_raw_block_data(store_raw_block_data)
+            .with_block_hash_verification(verify_block_hashes)
+            .with_error_tracker(last_error.clone())
+            .with_transaction_feed(tx_feed.clone())
+            .with_max_block_attempts(block_max_attempts)
+            .with_confirmations(confirmations),
+        Err(e) => {
+            error!(error = %e, "❌ Failed to create Penumbra client");
+            return Err(e.into());
+        }
+    };
+
+    // Fail here on a wrong RPC_URL rather than deep inside the sync loop
+    info!("Waiting for Penumbra node to be reachable...");
+    let mut rpc_retry_count = 0;
+    loop {
+        match client.check_node(Duration::from_secs(rpc_startup_timeout_secs)).await {
+            Ok(chain_height) => {
+                info!(chain_height, "✅ Connected to Penumbra node");
+                break;
+            }
+            Err(e) => {
+                rpc_retry_count += 1;
+                warn!(attempt = rpc_retry_count, max_retries = rpc_startup_attempts, error = %e, "Penumbra node reachability check failed");
+                if rpc_retry_count >= rpc_startup_attempts {
+                    error!(rpc_url = %rpc_url, attempts = rpc_startup_attempts, "❌ Penumbra node is unreachable. Exiting...");
+                    return Err(format!(
+                        "Penumbra node at {} unreachable after {} attempts: {}",
+                        rpc_url, rpc_startup_attempts, e
+                    ).into());
+                }
+                let wait_time = 2 * rpc_retry_count as u64;
+                info!(wait_secs = wait_time, "Retrying Penumbra node reachability check");
+                time::sleep(Duration::from_secs(wait_time)).await;
+            }
+        }
+    }
+
+    info!("Creating API router...");
+    let api_config = api::ApiConfig {
+        rate_limit_per_sec: api_rate_limit_per_sec,
+        enable_debug_endpoints,
+        api_key,
+        cors_allowed_origins,
+        base_path,
+        max_body_bytes,
+        request_timeout: Duration::from_secs(request_timeout_secs),
+    };
+    let (resync_tx, resync_rx) = resync::channel();
+    let app = api::create_router(pool.clone(), metrics_handle, block_tx.clone(), tx_feed.clone(), throughput.clone(), last_error.clone(), resync_tx, client.chain_tip(), api_config);
+
+    if block_feed_from_db {
+        tokio::spawn(api::routes::ws::relay_stored_blocks(pool.clone(), block_tx.clone()));
+    }
 
-    println!("Starting API server on port {}", api_port);
+    info!(port = api_port, "Starting API server");
     let api_handle = tokio::spawn(async move {
         match TcpListener::bind(("0.0.0.0", api_port)).await {
             Ok(listener) => {
-                println!("API server listening on port {}", api_port);
-                if let Err(e) = axum::serve(listener, app).await {
-                    println!("API server error: {}", e);
+                info!(port = api_port, "API server listening");
+                if let Err(e) = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await {
+                    error!(error = %e, "API server error");
                 }
             },
             Err(e) => {
-                println!("Failed to bind API server to port {}: {}", api_port, e);
+                error!(port = api_port, error = %e, "Failed to bind API server");
             }
         }
     });
 
-    println!("Starting block indexer...");
-    let indexer_handle = tokio::spawn({
+    info!(interval_minutes = stats_refresh_minutes, "Scheduling daily stats refresh");
+    tokio::spawn({
         let pool = pool.clone();
         async move {
-            println!("Connecting to Penumbra node at {}", rpc_url);
-            let client = match PenumbraClient::connect(&rpc_url, pool).await {
-                Ok(client) => {
-                    println!("✅ Connected to Penumbra node");
-                    client
-                },
-                Err(e) => {
-                    println!("❌ Failed to connect to Penumbra node: {}", e);
-                    return;
+            let mut interval = time::interval(Duration::from_secs(stats_refresh_minutes * 60));
+            loop {
+                interval.tick().await;
+                if let Err(e) = StatsQueries::refresh_daily_stats(&pool).await {
+                    error!(error = %e, "Error refreshing daily stats");
                 }
-            };
-
-            println!("Starting blockchain synchronization with batch size: {}", batch_size);
-            if let Err(e) = client.sync_from_genesis(batch_size).await {
-                println!("Error during initial sync: {}", e);
             }
+        }
+    });
 
-            let mut last_processed_block: Option<u64> = None;
+    // Only a following indexer prunes; fixed-range runs would race their own range
+    if let Some(days) = prune_before_days.filter(|_| sync_mode == SyncMode::Follow) {
+        info!(days, interval_secs = PRUNE_INTERVAL_SECS, "Scheduling pruning of old blocks and transactions");
+        tokio::spawn({
+            let pool = pool.clone();
+            async move {
+                let mut interval = time::interval(Duration::from_secs(PRUNE_INTERVAL_SECS));
+                loop {
+                    interval.tick().await;
+                    let cutoff = Utc::now() - chrono::Duration::days(days.into());
+                    match db::prune_before(&pool, cutoff).await {
+                        Ok((0, 0)) => debug!(%cutoff, "Nothing to prune"),
+                        Ok((transactions, blocks)) => {
+                            info!(%cutoff, blocks, transactions, "Pruned old blocks and transactions");
+                            // Drop the pruned days from the daily aggregates right away
+                            if let Err(e) = StatsQueries::refresh_daily_stats(&pool).await {
+                                error!(error = %e, "Error refreshing daily stats after pruning");
+                            }
+                        }
+                        Err(e) => error!(error = %e, "Error pruning old blocks and transactions"),
+                    }
+                }
+            }
+        });
+    }
 
-            println!("Entering synchronization loop");
+    info!("Backfilling block action counts...");
+    tokio::spawn({
+        let pool = pool.clone();
+        async move {
+            let mut backfilled = 0;
             loop {
-                match client.get_status().await {
-                    Ok(status) => {
-                        let latest_height: u64 = status.result.sync_info.latest_block_height
-                            .parse()
-                            .unwrap_or(0);
-
-                        if Some(latest_height) != last_processed_block {
-                            println!("Processing new block at height {}", latest_height);
-                            if let Err(e) = client.fetch_blocks(latest_height, latest_height, 5).await {
-                                println!("Error fetching block {}: {}", latest_height, e);
+                match db::blocks::backfill_action_counts(&pool, ACTION_COUNTS_BACKFILL_BATCH).await {
+                    Ok(0) => break,
+                    Ok(updated) => backfilled += updated,
+                    Err(e) => {
+                        error!(error = %e, "Error backfilling block action counts");
+                        break;
+                    }
+                }
+            }
+            if backfilled > 0 {
+                info!(blocks = backfilled, "Backfilled block action counts");
+            }
+        }
+    });
+
+    // Without a worker the queue is closed and the admin API rejects resyncs
+    if sync_mode == SyncMode::Follow {
+        info!("Starting manual resync worker...");
+        tokio::spawn(resync::run_worker(client.clone(), resync_rx, batch_size));
+    } else {
+        drop(resync_rx);
+    }
+
+    info!("Starting block indexer...");
+    let indexer_handle = tokio::spawn(async move {
+        match sync_mode {
+            SyncMode::Backfill => {
+                return client.backfill(sync_start_height, sync_end_height, batch_size, sync_workers).await;
+            }
+            SyncMode::Verify => {
+                let summary = client.verify_range(sync_start_height, sync_end_height, verify_fix).await?;
+                return match summary.unresolved() {
+                    0 => Ok(()),
+                    unresolved => Err(ClientError::VerifyMismatch { unresolved }),
+                };
+            }
+            SyncMode::Follow => {}
+        }
+
+        info!(batch_size, sync_workers, "Starting blockchain synchronization");
+        if let Err(e) = client.sync_from_genesis(batch_size, sync_start_height, sync_workers).await {
+            error!(error = %e, "Error during initial sync");
+            last_error.record(None, &e);
+        }
+
+        let mut last_processed_block: Option<u64> = None;
+        // Starts due so blocks that failed before a restart are retried right after the initial sync
+        let mut last_failed_block_retry: Option<Instant> = None;
+
+        info!("Entering synchronization loop");
+        loop {
+            match client.get_status().await.and_then(|status| status.chain_height()) {
+                Ok(latest_height) => {
+
+                    match db::blocks::get_latest_height(&client.db_pool).await {
+                        Ok(indexed_height) => {
+                            let indexed_height = indexed_height.unwrap_or(0);
+                            let lag = latest_height as i64 - indexed_height;
+                            gauge!(telemetry::SYNC_LAG_BLOCKS).set(lag.max(0) as f64);
+
+                            if let Err(e) = StatsQueries::record_sync_status(
+                                &client.db_pool,
+                                latest_height as i64,
+                                indexed_height,
+                            ).await {
+                                warn!(error = %e, "Error recording sync status");
                             }
-                            last_processed_block = Some(latest_height);
+                        }
+                        Err(e) => {
+                            warn!(error = %e, "Error reading indexed height");
+                            last_error.record(None, &e);
                         }
                     }
-                    Err(e) => {
-                        println!("Error getting node status: {}", e);
+
+                    // Trail the tip so blocks are indexed once they are unlikely to be reorged out
+                    let confirmed_height = client.confirmed_height(latest_height);
+                    if confirmed_height > 0 && Some(confirmed_height) != last_processed_block {
+                        debug!(height = confirmed_height, latest_height, "Processing new block");
+                        if let Err(e) = client.fetch_blocks(confirmed_height, confirmed_height, 5).await {
+                            error!(height = confirmed_height, error = %e, "Error fetching block");
+                            last_error.record(Some(confirmed_height), &e);
+                        }
+                        last_processed_block = Some(confirmed_height);
                     }
                 }
+                // A node answering with garbage will not recover by polling it again
+                Err(e @ ClientError::Parse(_)) => {
+                    error!(error = %e, "Node returned an unparsable status, stopping indexer");
+                    last_error.record(None, &e);
+                    return Err(e);
+                }
+                Err(e) => {
+                    warn!(error = %e, "Error getting chain height from node status");
+                    last_error.record(None, &e);
+                }
+            }
+
+            let retry_due = last_failed_block_retry
+                .is_none_or(|retried| retried.elapsed() >= Duration::from_secs(FAILED_BLOCK_RETRY_INTERVAL_SECS));
+            if retry_due {
+                match client.retry_failed_blocks().await {
+                    Ok(0) => {}
+                    Ok(recovered) => info!(recovered, "Recovered previously failed blocks"),
+                    Err(e) => warn!(error = %e, "Error retrying failed blocks"),
+                }
+                last_failed_block_retry = Some(Instant::now());
             }
+
+            time::sleep(Duration::from_secs(POLL_INTERVAL_SECS)).await;
         }
     });
 
-    println!("All services started successfully - running indefinitely");
+    info!("All services started successfully - running indefinitely");
 
     tokio::select! {
         result = api_handle => {
             if let Err(e) = result {
-                println!("API server task failed: {}", e);
+                error!(error = %e, "API server task failed");
             } else {
-                println!("API server task completed unexpectedly");
+                error!("API server task completed unexpectedly");
             }
         },
         result = indexer_handle => {
-            if let Err(e) = result {
-                println!("Indexer task failed: {}", e);
-            } else {
-                println!("Indexer task completed unexpectedly");
+            match result {
+                Ok(Ok(())) if sync_mode != SyncMode::Follow => {
+                    info!(mode = ?sync_mode, "Run finished, exiting");
+                    telemetry::shutdown_tracing();
+                    return Ok(());
+                }
+                Ok(Ok(())) => error!("Indexer task completed unexpectedly"),
+                Ok(Err(e)) => error!(error = %e, "Indexer task failed"),
+                Err(e) => error!(error = %e, "Indexer task failed"),
             }
         }
     }
 
-    println!("One of the critical tasks has terminated unexpectedly - application will now exit");
+    error!("One of the critical tasks has terminated unexpectedly - application will now exit");
+    telemetry::shutdown_tracing();
     Err("Critical service terminated".into())
 }
@@ -0,0 +1,125 @@
+/*
+* Monetary amounts in base units.
+*
+* Amounts are carried as u128 from decoding to the API, stored as
+* NUMERIC(39,0) and exposed in JSON as decimal strings: base-unit amounts
+* exceed 2^53, above which floating point and JSON numbers lose precision.
+*/
+
+use std::fmt;
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::{
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef},
+    Decode, Encode, Postgres, Type,
+};
+
+/*
+* An exact amount in base units, serialized as a decimal string.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenAmount(pub u128);
+
+impl TokenAmount {
+    /*
+    * Returns whether the amount is zero.
+    */
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl From<u128> for TokenAmount {
+    fn from(amount: u128) -> Self {
+        Self(amount)
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for TokenAmount {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        value.parse().map(Self)
+    }
+}
+
+impl Serialize for TokenAmount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(|_| serde::de::Error::custom(format!("invalid decimal amount '{}'", value)))
+    }
+}
+
+impl Type<Postgres> for TokenAmount {
+    fn type_info() -> PgTypeInfo {
+        <BigDecimal as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <BigDecimal as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for TokenAmount {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        // Parsing the integer's own digits cannot fail
+        let value = BigDecimal::from_str(&self.0.to_string()).expect("integer is a valid decimal");
+        <BigDecimal as Encode<Postgres>>::encode(value, buf)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for TokenAmount {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let value = <BigDecimal as Decode<Postgres>>::decode(value)?;
+        if !value.is_integer() {
+            return Err(format!("amount {} is not a whole number of base units", value).into());
+        }
+        Ok(Self(value.with_scale(0).to_string().parse()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* 2^53 + 1, the first integer a double cannot represent */
+    const ABOVE_F64_PRECISION: u128 = (1 << 53) + 1;
+
+    #[test]
+    fn serializes_amounts_above_2_53_exactly() {
+        let amount = TokenAmount(ABOVE_F64_PRECISION);
+        let json = serde_json::to_string(&amount).unwrap();
+        assert_eq!(json, "\"9007199254740993\"");
+        assert_eq!(serde_json::from_str::<TokenAmount>(&json).unwrap(), amount);
+    }
+
+    #[test]
+    fn round_trips_the_largest_amount() {
+        let amount = TokenAmount(u128::MAX);
+        let json = serde_json::to_string(&amount).unwrap();
+        assert_eq!(json, format!("\"{}\"", u128::MAX));
+        assert_eq!(serde_json::from_str::<TokenAmount>(&json).unwrap(), amount);
+    }
+
+    #[test]
+    fn rejects_non_integer_strings() {
+        assert!(serde_json::from_str::<TokenAmount>("\"1.5\"").is_err());
+        assert!(serde_json::from_str::<TokenAmount>("\"-1\"").is_err());
+        assert!(serde_json::from_str::<TokenAmount>("12").is_err());
+    }
+}
@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
-use sqlx::FromRow;
+use sqlx::{postgres::PgRow, FromRow, Row};
 use utoipa::ToSchema;
+use super::amount::TokenAmount;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StoredBlock {
     /// Block height in the blockchain
     pub height: i64,
@@ -24,15 +25,81 @@ pub struct StoredBlock {
     /// Hash of the previous block (if available)
     pub previous_block_hash: Option<String>,
 
-    /// Total amount of tokens burned in this block
-    pub burn_amount: f64,
+    /// Total amount of tokens burned in this block, as a decimal string of base units
+    #[schema(value_type = String, example = "0")]
+    pub burn_amount: TokenAmount,
+
+    /// Size of the serialized block in bytes (absent for blocks indexed before it was tracked)
+    pub block_size_bytes: Option<i64>,
 
     /// Full block data in JSON format
     pub data: serde_json::Value,
 
+    /// Number of transactions per action type (absent until backfilled for older blocks)
+    pub action_counts: Option<serde_json::Value>,
+
+    /// Whether any transaction in the block is a swap or swap claim
+    pub has_swaps: bool,
+
+    /// Whether any transaction in the block is a plain or IBC transfer
+    pub has_transfers: bool,
+
     /// Timestamp when the block record was created in the indexer
     #[schema(value_type = String, format = "date-time", example = "2025-02-25T12:34:56Z")]
     pub created_at: DateTime<Utc>,
+
+    /// Timestamp when the block record was last overwritten (absent if it never was)
+    #[schema(value_type = Option<String>, format = "date-time", example = "2025-02-26T08:00:00Z")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/*
+* Resolves a block payload from its data and data_compressed columns.
+*
+* @param data JSONB payload, NULL when the block was stored compressed
+* @param compressed zstd-compressed JSON payload
+* @return The block payload as JSON, null when no payload was stored
+*/
+pub fn decode_block_data(
+    data: Option<serde_json::Value>,
+    compressed: Option<Vec<u8>>,
+) -> Result<serde_json::Value, sqlx::Error> {
+    match (data, compressed) {
+        (Some(data), _) => Ok(data),
+        (None, None) => Ok(serde_json::Value::Null),
+        (None, Some(compressed)) => {
+            let bytes = zstd::decode_all(compressed.as_slice())
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+            serde_json::from_slice(&bytes).map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        }
+    }
+}
+
+/*
+* Reads a block row, decompressing the payload when it was stored in
+* data_compressed instead of the JSONB data column.
+*/
+impl<'r> FromRow<'r, PgRow> for StoredBlock {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let data = decode_block_data(row.try_get("data")?, row.try_get("data_compressed")?)?;
+
+        Ok(Self {
+            height: row.try_get("height")?,
+            time: row.try_get("time")?,
+            hash: row.try_get("hash")?,
+            proposer_address: row.try_get("proposer_address")?,
+            tx_count: row.try_get("tx_count")?,
+            previous_block_hash: row.try_get("previous_block_hash")?,
+            burn_amount: row.try_get("burn_amount")?,
+            block_size_bytes: row.try_get("block_size_bytes")?,
+            data,
+            action_counts: row.try_get("action_counts")?,
+            has_swaps: row.try_get("has_swaps")?,
+            has_transfers: row.try_get("has_transfers")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
 }
 
 impl StoredBlock {
@@ -40,12 +107,16 @@ impl StoredBlock {
         BlockSummary {
             height: self.height,
             time: self.time,
-            tx_count: self.tx_count
+            tx_count: self.tx_count,
+            block_size_bytes: self.block_size_bytes,
+            has_transactions: self.tx_count > 0,
+            has_swaps: self.has_swaps,
+            has_transfers: self.has_transfers,
         }
     }
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
 pub struct BlockSummary {
     /* Block height */
     pub height: i64,
@@ -56,6 +127,52 @@ pub struct BlockSummary {
 
     /* Number of transactions */
     pub tx_count: i32,
+
+    /* Size of the serialized block in bytes */
+    pub block_size_bytes: Option<i64>,
+
+    /* Whether the block contains any transactions */
+    pub has_transactions: bool,
+
+    /* Whether the block contains a swap or swap claim */
+    pub has_swaps: bool,
+
+    /* Whether the block contains a plain or IBC transfer */
+    pub has_transfers: bool,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct FailedBlock {
+    /// Height of the block
+    pub height: i64,
+
+    /// Error of the most recent attempt
+    pub last_error: String,
+
+    /// Total number of attempts made
+    pub attempts: i32,
+
+    /// When the block was last attempted
+    pub last_attempt: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlockTxCount {
+    /* Block height */
+    pub height: i64,
+
+    /* Number of transactions in the block */
+    pub tx_count: i32,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct BlockTime {
+    /* Block height */
+    pub height: i64,
+
+    /* Block timestamp */
+    #[schema(value_type = String, format = "date-time", example = "2025-02-25T12:34:56Z")]
+    pub time: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -63,19 +180,37 @@ pub struct BlockList {
     /* Collection of block summaries */
     pub blocks: Vec<BlockSummary>,
 
-    /* Total count of blocks in the list */
+    /* Total count of blocks matching the query, not just this page */
     pub total_count: i64,
 }
 
 impl BlockList {
     /*
-    * Creates a new BlockList from a collection of block summaries.
+    * Creates a new BlockList from a page of block summaries.
     *
     * @param blocks Vector of BlockSummary objects to include
+    * @param total_count Total number of blocks matching the query
     * @return A new BlockList instance
     */
-    pub fn new(blocks: Vec<BlockSummary>) -> Self {
-        let total_count = blocks.len() as i64;
+    pub fn new(blocks: Vec<BlockSummary>, total_count: i64) -> Self {
         Self { blocks, total_count }
     }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BusiestBlockList {
+    /* Block summaries ordered by transaction count, most first */
+    pub blocks: Vec<BlockSummary>,
+}
+
+impl BusiestBlockList {
+    /*
+    * Creates a new BusiestBlockList from block summaries.
+    *
+    * @param blocks Vector of BlockSummary objects, most transactions first
+    * @return A new BusiestBlockList instance
+    */
+    pub fn new(blocks: Vec<BlockSummary>) -> Self {
+        Self { blocks }
+    }
 }
\ No newline at end of file
@@ -6,6 +6,7 @@
 * utilized for database interactions and API responses.
 */
 
+pub mod amount;
 pub mod block;
 pub mod transaction;
 pub mod stats;
@@ -1,6 +1,7 @@
 use serde::Serialize;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
+use super::amount::TokenAmount;
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct StatsResponse {
@@ -22,8 +23,14 @@ pub struct CurrentBlockStats {
     /// Time between blocks
     pub block_time: String,
 
-    /// Time since the latest block was received
+    /// Time since the latest block was produced, combining chain and indexer latency
     pub received_new: String,
+
+    /// Average seconds between blocks over the most recent blocks
+    pub avg_block_time_secs: Option<f64>,
+
+    /// Average seconds from a block being produced to it being indexed, over the most recent blocks
+    pub avg_index_delay_secs: Option<f64>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -40,11 +47,18 @@ pub struct TransactionStats {
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct BurnStats {
-    /// Total amount of tokens burned
+    /// Total amount of tokens burned, formatted for display
     pub amount: String,
 
+    /// Total amount of tokens burned at full precision, as a decimal string
+    #[schema(value_type = String, example = "42000000")]
+    pub amount_raw: TokenAmount,
+
     /// Historical burn data for charting
-    pub history: Vec<ChartPoint>,
+    pub history: Vec<BurnChartPoint>,
+
+    /// Whether any burn has been recorded; false while every stored block reports zero
+    pub burn_available: bool,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -56,6 +70,264 @@ pub struct ChartPoint {
     pub value: i64,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BurnChartPoint {
+    /// Date label for the data point
+    pub date: String,
+
+    /// Amount burned on that date in base units, as a decimal string
+    #[schema(value_type = String, example = "1250000")]
+    pub value: TokenAmount,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CumulativeBurn {
+    /// Running burn total at the end of each day labeled YYYY-MM-DD, oldest first
+    pub history: Vec<BurnChartPoint>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProposerStats {
+    /// Address of the validator who proposed the blocks
+    pub proposer_address: String,
+
+    /// Number of blocks proposed by this validator
+    pub blocks_proposed: i64,
+
+    /// Total number of transactions in blocks proposed by this validator
+    pub total_transactions: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProposerStatsList {
+    /// Proposer statistics ordered by blocks proposed
+    pub validators: Vec<ProposerStats>,
+
+    /// Total number of distinct proposers, not just those listed
+    pub total_count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProposerMatch {
+    /// Address of the matching proposer
+    pub proposer_address: String,
+
+    /// Number of blocks proposed by this validator
+    pub blocks_proposed: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProposerMatchList {
+    /// Proposers whose address starts with the prefix, ordered by blocks proposed
+    pub validators: Vec<ProposerMatch>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DailyTransactionCounts {
+    /// Transaction counts per day labeled YYYY-MM-DD, oldest first
+    pub history: Vec<ChartPoint>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TimezoneTransactionCounts {
+    /// IANA timezone the days are bucketed in
+    #[schema(example = "America/New_York")]
+    pub timezone: String,
+
+    /// Transactions so far today in the timezone
+    pub today: i64,
+
+    /// Transaction counts per local day labeled YYYY-MM-DD, oldest first
+    pub history: Vec<ChartPoint>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HeightBucket {
+    /// First block height in the bucket
+    pub start_height: i64,
+
+    /// Last block height in the bucket
+    pub end_height: i64,
+
+    /// Number of transactions in the bucket's blocks
+    pub tx_count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HeightBucketList {
+    /// Transaction counts per height bucket in ascending order
+    pub buckets: Vec<HeightBucket>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ActionCount {
+    /// Normalized action type, e.g. "ibc_transfer"
+    pub action_type: String,
+
+    /// Number of transactions of this type in the window
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ActionBreakdown {
+    /// Transaction counts per action type, most frequent first
+    pub actions: Vec<ActionCount>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ActionShare {
+    /// Normalized action type, e.g. "ibc_transfer"
+    pub action_type: String,
+
+    /// Number of transactions of this type in the window
+    pub count: i64,
+
+    /// Share of all transactions in the window, from 0 to 100
+    pub percentage: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ActionDistribution {
+    /// Transaction shares per action type, most frequent first
+    pub actions: Vec<ActionShare>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlockSizeStats {
+    /// Number of days ending today the statistics cover
+    pub days: i64,
+
+    /// Number of blocks in the window with a recorded size
+    pub blocks: i64,
+
+    /// Smallest block size in bytes
+    pub min_bytes: i64,
+
+    /// Largest block size in bytes
+    pub max_bytes: i64,
+
+    /// Average block size in bytes
+    pub avg_bytes: f64,
+
+    /// 95th percentile block size in bytes, interpolated
+    pub p95_bytes: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeeTotal {
+    /// Denomination the fees were paid in, "penumbra" for UM or the "passet1..." asset ID otherwise
+    pub denom: String,
+
+    /// Sum of the fees, as a decimal string of base units
+    #[schema(value_type = String, example = "1250000")]
+    pub amount: TokenAmount,
+
+    /// Number of transactions that paid a fee in this denomination
+    pub transactions: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TotalFees {
+    /// Number of days ending today the totals cover
+    pub days: i64,
+
+    /// Fee totals per denomination, largest first
+    pub totals: Vec<FeeTotal>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IndexerThroughput {
+    /// Number of blocks stored in the last minute
+    pub blocks_last_minute: u64,
+
+    /// Average blocks stored per second over the last minute
+    pub blocks_per_sec: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Coverage {
+    /// Lowest indexed block height, 0 when no blocks are indexed
+    pub first_indexed: i64,
+
+    /// Highest indexed block height, 0 when no blocks are indexed
+    pub latest_indexed: i64,
+
+    /// Chain height at the indexer's last node status check, 0 before the first check
+    pub chain_height: i64,
+
+    /// Number of indexed blocks
+    pub total_indexed: i64,
+
+    /// Heights between first_indexed and latest_indexed that are not indexed
+    pub missing_count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NetworkOverview {
+    /// Highest indexed block height
+    pub latest_height: i64,
+
+    /// Average seconds between blocks over the most recent blocks
+    pub avg_block_time_secs: Option<f64>,
+
+    /// Total number of indexed transactions
+    pub total_transactions: i64,
+
+    /// Number of transactions indexed today
+    pub today_transactions: i64,
+
+    /// Total amount of tokens burned, as a decimal string
+    #[schema(value_type = String, example = "42000000")]
+    pub total_burn: TokenAmount,
+
+    /// Number of distinct proposers in the last 24 hours
+    pub active_proposers: i64,
+
+    /// Blocks the indexer was behind the node at the last status check, None before the first check
+    pub sync_lag: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncStatusPoint {
+    /// Time the node status was observed
+    #[schema(value_type = String, format = "date-time")]
+    pub observed_at: DateTime<Utc>,
+
+    /// Latest block height reported by the node
+    pub chain_height: i64,
+
+    /// Highest block height stored by the indexer
+    pub indexed_height: i64,
+
+    /// Number of blocks the indexer was behind the node
+    pub lag: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncHistory {
+    /// Sync status snapshots in chronological order
+    pub history: Vec<SyncStatusPoint>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlockTimeAnomaly {
+    /// Height of the block that arrived late
+    pub height: i64,
+
+    /// Seconds between the previous block and this one
+    pub gap_secs: f64,
+
+    /// Block timestamp
+    #[schema(value_type = String, format = "date-time")]
+    pub time: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BlockTimeAnomalyList {
+    /// Blocks whose gap exceeded the threshold, newest first
+    pub anomalies: Vec<BlockTimeAnomaly>,
+}
+
 #[derive(Debug)]
 pub struct BlockTimingInfo {
     pub height: i64,
@@ -77,11 +349,19 @@ impl StatsResponse {
 }
 
 impl CurrentBlockStats {
-    pub fn new(height: i64, block_time: String, received_new: String) -> Self {
+    pub fn new(
+        height: i64,
+        block_time: String,
+        received_new: String,
+        avg_block_time_secs: Option<f64>,
+        avg_index_delay_secs: Option<f64>,
+    ) -> Self {
         Self {
             height,
             block_time,
             received_new,
+            avg_block_time_secs,
+            avg_index_delay_secs,
         }
     }
 }
@@ -97,10 +377,80 @@ impl TransactionStats {
 }
 
 impl BurnStats {
-    pub fn new(amount: f64, history: Vec<ChartPoint>) -> Self {
+    pub fn new(amount: TokenAmount, history: Vec<BurnChartPoint>) -> Self {
         Self {
-            amount: format!("{} UM", amount.round() as i64),
+            amount: format!("{} UM", amount),
+            amount_raw: amount,
             history,
+            burn_available: !amount.is_zero(),
         }
     }
-}
\ No newline at end of file
+}
+
+impl ProposerStatsList {
+    pub fn new(validators: Vec<ProposerStats>, total_count: i64) -> Self {
+        Self { validators, total_count }
+    }
+}
+
+impl ProposerMatchList {
+    pub fn new(validators: Vec<ProposerMatch>) -> Self {
+        Self { validators }
+    }
+}
+
+impl DailyTransactionCounts {
+    pub fn new(history: Vec<ChartPoint>) -> Self {
+        Self { history }
+    }
+}
+
+impl TimezoneTransactionCounts {
+    pub fn new(timezone: String, history: Vec<ChartPoint>) -> Self {
+        // The last bucket is the current local day
+        let today = history.last().map_or(0, |point| point.value);
+        Self { timezone, today, history }
+    }
+}
+
+impl CumulativeBurn {
+    pub fn new(history: Vec<BurnChartPoint>) -> Self {
+        Self { history }
+    }
+}
+
+impl TotalFees {
+    pub fn new(days: i64, totals: Vec<FeeTotal>) -> Self {
+        Self { days, totals }
+    }
+}
+
+impl ActionBreakdown {
+    pub fn new(actions: Vec<ActionCount>) -> Self {
+        Self { actions }
+    }
+}
+
+impl ActionDistribution {
+    pub fn new(actions: Vec<ActionShare>) -> Self {
+        Self { actions }
+    }
+}
+
+impl HeightBucketList {
+    pub fn new(buckets: Vec<HeightBucket>) -> Self {
+        Self { buckets }
+    }
+}
+
+impl BlockTimeAnomalyList {
+    pub fn new(anomalies: Vec<BlockTimeAnomaly>) -> Self {
+        Self { anomalies }
+    }
+}
+
+impl SyncHistory {
+    pub fn new(history: Vec<SyncStatusPoint>) -> Self {
+        Self { history }
+    }
+}
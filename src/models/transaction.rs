@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 use utoipa::ToSchema;
+use super::amount::TokenAmount;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Transaction {
@@ -21,12 +22,26 @@ pub struct Transaction {
     /// Type of action performed in this transaction
     pub action_type: String,
 
-    /// Amount involved in the transaction (if applicable)
-    pub amount: Option<f64>,
+    /// Amount involved in the transaction (if applicable), as a decimal string of base units
+    #[schema(value_type = Option<String>, example = "1000000")]
+    pub amount: Option<TokenAmount>,
+
+    /// Denomination of the amount, "penumbra" for UM
+    pub denom: Option<String>,
+
+    /// Fee paid by the transaction, as a decimal string of base units
+    #[schema(value_type = Option<String>, example = "1000")]
+    pub fee_amount: Option<TokenAmount>,
+
+    /// Denomination of the fee, "penumbra" for UM or the "passet1..." asset ID otherwise
+    pub fee_denom: Option<String>,
 
     /// Raw transaction data
     pub data: String,
 
+    /// Action-specific details, such as IBC channels for transfers
+    pub metadata: Option<serde_json::Value>,
+
     /// Timestamp when the transaction was indexed
     #[schema(value_type = String, format = "date-time", example = "2025-02-25T12:34:56Z")]
     pub created_at: DateTime<Utc>,
@@ -38,12 +53,16 @@ impl Transaction {
             tx_hash: self.tx_hash.clone(),
             block_height: self.block_height,
             action_type: self.action_type.clone(),
-            amount: self.amount
+            amount: self.amount,
+            denom: self.denom.clone(),
+            fee_amount: self.fee_amount,
+            fee_denom: self.fee_denom.clone(),
+            metadata: self.metadata.clone(),
         }
     }
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct TransactionSummary {
     /// Unique transaction hash
     pub tx_hash: String,
@@ -54,8 +73,22 @@ pub struct TransactionSummary {
     /// Type of action performed in this transaction
     pub action_type: String,
 
-    /// Amount involved in the transaction (if applicable)
-    pub amount: Option<f64>,
+    /// Amount involved in the transaction (if applicable), as a decimal string of base units
+    #[schema(value_type = Option<String>, example = "1000000")]
+    pub amount: Option<TokenAmount>,
+
+    /// Denomination of the amount, "penumbra" for UM
+    pub denom: Option<String>,
+
+    /// Fee paid by the transaction, as a decimal string of base units
+    #[schema(value_type = Option<String>, example = "1000")]
+    pub fee_amount: Option<TokenAmount>,
+
+    /// Denomination of the fee, "penumbra" for UM or the "passet1..." asset ID otherwise
+    pub fee_denom: Option<String>,
+
+    /// Action-specific details, such as IBC channels for transfers
+    pub metadata: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -63,13 +96,67 @@ pub struct TransactionList {
     /// List of transaction summaries
     pub transactions: Vec<TransactionSummary>,
 
-    /// Total count of transactions in the response
+    /// Total count of transactions matching the query, not just this page
     pub total_count: i64,
 }
 
 impl TransactionList {
-    pub fn new(transactions: Vec<TransactionSummary>) -> Self {
-        let total_count = transactions.len() as i64;
+    pub fn new(transactions: Vec<TransactionSummary>, total_count: i64) -> Self {
         Self { transactions, total_count }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DecodedTransaction {
+    /// Unique transaction hash
+    pub tx_hash: String,
+
+    /// Block height where this transaction was included
+    pub block_height: i64,
+
+    /// Decoded actions in transaction order, each tagged with its "type"
+    #[schema(value_type = Vec<Object>)]
+    pub actions: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct NetDelegation {
+    /// Bech32m identity key of the validator, "penumbravalid1..."
+    pub validator_identity: String,
+
+    /// UM delegated minus UM undelegated, as a decimal string of base units
+    #[schema(example = "1000000")]
+    pub net_amount: String,
+
+    /// Number of delegations to the validator
+    pub delegations: i64,
+
+    /// Number of undelegations from the validator
+    pub undelegations: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NetDelegationList {
+    /// Validators ordered by net delegated amount, largest first
+    pub validators: Vec<NetDelegation>,
+}
+
+impl NetDelegationList {
+    pub fn new(validators: Vec<NetDelegation>) -> Self {
+        Self { validators }
+    }
+}
+
+/*
+* Height ordering of a transaction list.
+*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Oldest blocks first
+    Asc,
+
+    /// Newest blocks first
+    #[default]
+    Desc,
+}
@@ -0,0 +1,136 @@
+/*
+* Logging and Prometheus metrics for the Penumbra indexer.
+*
+* Installs the tracing subscriber, optionally exporting spans over OTLP,
+* defines the metric names recorded by the block synchronizer and
+* installs the global recorder whose output is served on /metrics.
+*/
+
+use std::env;
+use metrics_exporter_prometheus::{BuildError, Matcher, PrometheusBuilder, PrometheusHandle};
+use opentelemetry::{trace::TraceError, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace::{self as sdktrace, Tracer}, Resource};
+use tracing::{info, warn};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+/* Log filter used when RUST_LOG is unset or invalid */
+const DEFAULT_LOG_FILTER: &str = "info";
+
+/* LOG_FORMAT value selecting newline-delimited JSON log output */
+const JSON_LOG_FORMAT: &str = "json";
+
+/* Service name attached to exported traces */
+const OTEL_SERVICE_NAME: &str = "penumbra-indexer";
+
+/* Counter of blocks successfully stored */
+pub const BLOCKS_INDEXED_TOTAL: &str = "blocks_indexed_total";
+
+/* Counter of transactions successfully stored */
+pub const TRANSACTIONS_INDEXED_TOTAL: &str = "transactions_indexed_total";
+
+/* Counter of stored blocks overwritten by a later write of the same height */
+pub const BLOCKS_OVERWRITTEN_TOTAL: &str = "blocks_overwritten_total";
+
+/* Counter of failed block fetches from the RPC node */
+pub const BLOCK_FETCH_ERRORS_TOTAL: &str = "block_fetch_errors_total";
+
+/* Counter of blocks whose reported hash does not match their header */
+pub const BLOCK_HASH_MISMATCHES_TOTAL: &str = "block_hash_mismatches_total";
+
+/* Gauge of chain height minus indexed height */
+pub const SYNC_LAG_BLOCKS: &str = "sync_lag_blocks";
+
+/* Gauge of RPC requests currently holding a concurrency permit */
+pub const RPC_REQUESTS_IN_FLIGHT: &str = "rpc_requests_in_flight";
+
+/* Histogram of time spent fetching and storing a single block */
+pub const BLOCK_PROCESSING_SECONDS: &str = "block_processing_seconds";
+
+/* Histogram buckets for block processing latency in seconds */
+const BLOCK_PROCESSING_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/*
+* Installs the global tracing subscriber.
+*
+* Emits JSON when LOG_FORMAT=json and human-readable text otherwise.
+* Verbosity follows RUST_LOG, defaulting to info, and the active filter
+* is logged once at startup. When OTEL_EXPORTER_OTLP_ENDPOINT is set,
+* spans are also exported to that collector over OTLP/gRPC; without it
+* no exporter is created. Must be called from within the Tokio runtime.
+*/
+pub fn init_logging() {
+    let (filter, source) = match EnvFilter::try_from_default_env() {
+        Ok(filter) => (filter, "RUST_LOG"),
+        Err(_) => (EnvFilter::new(DEFAULT_LOG_FILTER), "default"),
+    };
+    let directives = filter.to_string();
+
+    let otlp_endpoint = env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok().filter(|endpoint| !endpoint.is_empty());
+    let (otel_layer, otel_error) = match otlp_endpoint.as_deref().map(otlp_tracer) {
+        Some(Ok(tracer)) => (Some(tracing_opentelemetry::layer().with_tracer(tracer)), None),
+        Some(Err(e)) => (None, Some(e)),
+        None => (None, None),
+    };
+
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(otel_layer);
+
+    match env::var("LOG_FORMAT") {
+        Ok(format) if format.eq_ignore_ascii_case(JSON_LOG_FORMAT) => registry.with(fmt::layer().json()).init(),
+        _ => registry.with(fmt::layer()).init(),
+    }
+
+    info!(filter = %directives, source, "Logging initialized");
+    match (otlp_endpoint, otel_error) {
+        (Some(endpoint), None) => info!(endpoint = %endpoint, "Exporting traces over OTLP"),
+        (Some(endpoint), Some(e)) => warn!(endpoint = %endpoint, error = %e, "Failed to set up OTLP trace export, continuing without it"),
+        (None, _) => {}
+    }
+}
+
+/*
+* Builds a tracer whose spans are batched and exported over OTLP/gRPC.
+*
+* @param endpoint Collector endpoint, e.g. "http://localhost:4317"
+* @return The tracer, also installed as the global tracer provider
+*/
+fn otlp_tracer(endpoint: &str) -> Result<Tracer, TraceError> {
+    opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint)
+        )
+        .with_trace_config(
+            sdktrace::config().with_resource(Resource::new(vec![
+                KeyValue::new("service.name", OTEL_SERVICE_NAME),
+            ]))
+        )
+        .install_batch(runtime::Tokio)
+}
+
+/*
+* Flushes spans still queued for OTLP export.
+*
+* Does nothing when trace export is disabled.
+*/
+pub fn shutdown_tracing() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+/*
+* Installs the global Prometheus recorder.
+*
+* @return Handle used to render the current metrics snapshot
+*/
+pub fn install_recorder() -> Result<PrometheusHandle, BuildError> {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full(BLOCK_PROCESSING_SECONDS.to_string()),
+            BLOCK_PROCESSING_BUCKETS,
+        )?
+        .install_recorder()
+}